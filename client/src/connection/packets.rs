@@ -2,9 +2,10 @@ use std::fmt::Binary;
 
 use gloo::console::console;
 use gloo_utils::window;
-use shared::{connection::packets::{ClanPacketOpcode, ServerboundPackets}, game::{body::BodyIdentityIds, entity::{ClanInformation, InputFlags, Notification}, turret::TurretIdentityIds}, normalize_angle, utils::{codec::BinaryCodec, color::Color, consts::ARENA_SIZE, vec2::Vector2D}};
+use shared::{connection::packets::{ClanPacketOpcode, GameplayEventType, InventoryPacketOpcode, ServerboundPackets, SpectatePacketOpcode}, game::{body::BodyIdentityIds, entity::{ClanInformation, ClanStanding, InputFlags, Marker, Notification, SpectateMode}, gear::{Gear, Inventory}, turret::TurretIdentityIds, world::{GameMode, Wall, WorldInfo}, profile::{Profile, Run, TankRecord}, highscores::{HighScorePeriod, RecordedRun}}, utils::{codec::BinaryCodec, color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
+use strum::IntoEnumIterator;
 
-use crate::{game::entity::base::{Entity, HealthState}, storage_set, world::{get_world, World}};
+use crate::{game::entity::base::{DeathSummary, Entity, HealthState, InventoryState, MarkerDisplay, SpectatorState, TradeDisplay}, rendering::phases::GamePhase, storage_get, storage_set, world::{get_world, World}};
 
 pub fn form_spawn_packet(
     name: String
@@ -21,7 +22,8 @@ pub fn form_spawn_packet(
 
 pub fn form_input_packet(
     flags: InputFlags,
-    mouse: Vector2D
+    mouse: Vector2D,
+    sequence: u32
 ) -> BinaryCodec {
     let mut codec = BinaryCodec::new();
     codec.encode_varuint(ServerboundPackets::Input as u64);
@@ -29,6 +31,7 @@ pub fn form_input_packet(
     codec.encode_varuint(flags.get_value() as u64);
     codec.encode_f32(mouse.x);
     codec.encode_f32(mouse.y);
+    codec.encode_varuint(sequence as u64);
 
     codec
 }
@@ -94,12 +97,127 @@ pub fn form_clan_packet_join(id: u32) -> BinaryCodec {
     codec
 }
 
+pub fn form_clan_packet_leave() -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Clan as u64);
+    codec.encode_varuint(ClanPacketOpcode::Leave as u64);
+
+    codec
+}
+
+/// Forms a packet which acts on a member of the self entity's clan, such as by kicking or accepting it.
+pub fn form_clan_packet_member(opcode: ClanPacketOpcode, id: u32) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Clan as u64);
+    codec.encode_varuint(opcode as u64);
+
+    codec.encode_varuint(id as u64);
+
+    codec
+}
+
+pub fn form_clan_packet_distress() -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Clan as u64);
+    codec.encode_varuint(ClanPacketOpcode::Distress as u64);
+
+    codec
+}
+
+pub fn form_inventory_packet_item(opcode: InventoryPacketOpcode, index: usize) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Inventory as u64);
+    codec.encode_varuint(opcode as u64);
+
+    codec.encode_varuint(index as u64);
+
+    codec
+}
+
+pub fn form_inventory_packet_trade_request(id: u32) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Inventory as u64);
+    codec.encode_varuint(InventoryPacketOpcode::TradeRequest as u64);
+
+    codec.encode_varuint(id as u64);
+
+    codec
+}
+
+pub fn form_inventory_packet_trade_offer(indices: &[usize]) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Inventory as u64);
+    codec.encode_varuint(InventoryPacketOpcode::TradeOffer as u64);
+
+    codec.encode_varuint(indices.len() as u64);
+    for &index in indices.iter() {
+        codec.encode_varuint(index as u64);
+    }
+
+    codec
+}
+
+pub fn form_inventory_packet_trade(opcode: InventoryPacketOpcode) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Inventory as u64);
+    codec.encode_varuint(opcode as u64);
+
+    codec
+}
+
+pub fn form_spectate_packet(opcode: SpectatePacketOpcode) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Spectate as u64);
+    codec.encode_varuint(opcode as u64);
+
+    codec
+}
+
+/// Forms a packet which pans a spectator's camera along its movement inputs.
+pub fn form_spectate_packet_pan(inputs: InputFlags) -> BinaryCodec {
+    let mut codec = form_spectate_packet(SpectatePacketOpcode::Pan);
+    codec.encode_varuint(inputs.get_value() as u64);
+
+    codec
+}
+
+/// Forms a packet which identifies the player by the profile token it remembers.
+pub fn form_profile_packet() -> BinaryCodec {
+    let token = storage_get!("profile_token")
+        .and_then(|token| token.parse::<u64>().ok())
+        .unwrap_or_default();
+
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Profile as u64);
+    codec.encode_varuint(token);
+
+    codec
+}
+
+pub fn form_high_scores_packet() -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::HighScores as u64);
+
+    codec
+}
+
+/// Forms a packet which reclaims the tank left behind after a disconnect.
+pub fn form_resume_packet(token: u64) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ServerboundPackets::Resume as u64);
+    codec.encode_varuint(token);
+
+    codec
+}
+
 pub fn handle_update_packet(
     world: &mut World,
     mut codec: BinaryCodec
 ) {
-    // parse world information later
-    world.game.arena_size = ARENA_SIZE;
+    // CLOCK //
+    let tick = codec.decode_varuint().unwrap();
+    let timestamp = codec.decode_f64().unwrap();
+    world.game.clock.update(tick, timestamp, window().performance().unwrap().now());
 
     // CLANS //
     let mut clans: Vec<ClanInformation> = Vec::with_capacity(codec.decode_varuint().unwrap() as usize);
@@ -114,16 +232,22 @@ pub fn handle_update_packet(
             pending_members: Vec::with_capacity(codec.decode_varuint().unwrap() as usize)
         };
         
-        let old_clan = world.game.clan_state.clans.get(clan.id as usize);
+        let old_clan = world.game.clan_state.clans.iter().find(|old_clan| old_clan.id == clan.id);
 
         for _ in 0..clan.members.capacity() {
             let id = codec.decode_varuint().unwrap() as u32;
             clan.members.push(id);
+            if codec.decode_bool().unwrap() {
+                world.game.clan_state.names.insert(id, codec.decode_string().unwrap());
+            }
         }
 
         for _ in 0..clan.pending_members.capacity() {
             let id = codec.decode_varuint().unwrap() as u32;
             clan.pending_members.push(id);
+            if codec.decode_bool().unwrap() {
+                world.game.clan_state.names.insert(id, codec.decode_string().unwrap());
+            }
 
             if let Some(old_clan) = old_clan && world.game.self_entity.id == clan.owner && !old_clan.pending_members.contains(&id) {
                 console!("KILL?!".to_string());
@@ -141,8 +265,28 @@ pub fn handle_update_packet(
         clans.push(clan);
     }
 
+    let self_id = world.game.self_entity.id;
+    world.game.self_entity.display.pending_clan_id = clans.iter()
+        .find(|clan| clan.pending_members.contains(&self_id))
+        .map(|clan| clan.id);
+
+    world.game.clan_state.names.retain(|id, _| clans.iter().any(|clan| clan.members.contains(id) || clan.pending_members.contains(id)));
     world.game.clan_state.clans = clans;
 
+    // SPECTATING //
+    if codec.decode_bool().unwrap() {
+        let mode: SpectateMode = (codec.decode_varuint().unwrap() as u8).try_into().unwrap();
+        let spectated = Some(codec.decode_varuint().unwrap() as u32).filter(|&id| id != 0);
+
+        let spectator = world.game.spectator.get_or_insert_with(SpectatorState::default);
+        spectator.mode = mode;
+        spectator.spectated = spectated;
+
+        world.renderer.change_phase(GamePhase::Spectate);
+    } else if world.game.spectator.take().is_some() {
+        world.renderer.change_phase(GamePhase::Home(Box::default()));
+    }
+
     // ENTITIES //
     Entity::parse_census(world, &mut codec, true);
 
@@ -159,6 +303,8 @@ pub fn handle_update_packet(
             HealthState::Dead => deletion_ids.push(id),
             _ => entity.stats.health_state = HealthState::Dying
         }
+
+        entity.physics.snapshots.mark_stale();
     }
 
     world.game.surroundings.retain(|id, _| !deletion_ids.contains(id));
@@ -173,10 +319,6 @@ pub fn handle_notification_packet(
         let message = codec.decode_string().unwrap();
         let (r, g, b) = (codec.decode_varuint().unwrap(), codec.decode_varuint().unwrap(), codec.decode_varuint().unwrap());
         let lifetime = codec.decode_varuint().unwrap();
-        
-        if message.contains("You killed") {
-            world.game.self_entity.display.kills += 1;
-        }
 
         world.game.self_entity.display.notifications.push(Notification {
             message,
@@ -187,6 +329,159 @@ pub fn handle_notification_packet(
     }
 }
 
+pub fn handle_inventory_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    let decode_gear = |codec: &mut BinaryCodec| Gear {
+        kind: (codec.decode_varuint().unwrap() as u8).try_into().unwrap(),
+        rarity: (codec.decode_varuint().unwrap() as u8).try_into().unwrap()
+    };
+
+    // ITEMS //
+    let mut inventory = Inventory::default();
+    for _ in 0..codec.decode_varuint().unwrap() {
+        inventory.items.push(decode_gear(&mut codec));
+    }
+
+    for slot in inventory.equipped.iter_mut() {
+        if codec.decode_bool().unwrap() {
+            *slot = Some(decode_gear(&mut codec));
+        }
+    }
+
+    // REQUESTS //
+    let mut requests = vec![];
+    for _ in 0..codec.decode_varuint().unwrap() {
+        requests.push((codec.decode_varuint().unwrap() as u32, codec.decode_string().unwrap()));
+    }
+
+    let requested = codec.decode_varint().unwrap();
+
+    // TRADE //
+    let trade = if codec.decode_bool().unwrap() {
+        let mut trade = TradeDisplay {
+            partner: codec.decode_varuint().unwrap() as u32,
+            partner_name: codec.decode_string().unwrap(),
+            ..Default::default()
+        };
+
+        for _ in 0..codec.decode_varuint().unwrap() {
+            trade.offer.push(codec.decode_varuint().unwrap() as usize);
+        }
+
+        for _ in 0..codec.decode_varuint().unwrap() {
+            trade.partner_offer.push(decode_gear(&mut codec));
+        }
+
+        trade.confirmed = codec.decode_bool().unwrap();
+        trade.partner_confirmed = codec.decode_bool().unwrap();
+
+        Some(trade)
+    } else {
+        None
+    };
+
+    world.game.inventory = InventoryState {
+        inventory,
+        requests,
+        requested: (requested != -1).then_some(requested as u32),
+        trade
+    };
+}
+
+pub fn handle_events_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    let self_id = world.game.self_entity.id;
+
+    for _ in 0..codec.decode_varuint().unwrap() {
+        let event: GameplayEventType = (codec.decode_varuint().unwrap() as u8).try_into().unwrap();
+        match event {
+            GameplayEventType::TurretFired => {
+                let id = codec.decode_varuint().unwrap() as u32;
+                let turret = codec.decode_varuint().unwrap() as usize;
+
+                let entity = if id == self_id { Some(&mut world.game.self_entity) } else { world.game.surroundings.get_mut(&id) };
+                if let Some(entity) = entity && let Some(length) = entity.display.turret_lengths.get_mut(turret) {
+                    length.target = 0.75;
+                }
+            },
+            GameplayEventType::Hit => {
+                let _attacker = codec.decode_varint().unwrap();
+                let victim = codec.decode_varuint().unwrap() as u32;
+                let damage = codec.decode_f32().unwrap();
+
+                let position = if victim == self_id {
+                    Some(world.game.self_entity.physics.position.value)
+                } else {
+                    world.game.surroundings.get(&victim).map(|entity| entity.physics.position.value)
+                };
+
+                if let Some(position) = position {
+                    world.game.add_damage_number(victim, position, damage, victim == self_id);
+                }
+            },
+            GameplayEventType::LevelUp => {
+                let _level = codec.decode_varuint().unwrap();
+                world.sounds.get_mut_sound("dialogue_normal").play();
+            },
+            GameplayEventType::UpgradeAvailable => world.sounds.get_mut_sound("button_click").play(),
+            GameplayEventType::ObjectiveCaptured => {
+                let id = codec.decode_varuint().unwrap() as u32;
+
+                let message = if id == self_id {
+                    world.sounds.get_mut_sound("dialogue_normal").play();
+                    "You captured a radiant orb!".to_string()
+                } else if let Some(entity) = world.game.surroundings.get(&id) {
+                    format!("{} captured a radiant orb.", entity.display.name)
+                } else {
+                    continue;
+                };
+
+                world.game.self_entity.display.notifications.push(Notification {
+                    message,
+                    color: Color::ORANGE,
+                    lifetime: 150,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+pub fn handle_death_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    let mut summary = DeathSummary::default();
+
+    // KILLER //
+    if codec.decode_bool().unwrap() {
+        summary.killer = Some(codec.decode_string().unwrap());
+
+        if codec.decode_bool().unwrap() {
+            let body: BodyIdentityIds = (codec.decode_varuint().unwrap() as usize).try_into().unwrap();
+            let turret: TurretIdentityIds = (codec.decode_varuint().unwrap() as usize).try_into().unwrap();
+            summary.killer_tank = Some((body, turret));
+        }
+    }
+
+    // DAMAGE TAKEN //
+    for _ in 0..codec.decode_varuint().unwrap() {
+        summary.damage_taken.push((codec.decode_string().unwrap(), codec.decode_f32().unwrap()));
+    }
+
+    // STATISTICS //
+    summary.kills = codec.decode_varuint().unwrap() as usize;
+    summary.level = codec.decode_varuint().unwrap() as usize;
+    summary.score = codec.decode_varuint().unwrap() as usize;
+    summary.time_alive = codec.decode_varuint().unwrap() as f64 * 1000.0 / world.game.world_info.tick_rate as f64;
+
+    world.game.death_summary = summary;
+}
+
 pub fn handle_server_info_packet(
     world: &mut World,
     mut codec: BinaryCodec
@@ -194,6 +489,9 @@ pub fn handle_server_info_packet(
     let mspt = codec.decode_f32().unwrap();
     world.connection.mspt.target = mspt;
 
+    world.game.leaderboard.players = codec.decode_varuint().unwrap() as usize;
+    world.game.leaderboard.spectators = codec.decode_varuint().unwrap() as usize;
+
     let leaderboard_length = codec.decode_varuint().unwrap() as usize;
     world.game.leaderboard.entries = Vec::with_capacity(leaderboard_length);
 
@@ -206,11 +504,133 @@ pub fn handle_server_info_packet(
         world.game.leaderboard.entries.push((score, name, body_identity, turret_identity));
     }
 
-    let angle = codec.decode_f32().unwrap();
+    let markers_length = codec.decode_varuint().unwrap() as usize;
+    let mut markers = Vec::with_capacity(markers_length);
 
-    if angle != -13.0 {
-        world.game.leaderboard.angle.target = normalize_angle!(angle + std::f32::consts::PI);
+    for _ in 0..markers_length {
+        markers.push(Marker {
+            kind: (codec.decode_varuint().unwrap() as u8).try_into().unwrap(),
+            id: codec.decode_varuint().unwrap() as u32,
+            position: Vector2D::new(codec.decode_f32().unwrap(), codec.decode_f32().unwrap()),
+            color: Color(codec.decode_varuint().unwrap() as u8, codec.decode_varuint().unwrap() as u8, codec.decode_varuint().unwrap() as u8)
+        });
     }
 
-    world.game.leaderboard.arrow_opacity.target = if angle == -13.0 { 0.0 } else { 1.0 };
+    for display in world.game.markers.iter_mut() {
+        display.opacity.target = 0.0;
+    }
+
+    for marker in markers {
+        match world.game.markers.iter_mut().find(|display| display.marker.kind == marker.kind && display.marker.id == marker.id) {
+            Some(display) => {
+                display.marker = marker;
+                display.position.target = marker.position;
+                display.opacity.target = 1.0;
+            },
+            None => {
+                let mut opacity = Interpolatable::new(0.0);
+                opacity.target = 1.0;
+
+                world.game.markers.push(MarkerDisplay { marker, position: Interpolatable::new(marker.position), opacity });
+            }
+        }
+    }
+
+    let clans_length = codec.decode_varuint().unwrap() as usize;
+    world.game.leaderboard.clans = Vec::with_capacity(clans_length);
+
+    for _ in 0..clans_length {
+        world.game.leaderboard.clans.push(ClanStanding {
+            name: codec.decode_string().unwrap(),
+            members: codec.decode_varuint().unwrap() as usize,
+            score: codec.decode_varuint().unwrap() as usize,
+            kills: codec.decode_varuint().unwrap() as usize,
+            war_kills: codec.decode_varuint().unwrap() as usize
+        });
+    }
+
+    world.game.leaderboard.war_remaining = codec.decode_bool().unwrap()
+        .then(|| codec.decode_varuint().unwrap() as f64 * 1000.0 / world.game.world_info.tick_rate as f64);
+}
+
+pub fn handle_profile_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    let token = codec.decode_varuint().unwrap();
+    storage_set!("profile_token", &token.to_string());
+
+    let kills = codec.decode_varuint().unwrap() as usize;
+    let playtime = codec.decode_varuint().unwrap();
+    let runs = codec.decode_varuint().unwrap() as usize;
+
+    let best_scores = (0..codec.decode_varuint().unwrap())
+        .map(|_| TankRecord {
+            body: (codec.decode_varuint().unwrap() as usize).try_into().unwrap(),
+            turret: (codec.decode_varuint().unwrap() as usize).try_into().unwrap(),
+            score: codec.decode_varuint().unwrap() as usize
+        })
+        .collect();
+
+    let achievements = (0..codec.decode_varuint().unwrap())
+        .map(|_| codec.decode_string().unwrap())
+        .collect();
+
+    world.game.profile = Some(Profile { best_scores, kills, playtime, runs, achievements });
+}
+
+pub fn handle_high_scores_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    world.game.high_scores.tables = HighScorePeriod::iter()
+        .map(|_| (0..codec.decode_varuint().unwrap())
+            .map(|_| RecordedRun {
+                run: Run {
+                    name: codec.decode_string().unwrap(),
+                    body: (codec.decode_varuint().unwrap() as usize).try_into().unwrap(),
+                    turret: (codec.decode_varuint().unwrap() as usize).try_into().unwrap(),
+                    score: codec.decode_varuint().unwrap() as usize,
+                    kills: codec.decode_varuint().unwrap() as usize,
+                    duration: codec.decode_varuint().unwrap(),
+                    achievements: vec![]
+                },
+                finished_at: codec.decode_varuint().unwrap()
+            })
+            .collect())
+        .collect();
+}
+
+/// Remembers the token which reclaims the player's tank, or sends the player
+/// home if the tank it tried to reclaim is gone.
+pub fn handle_session_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    world.connection.resume_token = Some(codec.decode_varuint().unwrap()).filter(|&token| token != 0);
+
+    if world.connection.resume_token.is_none() && matches!(world.renderer.phase, GamePhase::Game) {
+        world.game.self_entity.stats.has_spawned = false;
+        world.renderer.change_phase(GamePhase::Home(Box::default()));
+    }
+}
+
+pub fn handle_world_info_packet(
+    world: &mut World,
+    mut codec: BinaryCodec
+) {
+    let arena_size = codec.decode_f32().unwrap();
+    let mode: GameMode = (codec.decode_varuint().unwrap() as u8).try_into().unwrap();
+    let tick_rate = codec.decode_varuint().unwrap();
+    let teams = codec.decode_varuint().unwrap() as usize;
+
+    let walls = (0..codec.decode_varuint().unwrap())
+        .map(|_| Wall {
+            position: Vector2D::new(codec.decode_f32().unwrap(), codec.decode_f32().unwrap()),
+            width: codec.decode_f32().unwrap(),
+            height: codec.decode_f32().unwrap()
+        })
+        .collect();
+
+    world.game.world_info = WorldInfo { arena_size, mode, tick_rate, teams, walls };
 }
\ No newline at end of file
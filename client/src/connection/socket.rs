@@ -1,4 +1,4 @@
-use gloo::console::console;
+use gloo::{console::console, timers::callback::Timeout};
 use gloo_utils::window;
 use shared::{connection::packets::{ClientboundPackets, ServerboundPackets}, utils::{codec::BinaryCodec, interpolatable::Interpolatable}};
 use wasm_bindgen_futures::spawn_local;
@@ -6,7 +6,7 @@ use web_sys::{js_sys::{ArrayBuffer, Uint8Array}, wasm_bindgen::{prelude::Closure
 
 use crate::world::{get_world, World};
 
-use super::packets::{handle_notification_packet, handle_server_info_packet, handle_update_packet};
+use super::packets::{form_profile_packet, form_resume_packet, handle_death_packet, handle_events_packet, handle_high_scores_packet, handle_inventory_packet, handle_notification_packet, handle_profile_packet, handle_server_info_packet, handle_session_packet, handle_update_packet, handle_world_info_packet};
 
 const IS_PROD: bool = false;
 const URL: &str = if IS_PROD {
@@ -15,7 +15,11 @@ const URL: &str = if IS_PROD {
     "ws://127.0.0.1:8080/ws"
 };
 
-const MAX_RETRIES: usize = 3;
+const MAX_RETRIES: usize = 6;
+/// How long to wait before the first attempt to reconnect, in milliseconds.
+/// The delay doubles with every failed attempt.
+const RECONNECT_DELAY: u32 = 500;
+const MAX_RECONNECT_DELAY: u32 = 8000;
 
 #[derive(PartialEq)]
 pub enum ConnectionState {
@@ -28,6 +32,8 @@ pub struct Connection {
     pub state: ConnectionState,
     pub latency: Interpolatable<f64>,
     pub mspt: Interpolatable<f32>,
+    /// The token which reclaims the player's tank after reconnecting.
+    pub resume_token: Option<u64>,
 
     retries: usize,
     last_ping: Vec<f64>,
@@ -37,17 +43,14 @@ pub struct Connection {
 
 impl Connection {
     pub fn new() -> Connection {
-        let socket = WebSocket::new(URL)
-            .expect("ws api not supported");
-        socket.set_binary_type(BinaryType::Arraybuffer);
-
         let connection = Connection { 
             state: ConnectionState::Connecting,
             latency: Interpolatable::new(0.0),
             mspt: Interpolatable::new(0.0),
+            resume_token: None,
             retries: 0,
             last_ping: vec![],
-            socket 
+            socket: Connection::open_socket()
         };
 
         connection.setup_event_handlers();
@@ -55,6 +58,20 @@ impl Connection {
         connection
     }
 
+    fn open_socket() -> WebSocket {
+        let socket = WebSocket::new(URL)
+            .expect("ws api not supported");
+        socket.set_binary_type(BinaryType::Arraybuffer);
+
+        socket
+    }
+
+    fn reconnect(&mut self) {
+        self.socket = Connection::open_socket();
+        self.last_ping.clear();
+        self.setup_event_handlers();
+    }
+
     fn setup_event_handlers(&self) {
         let onmessage_callback = Closure::wrap(Box::new(move |event: MessageEvent| {
             spawn_local(async move {
@@ -78,27 +95,19 @@ impl Connection {
             });
         }) as Box<dyn FnMut(Event)>);
 
-        let onerror_callback = Closure::wrap(Box::new(move |_| {
-            spawn_local(async {
-                let mut world = get_world();
-                Connection::on_error(&mut world);
-            });
-        }) as Box<dyn FnMut(Event)>);
-
         let onclose_callback = Closure::wrap(Box::new(move |_| {
             spawn_local(async {
-                let world = get_world();
+                let mut world = get_world();
+                Connection::on_close(&mut world);
             });
         }) as Box<dyn FnMut(Event)>);
 
         self.socket.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
         self.socket.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
-        self.socket.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
         self.socket.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
 
         onmessage_callback.forget();
         onopen_callback.forget();
-        onerror_callback.forget();
         onclose_callback.forget();
     }
 
@@ -106,24 +115,30 @@ impl Connection {
         let connection = &mut world.connection;
         connection.retries = 0;
         connection.state = ConnectionState::Connected;
+        connection.send_message(form_profile_packet(), ServerboundPackets::Profile);
+
+        if let Some(token) = connection.resume_token {
+            connection.send_message(form_resume_packet(token), ServerboundPackets::Resume);
+        }
     }
 
-    fn on_error(world: &mut World) {
+    /// Reconnects with an exponential backoff. Errors are always followed by
+    /// a close, so failed attempts to connect end up here too.
+    fn on_close(world: &mut World) {
         let connection = &mut world.connection;
         connection.retries += 1;
 
-        if connection.retries <= MAX_RETRIES {
-            connection.socket = WebSocket::new(URL)
-                .expect("ws api not supported");
-
-            connection.setup_event_handlers();
-        } else {
+        if connection.retries > MAX_RETRIES {
             connection.state = ConnectionState::Failed;
+            return;
         }
-    }
 
-    fn on_close(world: &mut World) {
+        connection.state = ConnectionState::Connecting;
 
+        let delay = (RECONNECT_DELAY << (connection.retries - 1)).min(MAX_RECONNECT_DELAY);
+        Timeout::new(delay, || spawn_local(async {
+            get_world().connection.reconnect();
+        })).forget();
     }
 
     fn on_message(world: &mut World, mut codec: BinaryCodec) {
@@ -135,7 +150,14 @@ impl Connection {
                 world.connection.latency.target = window().performance().unwrap().now() 
                     - world.connection.last_ping.pop().unwrap_or(0.0);
             },
-            ClientboundPackets::ServerInfo => handle_server_info_packet(world, codec)
+            ClientboundPackets::ServerInfo => handle_server_info_packet(world, codec),
+            ClientboundPackets::Inventory => handle_inventory_packet(world, codec),
+            ClientboundPackets::Events => handle_events_packet(world, codec),
+            ClientboundPackets::Death => handle_death_packet(world, codec),
+            ClientboundPackets::WorldInfo => handle_world_info_packet(world, codec),
+            ClientboundPackets::Session => handle_session_packet(world, codec),
+            ClientboundPackets::Profile => handle_profile_packet(world, codec),
+            ClientboundPackets::HighScores => handle_high_scores_packet(world, codec)
         }
     }
 
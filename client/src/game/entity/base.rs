@@ -2,36 +2,155 @@ use std::collections::HashMap;
 use derive_new::new as New;
 use gloo::console::console;
 use gloo_utils::window;
-use shared::{connection::packets::CensusProperties, fuzzy_compare, game::{body::{BodyIdentity, BodyIdentityIds, BodyRenderingHints}, entity::{ClanInformation, EntityType, InputFlags, Notification, Ownership, UpgradeStats, BASE_TANK_RADIUS}, orb::OrbIdentity, theme::{BAR_BACKGROUND, HIGH_HEALTH_BAR, LOW_HEALTH_BAR, MEDIUM_HEALTH_BAR}, turret::{TurretIdentity, TurretIdentityIds, TurretRenderingHints, TurretStructure}}, lerp, lerp_angle, prettify_score, utils::{codec::BinaryCodec, color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
+use shared::{connection::packets::CensusProperties, fuzzy_compare, game::{ability::AbilityKind, gear::{Gear, Inventory}, body::{BodyIdentity, BodyIdentityIds, BodyRenderingHints}, entity::{ClanInformation, ClanStanding, EntityType, Marker, InputFlags, Notification, Ownership, SpectateMode, UpgradeStats, BASE_TANK_RADIUS}, orb::OrbIdentity, status::StatusEffectKind, theme::{BAR_BACKGROUND, HIGH_HEALTH_BAR, LOW_HEALTH_BAR, MEDIUM_HEALTH_BAR}, turret::{TurretIdentity, TurretIdentityIds, TurretRenderingHints, TurretStructure}, world::WorldInfo, profile::Profile, movement::MovementState, highscores::{HighScorePeriod, RecordedRun}}, lerp, lerp_angle, prettify_score, utils::{codec::BinaryCodec, color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
 use strum::EnumCount;
 use ui::{canvas2d::Canvas2d, core::UiElement, elements::tank::Tank};
 
-use crate::{rendering::phases::GamePhase, world::World};
+use crate::{rendering::phases::GamePhase, simulation::snapshots::{ServerClock, Snapshot, SnapshotBuffer}, world::World};
 
-use shared::game::theme::{ENEMY_FILL, ENEMY_STROKE, PLAYER_FILL, PLAYER_STROKE, SMASHER_GUARD_FILL, SMASHER_GUARD_STROKE, STROKE_SIZE, TURRET_FILL, TURRET_STROKE};
+use shared::game::theme::{ALLY_FILL, ALLY_STROKE, ENEMY_FILL, ENEMY_STROKE, PLAYER_FILL, PLAYER_STROKE, SMASHER_GUARD_FILL, SMASHER_GUARD_STROKE, STROKE_SIZE, TURRET_FILL, TURRET_STROKE};
+
+/// Who an entity fights for, relative to the self entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Allegiance {
+    /// The self entity, or something it owns.
+    Own,
+    /// A clanmate, or something a clanmate owns.
+    Ally,
+    Enemy
+}
 
 #[derive(Debug, Default)]
 pub struct LeaderboardState {
     pub entries: Vec<(usize, String, BodyIdentityIds, TurretIdentityIds)>,
-    pub angle: Interpolatable<f32>,
-    pub intersection: Interpolatable<Vector2D>,
-    pub arrow_opacity: Interpolatable<f32>
+    /// The clans with the highest combined score.
+    pub clans: Vec<ClanStanding>,
+    /// The time left in the current clan war, in milliseconds.
+    pub war_remaining: Option<f64>,
+    /// Whether the clan leaderboard is shown instead of the player leaderboard.
+    pub show_clans: bool,
+    /// The number of players connected to the arena, excluding spectators.
+    pub players: usize,
+    pub spectators: usize
+}
+
+/// The camera of a player who is spectating.
+#[derive(Debug, Default, Clone)]
+pub struct SpectatorState {
+    pub mode: SpectateMode,
+    /// The player being followed, if any.
+    pub spectated: Option<u32>,
+    /// The movement inputs last sent to pan the camera.
+    pub sent_inputs: u32
+}
+
+/// The high-score tables shown on the home screen.
+#[derive(Debug, Default, Clone)]
+pub struct HighScoreState {
+    /// The table being viewed.
+    pub period: HighScorePeriod,
+    /// The best runs of every period, indexed by the period.
+    pub tables: Vec<Vec<RecordedRun>>
+}
+
+/// The self entity's ability, as displayed on the HUD.
+#[derive(Debug, Clone)]
+pub struct AbilityDisplay {
+    pub kind: AbilityKind,
+    pub charges: u8,
+    pub max_charges: u8,
+    /// The fraction of the cooldown left before the next charge is restored.
+    pub cooldown: f32,
+    pub active: bool
 }
 
 #[derive(Debug, Default)]
 pub struct ClanState {
     pub clans: Vec<ClanInformation>,
+    /// The names of every member of a clan, and of every player requesting to join one.
+    pub names: HashMap<u32, String>,
     pub page: usize
 }
 
+/// A marker sent by the server, easing towards the latest position sent for it.
+#[derive(Debug, Clone)]
+pub struct MarkerDisplay {
+    pub marker: Marker,
+    pub position: Interpolatable<Vector2D>,
+    /// Fades out once the server stops sending the marker.
+    pub opacity: Interpolatable<f32>
+}
+
+/// A trade the self entity is part of.
+#[derive(Debug, Default, Clone)]
+pub struct TradeDisplay {
+    pub partner: u32,
+    pub partner_name: String,
+    /// The inventory indices of the items offered.
+    pub offer: Vec<usize>,
+    pub partner_offer: Vec<Gear>,
+    pub confirmed: bool,
+    pub partner_confirmed: bool
+}
+
+/// The self entity's gear, along with the trades it is part of.
+#[derive(Debug, Default)]
+pub struct InventoryState {
+    pub inventory: Inventory,
+    /// Players who have requested to trade, as (id, name).
+    pub requests: Vec<(u32, String)>,
+    /// The player a trade was requested from.
+    pub requested: Option<u32>,
+    pub trade: Option<TradeDisplay>
+}
+
+/// How long a damage number floats for, in frames.
+pub const DAMAGE_NUMBER_LIFETIME: f32 = 45.0;
+
+/// Damage dealt to or by the self entity, floating above the victim.
+#[derive(Debug, Clone)]
+pub struct DamageNumber {
+    pub victim: u32,
+    pub position: Vector2D,
+    pub damage: f32,
+    /// Whether the self entity took the damage, rather than dealt it.
+    pub taken: bool,
+    pub lifetime: f32
+}
+
+/// The summary of the self entity's last life, sent by the server when it dies.
+#[derive(Debug, Default, Clone)]
+pub struct DeathSummary {
+    pub killer: Option<String>,
+    /// The tank the killer was using, if it was a tank.
+    pub killer_tank: Option<(BodyIdentityIds, TurretIdentityIds)>,
+    /// The damage taken from each source, from most to least.
+    pub damage_taken: Vec<(String, f32)>,
+    pub kills: usize,
+    pub level: usize,
+    pub score: usize,
+    /// The time spent alive, in milliseconds.
+    pub time_alive: f64
+}
+
 #[derive(Debug, Default)]
 pub struct Game {
     pub surroundings: HashMap<u32, Entity>,
     pub self_entity: Entity,
     pub leaderboard: LeaderboardState,
     pub clan_state: ClanState,
-
-    pub arena_size: f32
+    pub inventory: InventoryState,
+    pub damage_numbers: Vec<DamageNumber>,
+    pub death_summary: DeathSummary,
+    pub markers: Vec<MarkerDisplay>,
+    pub clock: ServerClock,
+    /// Set while the player is spectating instead of playing.
+    pub spectator: Option<SpectatorState>,
+    /// The lifetime statistics of the player, once the server has sent them.
+    pub profile: Option<Profile>,
+    pub high_scores: HighScoreState,
+
+    pub world_info: WorldInfo
 }
 
 impl Game {
@@ -42,17 +161,39 @@ impl Game {
             self.surroundings.get_mut(&id).unwrap()
         }
     }
+
+    /// Shows `damage` above `victim`, merging it into a recent number for the same victim
+    /// so that continuous damage does not flood the screen.
+    pub fn add_damage_number(&mut self, victim: u32, position: Vector2D, damage: f32, taken: bool) {
+        if let Some(number) = self.damage_numbers.iter_mut()
+            .find(|number| number.victim == victim && number.taken == taken && number.lifetime > DAMAGE_NUMBER_LIFETIME / 2.0)
+        {
+            number.damage += damage;
+            number.position = position;
+            number.lifetime = DAMAGE_NUMBER_LIFETIME;
+        } else {
+            self.damage_numbers.push(DamageNumber { victim, position, damage, taken, lifetime: DAMAGE_NUMBER_LIFETIME });
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone, New)]
+#[derive(Debug, Default, Clone)]
 pub struct PhysicsComponent {
     pub position: Interpolatable<Vector2D>,
     pub velocity: Interpolatable<Vector2D>,
     pub angle: Interpolatable<f32>,
+    /// The velocity the server adds on top of the tank's own.
+    pub additional_velocity: Vector2D,
+    /// How fast the server accelerates the tank, including gear and status effects.
+    pub acceleration: f32,
     pub mouse: Vector2D,
 
     pub inputs: InputFlags,
-    pub auto_fire: bool
+    pub auto_fire: bool,
+    /// The sequence number of the last input the server processed.
+    pub acknowledged_input: u32,
+    /// Recent authoritative states, sampled when rendering.
+    pub snapshots: SnapshotBuffer
 }
 
 #[derive(Default, Debug, Clone)]
@@ -65,7 +206,6 @@ pub struct DisplayComponent {
     pub should_display_stats: bool,
     pub upgrades: Vec<i32>,
     pub notifications: Vec<Notification>,
-    pub kills: usize,
     
     pub opacity: Interpolatable<f32>,
     pub fov: Interpolatable<f32>,
@@ -75,11 +215,16 @@ pub struct DisplayComponent {
     pub turret_identity: TurretStructure,
     pub orb_identity: OrbIdentity,
     pub turret_lengths: Vec<Interpolatable<f32>>,
+    /// The absolute angles of any automatic turrets.
+    pub turret_angles: Vec<Interpolatable<f32>>,
     pub turret_index: usize,
     pub owners: Ownership,
     pub radius: Interpolatable<f32>,
     pub damage_blend: Interpolatable<f32>,
     pub invincible: bool,
+    /// The status effects the entity is afflicted with, their stacks and the fraction of their duration which remains.
+    pub status_effects: Vec<(StatusEffectKind, u8, f32)>,
+    pub ability: Option<AbilityDisplay>,
 
     pub typing: bool,
     pub messages: Vec<(String, Interpolatable<Vector2D>, Interpolatable<f32>)>,
@@ -120,7 +265,6 @@ pub struct ConnectionComponent {
 #[derive(Debug, Default, Clone)]
 pub struct StatsComponent {
     pub has_spawned: bool,
-    pub life_timestamps: (f64, f64),
     pub health: Interpolatable<f32>,
     pub max_health: Interpolatable<f32>,
     pub health_bar_opacity: Interpolatable<f32>,
@@ -142,13 +286,14 @@ pub struct Entity {
 
 impl Entity {
     pub fn parse_census(world: &mut World, codec: &mut BinaryCodec, is_self: bool) -> u32 {
+        let clock = world.game.clock;
+        let id = codec.decode_varuint().unwrap() as u32;
         let entity = if is_self {
             let entity = &mut world.game.self_entity;
-            entity.id = codec.decode_varuint().unwrap() as u32;
+            entity.id = id;
 
             entity
         } else {
-            let id = codec.decode_varuint().unwrap() as u32;
             world.game.surroundings.entry(id)
                 .or_insert_with(|| Entity { id, ..Default::default() })
         };
@@ -158,10 +303,30 @@ impl Entity {
         entity.display.entity_type = (codec.decode_varuint().unwrap() as u8).try_into().unwrap();
         match entity.display.entity_type {
             EntityType::Player => entity.parse_tank_census(codec, is_self),
-            EntityType::Bullet | EntityType::Drone | EntityType::Trap => entity.parse_projectile_census(codec),
+            EntityType::Bullet | EntityType::Drone | EntityType::Trap
+                | EntityType::Missile | EntityType::Minion | EntityType::Mine => entity.parse_projectile_census(codec),
             EntityType::Orb => entity.parse_orb_census(codec)
         }
 
+        entity.physics.snapshots.push(Snapshot {
+            tick: clock.tick,
+            timestamp: clock.timestamp,
+            position: entity.physics.position.target,
+            velocity: entity.physics.velocity.target,
+            angle: entity.physics.angle.target
+        });
+
+        if is_self && entity.stats.health_state == HealthState::Alive {
+            let physics = &entity.physics;
+            world.simulation.reconcile(
+                MovementState::new(physics.position.target, physics.velocity.target, physics.additional_velocity),
+                physics.acknowledged_input,
+                world.game.world_info.arena_size
+            );
+        }
+
+        let entity = if is_self { &mut world.game.self_entity } else { world.game.surroundings.get_mut(&id).unwrap() };
+
         if is_self {
             if entity.stats.health.target > 0.0 {
                 entity.display.score.direction = 1.0;
@@ -173,7 +338,6 @@ impl Entity {
                 if entity.display.score.direction == 1.0 {
                     entity.display.score.direction = -1.0;
                     entity.display.score.value = 0.0;
-                    entity.stats.life_timestamps.1 = window().performance().unwrap().now();
                     entity.stats.has_spawned = false;
                 }
 
@@ -187,50 +351,43 @@ impl Entity {
     pub fn render(world: &mut World, id: u32, dt: f32) {
         let self_id = world.game.self_entity.id;
         if id == self_id {
-            world.game.self_entity.render_tank(&mut world.renderer.canvas2d, true, true, dt);
+            world.game.self_entity.render_tank(&mut world.renderer.canvas2d, true, Allegiance::Own, dt);
         } else {
-            let (shooter, turret_idx) = {
-                let mut shooter = None;
-                let mut turret_idx = None;
-
-                let entity = world.game.surroundings.get_mut(&id).unwrap();
-                let is_friendly = id == self_id || entity.display.owners.has_owner(self_id);
-                match entity.display.entity_type {
-                    EntityType::Player => entity.render_tank(&mut world.renderer.canvas2d, false, is_friendly, dt),
-                    EntityType::Bullet | EntityType::Drone | EntityType::Trap => {
-                        if entity.time.server_ticks == 0 || entity.time.server_ticks == 1 {
-                            turret_idx = Some(entity.display.turret_index);
-    
-                            if is_friendly && let Some(turret) = world.game.self_entity.display.turret_lengths.get_mut(turret_idx.unwrap()) {
-                                turret.target = 0.75;
-                            } else {
-                                shooter = Some(entity.display.owners.deep);
-                            }
-                        }
-        
-                        entity.render_projectile(&mut world.renderer.canvas2d, is_friendly, dt);
-                    },
-                    EntityType::Orb => entity.render_orb(&mut world.renderer.canvas2d, dt)
-                }
-
-                (shooter, turret_idx)
+            let entity = world.game.surroundings.get_mut(&id).unwrap();
+            let member = if entity.display.entity_type == EntityType::Player { id } else { entity.display.owners.deep };
+
+            let allegiance = if entity.display.owners.has_owner(self_id) {
+                Allegiance::Own
+            } else if world.game.clan_state.clans.iter().any(|clan| clan.members.contains(&self_id) && clan.members.contains(&member)) {
+                Allegiance::Ally
+            } else {
+                Allegiance::Enemy
             };
 
-            if let Some(shooter) = shooter && let Some(entity) = world.game.surroundings.get_mut(&shooter) {
-                entity.display.turret_lengths[turret_idx.unwrap()].target = 0.75;
+            match entity.display.entity_type {
+                EntityType::Player => entity.render_tank(&mut world.renderer.canvas2d, false, allegiance, dt),
+                EntityType::Bullet | EntityType::Drone | EntityType::Trap
+                    | EntityType::Missile | EntityType::Minion | EntityType::Mine => entity.render_projectile(&mut world.renderer.canvas2d, allegiance, dt),
+                EntityType::Orb => entity.render_orb(&mut world.renderer.canvas2d, dt)
             }
         }
     }
 
-    pub fn compute_body_fill(&self, is_friendly: bool) -> (Color, Color) {
+    pub fn compute_body_fill(&self, allegiance: Allegiance) -> (Color, Color) {
+        let (fill, stroke) = match allegiance {
+            Allegiance::Own => (PLAYER_FILL, PLAYER_STROKE),
+            Allegiance::Ally => (ALLY_FILL, ALLY_STROKE),
+            Allegiance::Enemy => (ENEMY_FILL, ENEMY_STROKE)
+        };
+
         let mut fill = Color::blend_colors(
-            if is_friendly { PLAYER_FILL } else { ENEMY_FILL }, 
+            fill, 
             Color::RED, 
             self.display.damage_blend.value
         );
 
         let mut stroke = Color::blend_colors(
-            if is_friendly { PLAYER_STROKE } else { ENEMY_STROKE }, 
+            stroke, 
             Color::RED, 
             self.display.damage_blend.value
         );
@@ -240,9 +397,98 @@ impl Entity {
             stroke.blend_with(0.3, Color::WHITE);
         }
 
+        self.apply_status_tint(&mut fill, &mut stroke);
+
         (fill, stroke)
     }
 
+    /// Tints the entity with the color of every status effect it is afflicted with.
+    pub fn apply_status_tint(&self, fill: &mut Color, stroke: &mut Color) {
+        if self.display.status_effects.is_empty() {
+            return;
+        }
+
+        let factor = 0.4 / self.display.status_effects.len() as f32;
+        for &(kind, _, _) in self.display.status_effects.iter() {
+            // Stuns flicker so they stand out from the other effects.
+            if kind == StatusEffectKind::Stun && self.time.ticks % 10 > 5 {
+                continue;
+            }
+
+            fill.blend_with(factor, kind.color());
+            stroke.blend_with(factor, kind.color());
+        }
+    }
+
+    pub fn parse_status_effects(&mut self, codec: &mut BinaryCodec) {
+        self.display.status_effects.clear();
+
+        let len = codec.decode_varuint().unwrap() as usize;
+        for _ in 0..len {
+            let kind = (codec.decode_varuint().unwrap() as u8).try_into().unwrap();
+            let stacks = codec.decode_varuint().unwrap() as u8;
+            let remaining = codec.decode_f32().unwrap();
+
+            self.display.status_effects.push((kind, stacks, remaining));
+        }
+    }
+
+    /// Renders an icon for every status effect beneath the entity's health bar,
+    /// ringed by how much of the effect remains.
+    pub fn render_status_effects(world: &mut World, id: u32) {
+        let entity = if id == world.game.self_entity.id {
+            &world.game.self_entity
+        } else {
+            world.game.surroundings.get(&id).unwrap()
+        };
+
+        if entity.display.status_effects.is_empty() || entity.stats.health_state != HealthState::Alive {
+            return;
+        }
+
+        let (size, spacing) = (14.0, 36.0);
+        let context = &mut world.renderer.canvas2d;
+
+        context.save();
+        context.translate(
+            entity.physics.position.value.x + entity.physics.velocity.value.x - (entity.display.status_effects.len() - 1) as f32 * spacing / 2.0,
+            entity.physics.position.value.y + entity.physics.velocity.value.y + entity.display.radius.value + 50.0
+        );
+
+        context.set_font("bold 16px Ubuntu");
+        context.set_text_align("center");
+        context.set_text_baseline("middle");
+
+        for &(kind, stacks, remaining) in entity.display.status_effects.iter() {
+            let color = kind.color();
+
+            context.fill_style(color);
+            context.stroke_style(Color::blend_colors(color, Color::BLACK, 0.25));
+            context.set_stroke_size(4.0);
+            context.begin_arc(0.0, 0.0, size, std::f32::consts::TAU);
+            context.fill();
+            context.stroke();
+
+            context.save();
+            context.rotate(-std::f32::consts::FRAC_PI_2);
+            context.stroke_style(BAR_BACKGROUND);
+            context.begin_arc(0.0, 0.0, size + 5.0, std::f32::consts::TAU * remaining.clamp(0.0, 1.0));
+            context.stroke();
+            context.restore();
+
+            let glyph = if stacks > 1 { format!("{}{}", kind.glyph(), stacks) } else { kind.glyph().to_string() };
+            context.fill_style(Color::WHITE);
+            context.stroke_style(Color::BLACK);
+            context.set_stroke_size(3.0);
+            context.stroke_text(&glyph);
+            context.fill_text(&glyph);
+
+            context.translate(spacing, 0.0);
+        }
+
+        context.restore();
+    }
+
     pub fn render_health_bar(world: &mut World, id: u32, dt: f32) {
         let mut entity = if id == world.game.self_entity.id {
             &mut world.game.self_entity
@@ -301,6 +547,11 @@ impl Entity {
         let entity = world.game.surroundings.get_mut(&id).unwrap();
         let context = &world.renderer.canvas2d;
 
+        let name = match entity.display.clan.and_then(|clan| world.game.clan_state.clans.iter().find(|info| info.id == clan)) {
+            Some(clan) => format!("[{}] {}", clan.name, entity.display.name),
+            None => entity.display.name.clone()
+        };
+
         if entity.display.entity_type != EntityType::Player || entity.stats.health_state != HealthState::Alive {
             return;
         }
@@ -324,8 +575,8 @@ impl Entity {
         context.set_stroke_size((font / 5.0).ceil());
 
         context.translate(0.0, -entity.display.radius.value - 60.0);
-        context.stroke_text(&entity.display.name);
-        context.fill_text(&entity.display.name);
+        context.stroke_text(&name);
+        context.fill_text(&name);
         context.restore();
 
         if !fuzzy_compare!(entity.display.score.value, 0.0, 1e-3) {
@@ -344,26 +595,21 @@ impl Entity {
         context.restore();
     }
 
-    pub fn lerp_all(&mut self, dt: f32, is_self: bool) {
-        let factor = if self.time.ticks <= 1 { 1.0 } else { 0.2 * dt };
+    /// Positions the entity as it was at server time `time`, using its snapshot buffer.
+    pub fn interpolate(&mut self, time: f64, is_self: bool) {
+        let Some(state) = self.physics.snapshots.sample(time) else { return; };
+
+        self.physics.position.value = state.position;
+        self.physics.velocity.value = state.velocity;
 
         if !is_self {
-            self.physics.angle.value = lerp_angle!(
-                self.physics.angle.value, 
-                self.physics.angle.target, 
-                factor
-            );
+            self.physics.angle.value = state.angle;
         }
+    }
 
-        self.physics.position.value.lerp_towards(
-            self.physics.position.target, 
-            factor
-        );
-
-        self.physics.velocity.value.lerp_towards(
-            self.physics.velocity.target, 
-            factor
-        );
+    /// Smooths the entity's cosmetic values towards their targets.
+    pub fn lerp_all(&mut self, dt: f32) {
+        let factor = if self.time.ticks <= 1 { 1.0 } else { 0.2 * dt };
 
         self.display.score.value = lerp!(
             self.display.score.value, 
@@ -423,6 +669,10 @@ impl Entity {
             self.display.damage_blend.target = 0.0;
         }
 
+        for angle in self.display.turret_angles.iter_mut() {
+            angle.value = lerp_angle!(angle.value, angle.target, factor);
+        }
+
         for length in self.display.turret_lengths.iter_mut() {
             length.value = lerp!(length.value, length.target, factor);
             if fuzzy_compare!(length.value, length.target, 1e-1) {
@@ -49,19 +49,23 @@ impl Entity {
                     let orb_identity_id: OrbIdentityIds = (codec.decode_varuint().unwrap() as usize).try_into().unwrap();
                     self.display.orb_identity = orb_identity_id.try_into().unwrap();
                 },
+                CensusProperties::StatusEffects => self.parse_status_effects(codec),
                 _ => {}
             }
         }
     }
 
     fn compute_orb_fill(&self) -> (Color, Color) {
-        match self.display.orb_identity.id {
+        let (mut fill, mut stroke) = match self.display.orb_identity.id {
             OrbIdentityIds::Flickering => (ORB_FLICKERING_FILL, ORB_FLICKERING_STROKE),
             OrbIdentityIds::Basic => (ORB_BASIC_FILL, ORB_BASIC_STROKE),
             OrbIdentityIds::Stable => (ORB_STABLE_FILL, ORB_STABLE_STROKE),
             OrbIdentityIds::Heavy => (ORB_HEAVY_FILL, ORB_HEAVY_STROKE),
             OrbIdentityIds::Radiant => (ORB_RADIANT_FILL, ORB_RADIANT_STROKE)
-        }
+        };
+
+        self.apply_status_tint(&mut fill, &mut stroke);
+        (fill, stroke)
     }
 
     fn render_orb_body(&self, context: &mut Canvas2d) {
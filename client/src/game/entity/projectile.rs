@@ -9,7 +9,7 @@ use crate::{rendering::phases::GamePhase, world::World};
 
 use shared::game::theme::{ENEMY_FILL, ENEMY_STROKE, PLAYER_FILL, PLAYER_STROKE, SMASHER_GUARD_FILL, SMASHER_GUARD_STROKE, STROKE_SIZE, TURRET_FILL, TURRET_STROKE};
 
-use super::base::{Entity, HealthState};
+use super::base::{Allegiance, Entity, HealthState};
 
 impl Entity {
     pub fn parse_projectile_census(&mut self, codec: &mut BinaryCodec) {
@@ -55,13 +55,26 @@ impl Entity {
                     self.display.turret_index = codec.decode_varuint().unwrap() as usize;
                 },
                 CensusProperties::Ticks => self.time.server_ticks = codec.decode_varuint().unwrap(),
+                CensusProperties::Identity => {
+                    self.display.turret_identity.id = (codec.decode_varuint().unwrap() as usize).try_into().unwrap();
+                },
                 _ => {}
             }
         }
+
+        // Missiles and minions carry the turrets of the projectile identity which fired them.
+        if matches!(self.display.entity_type, EntityType::Missile | EntityType::Minion)
+            && self.display.turret_identity.turrets.is_empty()
+            && let Ok(structure) = TurretStructure::try_from(self.display.turret_identity.id)
+            && let Some(turret) = structure.turrets.get(self.display.turret_index)
+        {
+            self.display.turret_identity.turrets = turret.projectile_identity.turrets.clone();
+            self.display.turret_lengths.resize(self.display.turret_identity.turrets.len(), Interpolatable::new(1.0));
+        }
     }
 
-    fn render_projectile_body(&self, context: &mut Canvas2d, is_friendly: bool) {
-        let (fill, stroke) = self.compute_body_fill(is_friendly);
+    fn render_projectile_body(&self, context: &mut Canvas2d, allegiance: Allegiance) {
+        let (fill, stroke) = self.compute_body_fill(allegiance);
         
         context.save();
 
@@ -72,9 +85,10 @@ impl Entity {
         context.rotate(std::f32::consts::FRAC_PI_2);
 
         match self.display.entity_type {
-            EntityType::Bullet => context.begin_arc(0.0, 0.0, self.display.radius.value, std::f32::consts::TAU),
+            EntityType::Bullet | EntityType::Missile | EntityType::Minion => context.begin_arc(0.0, 0.0, self.display.radius.value, std::f32::consts::TAU),
             EntityType::Drone => context.begin_triangle(self.display.radius.value),
             EntityType::Trap => context.begin_star(3, self.display.radius.value / 1.5, self.display.radius.value * 1.75),
+            EntityType::Mine => context.begin_star(6, self.display.radius.value / 1.25, self.display.radius.value * 1.5),
             _ => unreachable!("Non-projectile entity attempted rendering.")
         }
         
@@ -84,10 +98,10 @@ impl Entity {
         context.restore();
     }
 
-    pub fn render_projectile(&mut self, context: &mut Canvas2d, is_friendly: bool, dt: f32) {
+    pub fn render_projectile(&mut self, context: &mut Canvas2d, allegiance: Allegiance, dt: f32) {
         self.time.ticks += 1;
         if matches!(self.stats.health_state, HealthState::Dying | HealthState::Dead) {
-            self.destroy_projectile(context, is_friendly, dt);
+            self.destroy_projectile(context, allegiance, dt);
         }
 
         context.save();
@@ -100,12 +114,16 @@ impl Entity {
         context.global_alpha(self.display.opacity.value);
         context.set_stroke_size(STROKE_SIZE);
 
-        self.render_projectile_body(context, is_friendly);
+        if !self.display.turret_identity.turrets.is_empty() {
+            Tank::render_turrets(context, STROKE_SIZE, self.display.radius.value, &self.display.turret_identity, &self.display.turret_lengths);
+        }
+
+        self.render_projectile_body(context, allegiance);
 
         context.restore();
     }
 
-    fn destroy_projectile(&mut self, context: &mut Canvas2d, is_friendly: bool, dt: f32) {
+    fn destroy_projectile(&mut self, context: &mut Canvas2d, allegiance: Allegiance, dt: f32) {
         if fuzzy_compare!(self.display.opacity.value, 0.0, 1e-1) {
             self.stats.health_state = HealthState::Dead;
             return;
@@ -11,7 +11,7 @@ use crate::{rendering::phases::GamePhase, world::World};
 
 use shared::game::theme::{ENEMY_FILL, ENEMY_STROKE, PLAYER_FILL, PLAYER_STROKE, SMASHER_GUARD_FILL, SMASHER_GUARD_STROKE, STROKE_SIZE, TURRET_FILL, TURRET_STROKE};
 
-use super::base::{Entity, HealthState};
+use super::base::{AbilityDisplay, Allegiance, Entity, HealthState};
 
 impl Entity {
     pub fn parse_tank_census(&mut self, codec: &mut BinaryCodec, is_self: bool) {
@@ -52,11 +52,6 @@ impl Entity {
                     self.stats.health.target = health;
 
                     if health > 0.0 && old_state != HealthState::Alive {
-                        if !self.stats.has_spawned {
-                            self.stats.life_timestamps.0 = window().performance().unwrap().now();
-                            // self.display.kills += 1;
-                        }
-
                         self.stats.health_state = HealthState::Alive;
                         self.stats.has_spawned = true;
                     } else if health <= 0.0 && old_state == HealthState::Alive {
@@ -100,12 +95,47 @@ impl Entity {
                     self.display.body_identity = body_identity_id.try_into().unwrap();
 
                     let turret_identity_id: TurretIdentityIds = (codec.decode_varuint().unwrap() as usize).try_into().unwrap();
-                    self.display.turret_identity = turret_identity_id.try_into().unwrap();
+                    self.display.turret_identity = TurretStructure::try_from(turret_identity_id).unwrap().with_body(&self.display.body_identity);
 
                     self.display.turret_lengths.resize(self.display.turret_identity.turrets.len(), Interpolatable::new(1.0));
+                    self.display.turret_angles.resize(self.display.turret_identity.turrets.len(), Interpolatable::new(self.physics.angle.value));
                 },
                 CensusProperties::Ticks => self.time.server_ticks = codec.decode_varuint().unwrap(),
                 CensusProperties::Invincibility => self.display.invincible = codec.decode_bool().unwrap(),
+                CensusProperties::InputSequence => self.physics.acknowledged_input = codec.decode_varuint().unwrap() as u32,
+                CensusProperties::StatusEffects => self.parse_status_effects(codec),
+                CensusProperties::Energy => {
+                    self.stats.energy.target = codec.decode_f32().unwrap();
+                    self.stats.max_energy.target = codec.decode_f32().unwrap();
+                },
+                CensusProperties::Movement => {
+                    self.physics.additional_velocity = Vector2D::new(codec.decode_f32().unwrap(), codec.decode_f32().unwrap());
+                    self.physics.acceleration = codec.decode_f32().unwrap();
+                },
+                CensusProperties::Ability => {
+                    self.display.ability = if codec.decode_bool().unwrap() {
+                        Some(AbilityDisplay {
+                            kind: (codec.decode_varuint().unwrap() as u8).try_into().unwrap(),
+                            charges: codec.decode_varuint().unwrap() as u8,
+                            max_charges: codec.decode_varuint().unwrap() as u8,
+                            cooldown: codec.decode_f32().unwrap(),
+                            active: codec.decode_bool().unwrap()
+                        })
+                    } else {
+                        None
+                    };
+                },
+                CensusProperties::TurretAngles => {
+                    let len = codec.decode_varuint().unwrap() as usize;
+                    for _ in 0..len {
+                        let index = codec.decode_varuint().unwrap() as usize;
+                        let angle = codec.decode_f32().unwrap();
+
+                        if let Some(turret_angle) = self.display.turret_angles.get_mut(index) {
+                            turret_angle.target = angle;
+                        }
+                    }
+                },
                 CensusProperties::Messages => {
                     let mut old_messages = std::mem::take(&mut self.display.messages);
 
@@ -160,19 +190,27 @@ impl Entity {
         }
     }
 
-    fn render_tank_turrets(&self, context: &mut Canvas2d, is_self: bool) {
+    fn render_tank_turrets(&self, context: &mut Canvas2d, allegiance: Allegiance) {
         Tank::render_turrets(context, STROKE_SIZE, self.display.radius.value, &self.display.turret_identity, &self.display.turret_lengths);
     }
 
-    fn render_tank_body(&self, context: &mut Canvas2d, is_self: bool) {
-        let (fill, stroke) = self.compute_body_fill(is_self);
+    fn render_tank_auto_turrets(&self, context: &mut Canvas2d) {
+        let angles: Vec<f32> = self.display.turret_angles.iter()
+            .map(|angle| angle.value - self.physics.angle.value)
+            .collect();
+
+        Tank::render_auto_turrets(context, STROKE_SIZE, self.display.radius.value, &self.display.turret_identity, &self.display.turret_lengths, &angles);
+    }
+
+    fn render_tank_body(&self, context: &mut Canvas2d, allegiance: Allegiance) {
+        let (fill, stroke) = self.compute_body_fill(allegiance);
         Tank::render_body(context, STROKE_SIZE, &self.display.body_identity, self.display.radius.value, fill, stroke);
     }
 
-    pub fn render_tank(&mut self, context: &mut Canvas2d, is_self: bool, is_friendly: bool, dt: f32) {
+    pub fn render_tank(&mut self, context: &mut Canvas2d, is_self: bool, allegiance: Allegiance, dt: f32) {
         self.time.ticks += 1;
         if matches!(self.stats.health_state, HealthState::Dying | HealthState::Dead) {
-            self.destroy_tank(context, is_friendly, dt);
+            self.destroy_tank(context, allegiance, dt);
         }
 
         context.save();
@@ -235,14 +273,15 @@ impl Entity {
         context.save();
         context.rotate(self.physics.angle.value);
         context.set_stroke_size(STROKE_SIZE);
-        self.render_tank_turrets(context, is_friendly);
-        self.render_tank_body(context, is_friendly);
+        self.render_tank_turrets(context, allegiance);
+        self.render_tank_body(context, allegiance);
+        self.render_tank_auto_turrets(context);
         context.restore();
 
         context.restore();
     }
 
-    fn destroy_tank(&mut self, context: &mut Canvas2d, is_friendly: bool, dt: f32) {
+    fn destroy_tank(&mut self, context: &mut Canvas2d, allegiance: Allegiance, dt: f32) {
         if fuzzy_compare!(self.display.opacity.value, 0.0, 1e-1) {
             self.stats.health_state = HealthState::Dead;
             return;
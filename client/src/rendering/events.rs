@@ -1,6 +1,6 @@
 use gloo::console::console;
 use gloo_utils::{body, document, window};
-use shared::{connection::packets::{Inputs, ServerboundPackets}, game::entity::{Notification, MAX_STAT_INVESTMENT}, utils::{color::Color, vec2::Vector2D}};
+use shared::{connection::packets::{Inputs, ServerboundPackets, SpectatePacketOpcode}, game::entity::{Notification, MAX_STAT_INVESTMENT}, utils::{color::Color, vec2::Vector2D}};
 use ui::{core::{ElementType, UiElement}, get_element_by_id_and_cast};
 use web_sys::{wasm_bindgen::JsCast, BeforeUnloadEvent, HtmlInputElement, KeyboardEvent, MouseEvent, WheelEvent};
 use crate::{connection::packets, world::World};
@@ -30,6 +30,7 @@ pub enum KeyCode {
     Escape      = 27,
     Space       = 32,
     KeyE        = 69,
+    KeyF        = 70,
     KeyW        = 87,
     ArrowUp     = 38,
     KeyA        = 65,
@@ -180,6 +181,7 @@ pub fn on_keydown(world: &mut World, event: KeyboardEvent) {
             Ok(KeyCode::Space) => world.game.self_entity.physics.inputs.set_flag(Inputs::Shoot),
             Ok(KeyCode::Shift) => world.game.self_entity.physics.inputs.set_flag(Inputs::Repel),
             Ok(KeyCode::Backslash) => world.game.self_entity.physics.inputs.set_flag(Inputs::Switch),
+            Ok(KeyCode::KeyF) => world.game.self_entity.physics.inputs.set_flag(Inputs::Ability),
             Ok(KeyCode::KeyE) => {
                 world.game.self_entity.physics.auto_fire = !world.game.self_entity.physics.auto_fire;
                 world.game.self_entity.display.notifications.push(Notification {
@@ -209,6 +211,8 @@ pub fn on_keyup(world: &mut World, event: KeyboardEvent) {
                     Some(false),
                     String::new()
                 ), ServerboundPackets::Chat);
+            } else if world.renderer.phase == GamePhase::Spectate && !is_modal_open {
+                world.connection.send_message(packets::form_spectate_packet(SpectatePacketOpcode::Stop), ServerboundPackets::Spectate);
             }
 
             let mut deletion_indices = Vec::new();
@@ -219,6 +223,10 @@ pub fn on_keyup(world: &mut World, event: KeyboardEvent) {
                             s if s.contains("settings") => matches!(modal, ModalType::Settings(_)),
                             s if s.contains("clans") => matches!(modal, ModalType::Clans(_)),
                             s if s.contains("clan-create") => matches!(modal, ModalType::ClanCreate(_)),
+                            s if s.contains("clan-view") => matches!(modal, ModalType::ClanView(_)),
+                            s if s.contains("inventory") => matches!(modal, ModalType::Inventory(_)),
+                            s if s.contains("profile") => matches!(modal, ModalType::Profile(_)),
+                            s if s.contains("high-scores") => matches!(modal, ModalType::HighScores(_)),
                             _ => false,
                         }
                     }) {
@@ -281,6 +289,7 @@ pub fn on_keyup(world: &mut World, event: KeyboardEvent) {
             Ok(KeyCode::Space) => world.game.self_entity.physics.inputs.clear_flag(Inputs::Shoot),
             Ok(KeyCode::Shift) => world.game.self_entity.physics.inputs.clear_flag(Inputs::Repel),
             Ok(KeyCode::Backslash) => world.game.self_entity.physics.inputs.clear_flag(Inputs::Switch),
+            Ok(KeyCode::KeyF) => world.game.self_entity.physics.inputs.clear_flag(Inputs::Ability),
             Ok(KeyCode::One) | Ok(KeyCode::Two) | Ok(KeyCode::Three) | Ok(KeyCode::Four) | Ok(KeyCode::Five) | Ok(KeyCode::Six) | Ok(KeyCode::Seven) | Ok(KeyCode::Eight)
             => {
                 let i = (event.key_code() as u8 - b'0') as usize - 1;
@@ -2,16 +2,16 @@ use std::{collections::BTreeSet, sync::{atomic::{AtomicBool, Ordering}, Arc}};
 
 use gloo::{console::{self, console}, dialogs::alert};
 use gloo_utils::{document, window};
-use shared::{bool, connection::packets::{Inputs, ServerboundPackets}, fuzzy_compare, game::{body::{BodyIdentity, BodyIdentityIds}, entity::{generate_identity, get_level_from_score, get_min_score_from_level, Notification, UpgradeStats, FICTITIOUS_TANK_RADIUS, MAX_STAT_INVESTMENT}, theme::{LEADER_ARROW_COLOR, MINIMAP_FILL, MINIMAP_PADDING, MINIMAP_PLAYER_FILL, MINIMAP_SIZE, MINIMAP_STROKE, STROKE_INTENSITY, STROKE_SIZE}, turret::{TurretIdentityIds, TurretStructure}}, lerp, lerp_angle, normalize_angle, prettify_ms, prettify_score, rand, to_locale, utils::{color::Color, consts::{ARENA_SIZE, CLAN_DESC_LENGTH, CLAN_NAME_LENGTH, SCREEN_HEIGHT, SCREEN_WIDTH}, vec2::Vector2D}};
+use shared::{bool, connection::packets::{ClanPacketOpcode, Inputs, InventoryPacketOpcode, ServerboundPackets, SpectatePacketOpcode}, fuzzy_compare, game::{body::{BodyIdentity, BodyIdentityIds}, definitions::get_definitions, highscores::HighScorePeriod, gear::{Gear, GearSlot, INVENTORY_CAPACITY}, entity::{generate_identity, get_level_from_score, get_min_score_from_level, MarkerKind, Notification, SpectateMode, UpgradeStats, FICTITIOUS_TANK_RADIUS, MAX_STAT_INVESTMENT}, theme::{MINIMAP_FILL, MINIMAP_PADDING, MINIMAP_PLAYER_FILL, MINIMAP_SIZE, MINIMAP_STROKE, STROKE_INTENSITY, STROKE_SIZE}, turret::{TurretIdentityIds, TurretStructure}}, lerp, lerp_angle, normalize_angle, prettify_ms, prettify_score, rand, to_locale, utils::{codec::BinaryCodec, color::Color, consts::{CLAN_DESC_LENGTH, CLAN_NAME_LENGTH, SCREEN_HEIGHT, SCREEN_WIDTH}, vec2::Vector2D}};
 use strum::{EnumCount, IntoEnumIterator};
 use ui::{canvas2d::{Canvas2d, ShapeType, Transform}, core::{DeletionEffects, ElementType, Events, HoverEffects, OnClickScript, UiElement}, elements::{button::Button, checkbox::Checkbox, image::Image, input::Input, label::{Label, TextEffects}, modal::Modal, progress_bar::ProgressBar, rect::Rect, tank::Tank, tooltip::Tooltip}, get_debug_window_props, get_element_by_id_and_cast, translate, utils::sound::Sound};
 use rand::Rng;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{wasm_bindgen::JsCast, HtmlDivElement, HtmlInputElement, MouseEvent};
-use crate::{connection::{packets::{self, form_clan_packet_create, form_clan_packet_join}, socket::ConnectionState}, game::entity::base::{Entity, HealthState}, storage_get, storage_set, world::{get_world, World}};
-use shared::game::theme::{BAR_BACKGROUND, GRID_ALPHA, GRID_COLOR, GRID_SIZE, INBOUNDS_FILL, LEVEL_BAR_FOREGROUND, OUTBOUNDS_FILL, SCORE_BAR_FOREGROUND, UPGRADE_STAT_COLORS};
+use crate::{connection::{packets::{self, form_clan_packet_create, form_clan_packet_join, form_clan_packet_leave}, socket::ConnectionState}, game::entity::base::{Entity, HealthState, MarkerDisplay, DAMAGE_NUMBER_LIFETIME}, simulation::Simulation, storage_get, storage_set, world::{get_world, World}};
+use shared::game::theme::{ABILITY_BAR_FOREGROUND, BAR_BACKGROUND, ENERGY_BAR_FOREGROUND, GRID_ALPHA, GRID_COLOR, GRID_SIZE, INBOUNDS_FILL, LEVEL_BAR_FOREGROUND, OUTBOUNDS_FILL, SCORE_BAR_FOREGROUND, UPGRADE_STAT_COLORS};
 
-use self::packets::{form_input_packet, form_ping_packet, form_stats_packet, form_upgrade_packet};
+use self::packets::{form_stats_packet, form_upgrade_packet};
 
 use super::renderer::ModalType;
 
@@ -20,7 +20,8 @@ pub enum GamePhase {
     Lore(u8),
     Home(Box<HomescreenElements>),
     Game,
-    Death
+    Death,
+    Spectate
 }
 
 impl Default for GamePhase {
@@ -96,7 +97,8 @@ impl GamePhase {
             (GamePhase::Lore(_), GamePhase::Lore(_)) |
             (GamePhase::Home(_), GamePhase::Home(_)) |
             (GamePhase::Game, GamePhase::Game) |
-            (GamePhase::Death, GamePhase::Death)
+            (GamePhase::Death, GamePhase::Death) |
+            (GamePhase::Spectate, GamePhase::Spectate)
         )    
     }
 
@@ -236,7 +238,48 @@ impl GamePhase {
                     )
             )]);
 
-        let buttons: [(Vector2D, Color, &str, Box<OnClickScript>); 2] = [
+        let spectate = Button::new()
+            .with_id("spectate_button")
+            .with_fill(Color::MATERIAL_CYAN)
+            .with_dimensions(Vector2D::new(150.0, 50.0))
+            .with_transform(translate!(0.0, 180.0))
+            .with_events(Events::default()
+                .with_hover_effects(vec![
+                    HoverEffects::Inflation(1.1),
+                    HoverEffects::AdjustBrightness(0.0)
+                ])
+                .with_on_click(Box::new(|_| {
+                    spawn_local(async {
+                        let mut world = get_world();
+
+                        world.sounds.get_mut_sound("button_click").play();
+                        world.connection.send_message(packets::form_spectate_packet(SpectatePacketOpcode::Start), ServerboundPackets::Spectate);
+                    });
+                }))
+            )
+            .with_children(vec![Box::new(
+                Label::new()
+                    .with_id("spectate_text")
+                    .with_text("Spectate".to_string())
+                    .with_fill(Color::WHITE)
+                    .with_font(24.0)
+                    .with_stroke(Color::from_numeric(0x566c85))
+                    .with_transform(translate!(0.0, 7.5))
+                    .with_events(Events::default()
+                        .with_hover_effects(vec![HoverEffects::Inflation(1.1)])
+                    )
+            )]);
+
+        let population = Label::new()
+            .with_id("population_text")
+            .with_text(GamePhase::format_population(world))
+            .with_fill(Color::WHITE)
+            .with_font(20.0)
+            .with_stroke(Color::from_numeric(0x566c85))
+            .with_transform(translate!(0.0, 240.0))
+            .with_events(Events::default().with_hoverable(false));
+
+        let buttons: [(Vector2D, Color, &str, Box<OnClickScript>); 4] = [
             (
                 Vector2D::ZERO,
                 Color::GRAY, "{icon}\u{f013}",
@@ -254,6 +297,27 @@ impl GamePhase {
                         let _ = window().open_with_url("https://discord.gg/UTvaAAgku3");
                     });
                 })
+            ),
+            (
+                Vector2D::new(-160.0, 0.0),
+                Color::MATERIAL_PURPLE, "{icon}\u{f007}",
+                Box::new(|_| {
+                    spawn_local(async {
+                        get_world().renderer.modals.push(ModalType::Profile(0));
+                    });
+                })
+            ),
+            (
+                Vector2D::new(-240.0, 0.0),
+                Color::MATERIAL_ORANGE, "{icon}\u{f091}",
+                Box::new(|_| {
+                    spawn_local(async {
+                        let mut world = get_world();
+
+                        world.renderer.modals.push(ModalType::HighScores(0));
+                        world.connection.send_message(packets::form_high_scores_packet(), ServerboundPackets::HighScores);
+                    });
+                })
             )
         ];
 
@@ -363,6 +427,158 @@ impl GamePhase {
             *count += 1;
         }
 
+        if let Some(ModalType::Profile(count)) = world.renderer.modals.iter_mut().find(|x| matches!(x, ModalType::Profile(_))) {
+            let lines = match &world.game.profile {
+                Some(profile) => {
+                    let definitions = get_definitions();
+                    let achievements: Vec<&str> = profile.achievements.iter()
+                        .filter_map(|id| definitions.quests.iter().find(|quest| quest.id == *id))
+                        .map(|quest| quest.name.as_str())
+                        .collect();
+
+                    let mut lines = vec![
+                        format!("Runs: {}  |  Kills: {}  |  Playtime: {}",
+                            to_locale!(profile.runs), to_locale!(profile.kills), prettify_ms!(profile.playtime as f64 * 1000.0)),
+                        format!("Achievements: {}", if achievements.is_empty() { "None yet".to_string() } else { achievements.join(", ") }),
+                        "Best Scores:".to_string()
+                    ];
+
+                    lines.extend(profile.best_scores.iter()
+                        .take(5)
+                        .map(|record| format!("{} - {}", generate_identity(record.body, record.turret), to_locale!(record.score))));
+
+                    if profile.best_scores.is_empty() {
+                        lines.push("Finish a run to set one.".to_string());
+                    }
+
+                    lines
+                },
+                None => vec!["Loading your profile...".to_string()]
+            };
+
+            let mut children: Vec<Box<dyn UiElement>> = vec![
+                Box::new(Label::new()
+                    .with_id("profile")
+                    .with_text("Profile".to_string())
+                    .with_fill(Color::WHITE)
+                    .with_font(48.0)
+                    .with_stroke(Color::BLACK)
+                    .with_transform(translate!(500.0, 75.0))
+                    .with_events(Events::default().with_hoverable(false)))
+            ];
+
+            for (i, line) in lines.into_iter().enumerate() {
+                children.push(Box::new(Label::new()
+                    .with_id(&format!("profile-line-{}", i))
+                    .with_text(line)
+                    .with_fill(Color::WHITE)
+                    .with_font(if i < 3 { 28.0 } else { 24.0 })
+                    .with_stroke(Color::BLACK)
+                    .with_transform(translate!(500.0, 150.0 + (i as f32 * 45.0)))
+                    .with_events(Events::default().with_hoverable(false))));
+            }
+
+            let mut modal = Modal::new(*count == 0) 
+                .with_id("modal-profile")
+                .with_fill(Color::MATERIAL_PURPLE)
+                .with_dimensions(Vector2D::new(1000.0, 500.0), *count == 0)
+                .with_children(children)
+                .with_close_button(Box::new(|_| {
+                    spawn_local(async {
+                        get_world().renderer.modals
+                            .retain(|&e| !matches!(e, ModalType::Profile(_)));
+                    });
+                }));
+                        
+            elements.push(Box::new(modal));
+            *count += 1;
+        }
+
+        if let Some(ModalType::HighScores(count)) = world.renderer.modals.iter_mut().find(|x| matches!(x, ModalType::HighScores(_))) {
+            let high_scores = &world.game.high_scores;
+            let mut children: Vec<Box<dyn UiElement>> = vec![
+                Box::new(Label::new()
+                    .with_id("high-scores")
+                    .with_text("High Scores".to_string())
+                    .with_fill(Color::WHITE)
+                    .with_font(48.0)
+                    .with_stroke(Color::BLACK)
+                    .with_transform(translate!(500.0, 75.0))
+                    .with_events(Events::default().with_hoverable(false)))
+            ];
+
+            for (i, period) in HighScorePeriod::iter().enumerate() {
+                children.push(Box::new(Button::new()
+                    .with_id(&format!("high-scores-tab-{}", i))
+                    .with_fill(if period == high_scores.period { Color::MATERIAL_GREEN } else { Color::GRAY })
+                    .with_dimensions(Vector2D::new(150.0, 45.0))
+                    .with_transform(translate!(300.0 + (i as f32 * 200.0), 140.0))
+                    .with_events(Events::default()
+                        .with_hover_effects(vec![
+                            HoverEffects::Inflation(1.1),
+                            HoverEffects::AdjustBrightness(0.0)
+                        ])
+                        .with_on_click(Box::new(move |_| {
+                            spawn_local(async move {
+                                get_world().game.high_scores.period = period;
+                            });
+                        }))
+                    )
+                    .with_children(vec![Box::new(
+                        Label::new()
+                            .with_id(&format!("high-scores-tab-text-{}", i))
+                            .with_text(period.name().to_string())
+                            .with_fill(Color::WHITE)
+                            .with_font(22.0)
+                            .with_stroke(Color::BLACK)
+                            .with_transform(translate!(0.0, 7.5))
+                            .with_events(Events::default().with_hoverable(false))
+                    )])));
+            }
+
+            let lines: Vec<String> = match high_scores.tables.get(high_scores.period as usize) {
+                Some(table) if !table.is_empty() => table.iter()
+                    .enumerate()
+                    .map(|(rank, recorded)| format!("#{}  {}  |  {}  |  {}  |  {} kills  |  {}",
+                        rank + 1,
+                        recorded.run.name,
+                        generate_identity(recorded.run.body, recorded.run.turret),
+                        to_locale!(recorded.run.score),
+                        recorded.run.kills,
+                        prettify_ms!(recorded.run.duration as f64 * 1000.0)
+                    ))
+                    .collect(),
+                Some(_) => vec!["No runs have finished yet.".to_string()],
+                None => vec!["Loading...".to_string()]
+            };
+
+            for (i, line) in lines.into_iter().enumerate() {
+                children.push(Box::new(Label::new()
+                    .with_id(&format!("high-scores-line-{}", i))
+                    .with_text(line)
+                    .with_fill(Color::WHITE)
+                    .with_font(22.0)
+                    .with_stroke(Color::BLACK)
+                    .with_transform(translate!(500.0, 215.0 + (i as f32 * 36.0)))
+                    .with_events(Events::default().with_hoverable(false))));
+            }
+
+            let mut modal = Modal::new(*count == 0) 
+                .with_id("modal-high-scores")
+                .with_fill(Color::MATERIAL_ORANGE)
+                .with_dimensions(Vector2D::new(1000.0, 600.0), *count == 0)
+                .with_children(children)
+                .with_close_button(Box::new(|_| {
+                    spawn_local(async {
+                        get_world().renderer.modals
+                            .retain(|&e| !matches!(e, ModalType::HighScores(_)));
+                    });
+                }));
+                        
+            elements.push(Box::new(modal));
+            *count += 1;
+        }
+
         let connection_text = match world.connection.state {
             ConnectionState::Connected => "",
             ConnectionState::Connecting => "Connecting...",
@@ -381,6 +597,8 @@ impl GamePhase {
 
         if world.connection.state == ConnectionState::Connected {
             elements.push(Box::new(start));
+            elements.push(Box::new(spectate));
+            elements.push(Box::new(population));
         }
 
         elements
@@ -427,6 +645,19 @@ impl GamePhase {
             250.0
         );
 
+        if world.connection.state != ConnectionState::Connected {
+            elements.push(Box::new(
+                Label::new()
+                    .with_id("reconnecting_text")
+                    .with_text(if world.connection.state == ConnectionState::Failed { "Could not reconnect." } else { "Reconnecting..." }.to_string())
+                    .with_fill(Color::WHITE)
+                    .with_font(40.0)
+                    .with_stroke(Color::from_numeric(0x566c85))
+                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 - 150.0))
+                    .with_events(Events::default().with_hoverable(false))
+            ));
+        }
+
         'nametag: {
             let score = world.game.self_entity.display.score.value as usize;
             let level = get_level_from_score(world.game.self_entity.display.score.target as usize);
@@ -491,6 +722,63 @@ impl GamePhase {
             ));
         }
 
+        'energy: {
+            let (energy, max_energy) = (world.game.self_entity.stats.energy.value, world.game.self_entity.stats.max_energy.value);
+            if max_energy <= 0.0 { break 'energy; }
+
+            elements.push(Box::new(
+                ProgressBar::new()
+                    .with_id("energy_bar")
+                    .with_transform(translate!(dimensions.x / 2.0 - 212.5 - 110.0, dimensions.y - 25.0))
+                    .with_fill(BAR_BACKGROUND)
+                    .with_accent(ENERGY_BAR_FOREGROUND)
+                    .with_dimensions(Vector2D::new(180.0, 25.0))
+                    .with_value(energy.clamp(0.0, max_energy))
+                    .with_max(max_energy)
+                    .with_children(vec![Box::new(
+                        Label::new()
+                            .with_id("energy_bar_text")
+                            .with_text(format!("Energy: {}/{}", energy.round() as usize, max_energy.round() as usize))
+                            .with_fill(Color::WHITE)
+                            .with_font(16.0)
+                            .with_stroke(Color::BLACK)
+                            .with_events(Events::default().with_hoverable(false))
+                        )
+                    ])
+            ));
+        }
+
+        'ability: {
+            let Some(ability) = world.game.self_entity.display.ability.clone() else { break 'ability; };
+
+            let text = if ability.active {
+                format!("[F] {} (Active)", ability.kind.name())
+            } else {
+                format!("[F] {} ({}/{})", ability.kind.name(), ability.charges, ability.max_charges)
+            };
+
+            elements.push(Box::new(
+                ProgressBar::new()
+                    .with_id("ability_bar")
+                    .with_transform(translate!(dimensions.x / 2.0 + 212.5 + 110.0, dimensions.y - 25.0))
+                    .with_fill(BAR_BACKGROUND)
+                    .with_accent(if ability.charges > 0 { ABILITY_BAR_FOREGROUND } else { Color::GRAY })
+                    .with_dimensions(Vector2D::new(180.0, 25.0))
+                    .with_value(if ability.charges == ability.max_charges { 1.0 } else { 1.0 - ability.cooldown })
+                    .with_max(1.0)
+                    .with_children(vec![Box::new(
+                        Label::new()
+                            .with_id("ability_bar_text")
+                            .with_text(text)
+                            .with_fill(Color::WHITE)
+                            .with_font(16.0)
+                            .with_stroke(Color::BLACK)
+                            .with_events(Events::default().with_hoverable(false))
+                        )
+                    ])
+            ));
+        }
+
         'stats: {
             let (stat_width, stat_height) = (200.0, 20.0);
             let upgrades_center = Vector2D::new(
@@ -742,7 +1030,7 @@ impl GamePhase {
                                     let i = parts[4].parse::<usize>().unwrap();
 
                                     let upgrade_message = (if upgrade_type == 0 {
-                                        ""
+                                        String::new()
                                     } else {
                                         std::convert::TryInto::<TurretStructure>::try_into(
                                             std::convert::TryInto::<TurretIdentityIds>::try_into(identity_id).unwrap()
@@ -855,8 +1143,8 @@ impl GamePhase {
 
             elements.push(Box::new(
                 Label::new()
-                    .with_id("leaderboard_title")
-                    .with_text("Leaderboard".to_string())
+                    .with_id(&format!("leaderboard_title-{}", world.game.leaderboard.show_clans))
+                    .with_text((if world.game.leaderboard.show_clans { "Clans" } else { "Leaderboard" }).to_string())
                     .with_fill(Color::WHITE)
                     .with_font(18.0)
                     .with_stroke(Color::BLACK)
@@ -866,6 +1154,111 @@ impl GamePhase {
                     ))
             ));
 
+            elements.push(Box::new(
+                Button::new()
+                    .with_id(&format!("leaderboard_tab-{}", world.game.leaderboard.show_clans))
+                    .with_fill(Color::SOFT_GREEN)
+                    .with_dimensions(Vector2D::new(24.0, 24.0))
+                    .with_transform(translate!(dimensions.x - 52.0, 54.0))
+                    .with_events(Events::default()
+                        .with_hover_effects(vec![
+                            HoverEffects::Inflation(1.1),
+                            HoverEffects::AdjustBrightness(0.0)
+                        ])
+                        .with_on_click(Box::new(|_| {
+                            spawn_local(async {
+                                let mut world = get_world();
+                                world.game.leaderboard.show_clans = !world.game.leaderboard.show_clans;
+                            });
+                        }))
+                    )
+                    .with_children(vec![Box::new(
+                        Label::new()
+                            .with_id("leaderboard_tab_symbol")
+                            .with_text((if world.game.leaderboard.show_clans { "{icon}\u{f007}" } else { "{icon}\u{f0c0}" }).to_string())
+                            .with_fill(Color::WHITE)
+                            .with_font(12.0)
+                            .with_transform(translate!(0.0, 5.0))
+                            .with_events(Events::default().with_hoverable(false))
+                    )])
+            ));
+
+            if let Some(remaining) = world.game.leaderboard.war_remaining {
+                let leader = world.game.leaderboard.clans.iter()
+                    .filter(|clan| clan.war_kills > 0)
+                    .max_by_key(|clan| clan.war_kills);
+
+                elements.push(Box::new(
+                    Label::new()
+                        .with_id(&format!("leaderboard_war-{}-{}", remaining as u64 / 1000, leader.map(|clan| clan.war_kills).unwrap_or_default()))
+                        .with_text(match leader {
+                            Some(clan) => format!("Clan War: {} leads with {} ({})", clan.name, clan.war_kills, prettify_ms!(remaining)),
+                            None => format!("Clan War ends in {}", prettify_ms!(remaining))
+                        })
+                        .with_fill(Color::WHITE)
+                        .with_font(12.0)
+                        .with_stroke(Color::BLACK)
+                        .with_transform(translate!(
+                            (dimensions.x - leaderboard_width - 30.0) + leaderboard_width / 2.0,
+                            30.0 + leaderboard_height + 20.0
+                        ))
+                        .with_events(Events::default().with_hoverable(false))
+                ));
+            }
+
+            if world.game.leaderboard.show_clans {
+                if world.game.leaderboard.clans.is_empty() {
+                    elements.push(Box::new(
+                        Label::new()
+                            .with_id("leaderboard-clans-empty")
+                            .with_text("No clans exist yet.".to_string())
+                            .with_fill(Color::WHITE)
+                            .with_font(12.0)
+                            .with_stroke(Color::BLACK)
+                            .with_transform(translate!(
+                                (dimensions.x - leaderboard_width - 30.0) + leaderboard_width / 2.0,
+                                100.0
+                            ))
+                            .with_events(Events::default().with_hoverable(false))
+                    ));
+                }
+
+                let max_score = world.game.leaderboard.clans.first().map(|clan| clan.score).unwrap_or_default().max(1);
+                for (i, clan) in world.game.leaderboard.clans.iter().enumerate() {
+                    elements.push(Box::new(
+                        ProgressBar::new()
+                            .with_id(&format!("leaderboard-clan-bar-{}", i))
+                            .with_transform(translate!(
+                                (dimensions.x - leaderboard_width - 30.0) + leaderboard_width / 2.0,
+                                80.0 + (i as f32 * (entry_bar_height + 5.0))
+                            ))
+                            .with_fill(BAR_BACKGROUND)
+                            .with_accent(SCORE_BAR_FOREGROUND)
+                            .with_dimensions(Vector2D::new(entry_bar_width, entry_bar_height))
+                            .with_value(clan.score as f32)
+                            .with_max(max_score as f32)
+                            .with_children(vec![Box::new(
+                                Label::new()
+                                    .with_id(&format!("leaderboard-clan-text-{}", i))
+                                    .with_text(format!("{} ({}) – {} – {} kill{}",
+                                        clan.name,
+                                        clan.members,
+                                        prettify_score!(clan.score as f32),
+                                        clan.kills,
+                                        if clan.kills == 1 { "" } else { "s" }
+                                    ))
+                                    .with_fill(Color::WHITE)
+                                    .with_font(12.0)
+                                    .with_stroke(Color::BLACK)
+                                    .with_events(Events::default().with_hoverable(false))
+                            )])
+                            .with_events(Events::default().with_hoverable(false))
+                    ));
+                }
+
+                break 'leaderboard;
+            }
+
             let max_score = world.game.leaderboard.entries[0].0;
             for (i, (score, name, body_identity, turret_identity)) in world.game.leaderboard.entries.iter().enumerate() {
                 elements.push(Box::new(
@@ -909,7 +1302,7 @@ impl GamePhase {
         }
 
         'menu_items: {
-            let buttons: [(Vector2D, Color, &str, Box<OnClickScript>); 1] = [
+            let buttons: [(Vector2D, Color, &str, Box<OnClickScript>); 2] = [
                 (
                     Vector2D::ZERO,
                     if world.game.self_entity.display.clan_ping { Color::SOFT_YELLOW } else { Color::SOFT_GREEN }, "{icon}\u{f015}",
@@ -918,6 +1311,18 @@ impl GamePhase {
                             get_world().renderer.modals.push(ModalType::Clans(0));
                         });
                     })
+                ),
+                (
+                    Vector2D::new(60.0, 0.0),
+                    if world.game.inventory.requests.is_empty() { Color::SOFT_ORANGE } else { Color::SOFT_YELLOW }, "{icon}\u{f0b1}",
+                    Box::new(|_| {
+                        spawn_local(async {
+                            let mut world = get_world();
+                            if !world.renderer.modals.iter().any(|modal| matches!(modal, ModalType::Inventory(_))) {
+                                world.renderer.modals.push(ModalType::Inventory(0));
+                            }
+                        });
+                    })
                 )
             ];
     
@@ -1059,6 +1464,7 @@ impl GamePhase {
                         let self_in_clan = members.contains(&world.game.self_entity.id);
                         let pending_clan = world.game.self_entity.display.pending_clan_id;
                         let current_clan = world.game.self_entity.display.clan;
+                        let clickable = self_in_clan || pending_clan == Some(clan.id) || (pending_clan.is_none() && current_clan.is_none());
 
                         let position = Vector2D::new(
                             100.0 + (i / 3) as f32 * 400.0,
@@ -1110,7 +1516,7 @@ impl GamePhase {
                                 ),
                                 Box::new(
                                     Button::new()
-                                        .with_id(&format!("clan-{}-{}-{}-join-{}-{}", i, name, members.len(), self_in_clan as u8, clan.id))
+                                        .with_id(&format!("clan-{}-{}-{}-join-{}-{}-{}", i, name, members.len(), self_in_clan as u8, (pending_clan == Some(clan.id)) as u8, clan.id))
                                         .with_fill(if self_in_clan { 
                                             Color::MATERIAL_YELLOW
                                         } else if pending_clan == Some(clan.id) {
@@ -1123,10 +1529,10 @@ impl GamePhase {
                                         .with_dimensions(Vector2D::new(300.0, 50.0))
                                         .with_transform(translate!(dimensions.x / 2.0, 125.0))
                                         .with_events(Events::default()
-                                            .with_hover_effects(if pending_clan.is_some() || current_clan.is_some() { vec![] } else { vec![
+                                            .with_hover_effects(if clickable { vec![
                                                 HoverEffects::Inflation(1.1),
                                                 HoverEffects::AdjustBrightness(0.0)
-                                            ]})
+                                            ]} else { vec![] })
                                             .with_on_click(Box::new(move |button: Box<&dyn UiElement>| {
                                                 let id = button.get_id().split('-').last().unwrap().parse::<u32>().unwrap();
 
@@ -1134,11 +1540,14 @@ impl GamePhase {
                                                     let mut world = get_world();
 
                                                     if self_in_clan {
-
+                                                        world.game.self_entity.display.clan_ping = false;
+                                                        if !world.renderer.modals.iter().any(|modal| matches!(modal, ModalType::ClanView(_))) {
+                                                            world.renderer.modals.push(ModalType::ClanView(0));
+                                                        }
                                                     } else if pending_clan == Some(id) {
-
+                                                        world.connection.send_message(form_clan_packet_leave(), ServerboundPackets::Clan);
+                                                        world.game.self_entity.display.pending_clan_id = None;
                                                     } else if pending_clan.is_none() && current_clan.is_none() {
-                                                        console!("wow?".to_string());
                                                         world.connection.send_message(form_clan_packet_join(id), ServerboundPackets::Clan);
                                                         world.game.self_entity.display.pending_clan_id = Some(id);
                                                     }
@@ -1148,7 +1557,7 @@ impl GamePhase {
                                     .with_children(vec![Box::new(
                                         Label::new()
                                             .with_id(&format!("clan-{}-{}-{}-join-text", i, name, members.len()))
-                                            .with_text((if self_in_clan { "View" } else if pending_clan == Some(clan.id) { "Pending.." } else { "Join" }).to_string())
+                                            .with_text((if self_in_clan { "View" } else if pending_clan == Some(clan.id) { "Cancel Request" } else { "Join" }).to_string())
                                             .with_fill(Color::WHITE)
                                             .with_font(20.0)
                                             .with_stroke(Color::BLACK)
@@ -1306,38 +1715,470 @@ impl GamePhase {
                 elements.push(Box::new(modal));
                 *count += 1;
             }
-        }
 
-        elements
-    }
+            let self_id = world.game.self_entity.id;
+            let own_clan = world.game.clan_state.clans.iter().find(|clan| clan.members.contains(&self_id)).cloned();
 
-    pub fn render_game(world: &mut World, delta_average: f64, is_dead: bool, dt: f32) {
-        world.renderer.canvas2d.fill_style(OUTBOUNDS_FILL);
-        world.renderer.canvas2d.fill_rect(0.0, 0.0, world.renderer.canvas2d.get_width(), world.renderer.canvas2d.get_height());
+            if let Some(ModalType::ClanView(count)) = world.renderer.modals.iter_mut().find(|x| matches!(x, ModalType::ClanView(_))) && let Some(clan) = &own_clan {
+                let modal_dimensions = Vector2D::new(1000.0, 820.0);
+                let is_owner = clan.owner == self_id;
 
-        world.renderer.canvas2d.save();
+                let send = |codec: BinaryCodec| -> Box<OnClickScript> {
+                    Box::new(move |_| {
+                        let codec = codec.clone();
+                        spawn_local(async move {
+                            get_world().connection.send_message(codec, ServerboundPackets::Clan);
+                        });
+                    })
+                };
 
-        world.game.self_entity.lerp_all(dt, true);
+                let button = |id: String, text: String, fill: Color, dimensions: Vector2D, position: Vector2D, on_click: Box<OnClickScript>| -> Box<dyn UiElement> {
+                    Box::new(Button::new()
+                        .with_id(&id)
+                        .with_fill(fill)
+                        .with_dimensions(dimensions)
+                        .with_transform(translate!(position.x, position.y))
+                        .with_events(Events::default()
+                            .with_hover_effects(vec![
+                                HoverEffects::Inflation(1.1),
+                                HoverEffects::AdjustBrightness(0.0)
+                            ])
+                            .with_on_click(on_click)
+                        )
+                        .with_children(vec![Box::new(
+                            Label::new()
+                                .with_id(&format!("{}-text", id))
+                                .with_text(text)
+                                .with_fill(Color::WHITE)
+                                .with_font(18.0)
+                                .with_stroke(Color::BLACK)
+                                .with_transform(translate!(0.0, 6.0))
+                                .with_events(Events::default().with_hoverable(false))
+                        )]))
+                };
 
-        world.renderer.canvas2d.save();
+                let label = |id: String, text: String, font: f32, position: Vector2D| -> Box<dyn UiElement> {
+                    Box::new(Label::new()
+                        .with_id(&id)
+                        .with_text(text)
+                        .with_fill(Color::WHITE)
+                        .with_font(font)
+                        .with_stroke(Color::BLACK)
+                        .with_transform(translate!(position.x, position.y))
+                        .with_events(Events::default().with_hoverable(false))
+                    )
+                };
 
-        let factor = world.renderer.canvas2d.compute_factor();
-        let (screen_width, screen_height) = (world.renderer.canvas2d.get_width() / factor, world.renderer.canvas2d.get_height() / factor);
+                let get_name = |id: u32| world.game.clan_state.names.get(&id)
+                    .filter(|name| !name.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| "An unnamed tank".to_string());
 
-        world.renderer.canvas2d.translate(world.renderer.canvas2d.get_width() / 2.0, world.renderer.canvas2d.get_height() / 2.0);
-        world.renderer.canvas2d.scale(factor * world.game.self_entity.display.fov.value, factor * world.game.self_entity.display.fov.value);
-        world.renderer.canvas2d.translate(
-            -world.game.self_entity.physics.position.value.x, 
-            -world.game.self_entity.physics.position.value.y
-        );
+                let mut children: Vec<Box<dyn UiElement>> = vec![
+                    label(format!("clan-view-{}", clan.name), clan.name.clone(), 48.0, Vector2D::new(modal_dimensions.x / 2.0, 75.0)),
+                    label(format!("clan-view-description-{}", clan.description), clan.description.clone(), 20.0, Vector2D::new(modal_dimensions.x / 2.0, 115.0)),
+                    label(
+                        format!("clan-view-members-{}-{}", clan.members.len(), clan.max_members),
+                        format!("Members ({}/{})", clan.members.len(), clan.max_members),
+                        28.0, Vector2D::new(if is_owner { 280.0 } else { modal_dimensions.x / 2.0 }, 170.0)
+                    )
+                ];
 
-        world.renderer.canvas2d.fill_style(INBOUNDS_FILL);
-        world.renderer.canvas2d.fill_rect(0.0, 0.0, world.game.arena_size, world.game.arena_size);
+                for (i, &id) in clan.members.iter().take(8).enumerate() {
+                    let y = 225.0 + i as f32 * 55.0;
+                    let name = format!("{}{}", get_name(id), if id == clan.owner { " (Owner)" } else { "" });
 
-        GamePhase::render_grid(&mut world.renderer.canvas2d, world.game.self_entity.display.fov.value, world.game.arena_size);
+                    if !is_owner {
+                        children.push(label(format!("clan-view-member-{}-{}", id, name), name, 20.0, Vector2D::new(modal_dimensions.x / 2.0, y + 6.0)));
+                        continue;
+                    }
+
+                    children.push(label(format!("clan-view-member-{}-{}", id, name), name, 20.0, Vector2D::new(160.0, y + 6.0)));
+
+                    if id != self_id {
+                        children.push(button(
+                            format!("clan-view-kick-{}", id), "Kick".to_string(), Color::SOFT_RED,
+                            Vector2D::new(80.0, 40.0), Vector2D::new(340.0, y),
+                            send(packets::form_clan_packet_member(ClanPacketOpcode::Kick, id))
+                        ));
+
+                        children.push(button(
+                            format!("clan-view-transfer-{}", id), "Make Owner".to_string(), Color::SOFT_ORANGE,
+                            Vector2D::new(120.0, 40.0), Vector2D::new(455.0, y),
+                            send(packets::form_clan_packet_member(ClanPacketOpcode::Transfer, id))
+                        ));
+                    }
+                }
+
+                if clan.members.len() > 8 {
+                    let hidden = clan.members.len() - 8;
+                    children.push(label(
+                        format!("clan-view-members-hidden-{}", hidden), format!("...and {} more", hidden), 18.0,
+                        Vector2D::new(if is_owner { 280.0 } else { modal_dimensions.x / 2.0 }, 675.0)
+                    ));
+                }
+
+                if is_owner {
+                    children.push(label(format!("clan-view-requests-{}", clan.pending_members.len()), format!("Requests ({})", clan.pending_members.len()), 28.0, Vector2D::new(770.0, 170.0)));
+
+                    if clan.pending_members.is_empty() {
+                        children.push(label("clan-view-requests-empty".to_string(), "Nobody has asked to join.".to_string(), 18.0, Vector2D::new(770.0, 231.0)));
+                    }
+
+                    for (i, &id) in clan.pending_members.iter().take(8).enumerate() {
+                        let y = 225.0 + i as f32 * 55.0;
+                        let name = get_name(id);
+
+                        children.push(label(format!("clan-view-request-{}-{}", id, name), name, 20.0, Vector2D::new(690.0, y + 6.0)));
+                        children.push(button(
+                            format!("clan-view-accept-{}", id), "Accept".to_string(), Color::SOFT_GREEN,
+                            Vector2D::new(80.0, 40.0), Vector2D::new(840.0, y),
+                            send(packets::form_clan_packet_member(ClanPacketOpcode::Accept, id))
+                        ));
+                        children.push(button(
+                            format!("clan-view-reject-{}", id), "Reject".to_string(), Color::SOFT_RED,
+                            Vector2D::new(80.0, 40.0), Vector2D::new(930.0, y),
+                            send(packets::form_clan_packet_member(ClanPacketOpcode::Reject, id))
+                        ));
+                    }
+                }
+
+                children.push(button(
+                    "clan-view-distress".to_string(), "Call for Help".to_string(), Color::SOFT_YELLOW,
+                    Vector2D::new(200.0, 50.0), Vector2D::new(modal_dimensions.x / 2.0 - 120.0, 750.0),
+                    send(packets::form_clan_packet_distress())
+                ));
+
+                children.push(button(
+                    format!("clan-view-leave-{}", is_owner), (if is_owner { "Disband" } else { "Leave" }).to_string(), Color::RED,
+                    Vector2D::new(200.0, 50.0), Vector2D::new(modal_dimensions.x / 2.0 + 120.0, 750.0),
+                    Box::new(|_| {
+                        spawn_local(async {
+                            let mut world = get_world();
+                            world.connection.send_message(form_clan_packet_leave(), ServerboundPackets::Clan);
+                            world.renderer.modals.retain(|&e| !matches!(e, ModalType::ClanView(_)));
+                        });
+                    })
+                ));
+
+                let modal = Modal::new(*count == 0)
+                    .with_id("modal-clan-view")
+                    .with_fill(Color::GRAY)
+                    .with_dimensions(modal_dimensions, *count == 0)
+                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0))
+                    .with_children(children)
+                    .with_close_button(Box::new(|_| {
+                        spawn_local(async {
+                            get_world().renderer.modals
+                                .retain(|&e| !matches!(e, ModalType::ClanView(_)));
+                        });
+                    }));
+
+                elements.push(Box::new(modal));
+                *count += 1;
+            }
+
+            if own_clan.is_none() {
+                world.renderer.modals.retain(|&e| !matches!(e, ModalType::ClanView(_)));
+            }
+
+            if let Some(ModalType::Inventory(count)) = world.renderer.modals.iter_mut().find(|x| matches!(x, ModalType::Inventory(_))) {
+                let modal_dimensions = Vector2D::new(1000.0, 820.0);
+                let state = &world.game.inventory;
+
+                let send = |codec: fn() -> BinaryCodec| -> Box<OnClickScript> {
+                    Box::new(move |_| {
+                        spawn_local(async move {
+                            get_world().connection.send_message(codec(), ServerboundPackets::Inventory);
+                        });
+                    })
+                };
+
+                let button = |id: String, text: String, fill: Color, dimensions: Vector2D, position: Vector2D, on_click: Option<Box<OnClickScript>>| -> Box<dyn UiElement> {
+                    let mut events = Events::default().with_hover_effects(if on_click.is_some() { vec![
+                        HoverEffects::Inflation(1.1),
+                        HoverEffects::AdjustBrightness(0.0)
+                    ]} else { vec![] });
+
+                    if let Some(on_click) = on_click {
+                        events = events.with_on_click(on_click);
+                    }
+
+                    Box::new(Button::new()
+                        .with_id(&id)
+                        .with_fill(fill)
+                        .with_dimensions(dimensions)
+                        .with_transform(translate!(position.x, position.y))
+                        .with_events(events)
+                        .with_children(vec![Box::new(
+                            Label::new()
+                                .with_id(&format!("{}-text", id))
+                                .with_text(text)
+                                .with_fill(Color::WHITE)
+                                .with_font(18.0)
+                                .with_stroke(Color::BLACK)
+                                .with_transform(translate!(0.0, 6.0))
+                                .with_events(Events::default().with_hoverable(false))
+                        )]))
+                };
+
+                let label = |id: String, text: String, font: f32, position: Vector2D| -> Box<dyn UiElement> {
+                    Box::new(Label::new()
+                        .with_id(&id)
+                        .with_text(text)
+                        .with_fill(Color::WHITE)
+                        .with_font(font)
+                        .with_stroke(Color::BLACK)
+                        .with_transform(translate!(position.x, position.y))
+                        .with_events(Events::default().with_hoverable(false))
+                    )
+                };
+
+                let describe = |gear: &Gear| format!("{} {} (+{}% {})", gear.rarity.name(), gear.kind.name(), (gear.bonus() * 100.0).round(), gear.kind.stat_name());
+
+                let mut children: Vec<Box<dyn UiElement>> = vec![
+                    Box::new(Label::new()
+                        .with_id("inventory")
+                        .with_text("Inventory".to_string())
+                        .with_fill(Color::WHITE)
+                        .with_font(48.0)
+                        .with_stroke(Color::BLACK)
+                        .with_transform(translate!(modal_dimensions.x / 2.0, 75.0))
+                        .with_events(Events::default().with_hoverable(false))
+                    ),
+                    label("inventory-equipped".to_string(), "Equipped".to_string(), 28.0, Vector2D::new(250.0, 150.0)),
+                    label(
+                        format!("inventory-backpack-{}", state.inventory.items.len()),
+                        format!("Backpack ({}/{})", state.inventory.items.len(), INVENTORY_CAPACITY),
+                        28.0, Vector2D::new(730.0, 150.0)
+                    )
+                ];
+
+                // Equipped gear is unequipped when clicked.
+                for slot in GearSlot::iter() {
+                    let i = slot as usize;
+                    let gear = state.inventory.equipped[i];
+                    let text = match gear {
+                        Some(gear) => format!("{}: {}", slot.name(), describe(&gear)),
+                        None => format!("{}: Empty", slot.name())
+                    };
+
+                    children.push(button(
+                        format!("inventory-slot-{}-{}", i, text),
+                        text,
+                        gear.map_or(Color::MATERIAL_GRAY, |gear| gear.rarity.color()),
+                        Vector2D::new(420.0, 60.0),
+                        Vector2D::new(250.0, 210.0 + i as f32 * 75.0),
+                        gear.map(|_| -> Box<OnClickScript> { Box::new(move |_| {
+                            spawn_local(async move {
+                                get_world().connection.send_message(
+                                    packets::form_inventory_packet_item(InventoryPacketOpcode::Unequip, i),
+                                    ServerboundPackets::Inventory
+                                );
+                            });
+                        })})
+                    ));
+                }
+
+                // Items are equipped when clicked, or offered if there is a trade.
+                if state.inventory.items.is_empty() {
+                    children.push(label("inventory-empty".to_string(), "Kill orbs to find gear.".to_string(), 20.0, Vector2D::new(730.0, 215.0)));
+                }
+
+                for (i, gear) in state.inventory.items.iter().enumerate() {
+                    let offer = state.trade.as_ref().map(|trade| trade.offer.clone());
+                    let offered = offer.as_ref().is_some_and(|offer| offer.contains(&i));
+                    let text = format!("{}{}", if offered { "[Offered] " } else { "" }, describe(gear));
+
+                    children.push(button(
+                        format!("inventory-item-{}-{}", i, text),
+                        text,
+                        if offered { Color::SOFT_YELLOW } else { gear.rarity.color() },
+                        Vector2D::new(400.0, 50.0),
+                        Vector2D::new(710.0, 205.0 + i as f32 * 60.0),
+                        Some(Box::new(move |_| {
+                            let offer = offer.clone();
+                            spawn_local(async move {
+                                let codec = match offer {
+                                    Some(mut offer) => {
+                                        if let Some(idx) = offer.iter().position(|&index| index == i) {
+                                            offer.remove(idx);
+                                        } else {
+                                            offer.push(i);
+                                        }
+
+                                        packets::form_inventory_packet_trade_offer(&offer)
+                                    },
+                                    None => packets::form_inventory_packet_item(InventoryPacketOpcode::Equip, i)
+                                };
+
+                                get_world().connection.send_message(codec, ServerboundPackets::Inventory);
+                            });
+                        }))
+                    ));
+
+                    children.push(button(
+                        format!("inventory-discard-{}-{}", i, describe(gear)),
+                        "{icon}\u{f1f8}".to_string(),
+                        Color::SOFT_RED,
+                        Vector2D::new(44.0, 44.0),
+                        Vector2D::new(945.0, 205.0 + i as f32 * 60.0),
+                        Some(Box::new(move |_| {
+                            spawn_local(async move {
+                                get_world().connection.send_message(
+                                    packets::form_inventory_packet_item(InventoryPacketOpcode::Discard, i),
+                                    ServerboundPackets::Inventory
+                                );
+                            });
+                        }))
+                    ));
+                }
+
+                if let Some(trade) = &state.trade {
+                    children.push(label(format!("inventory-trade-{}", trade.partner), format!("Trading with {}", trade.partner_name), 28.0, Vector2D::new(250.0, 450.0)));
+                    children.push(label("inventory-trade-hint".to_string(), "Click your items to offer them.".to_string(), 18.0, Vector2D::new(250.0, 485.0)));
+                    children.push(label("inventory-trade-offer".to_string(), "They offer:".to_string(), 22.0, Vector2D::new(250.0, 530.0)));
+
+                    if trade.partner_offer.is_empty() {
+                        children.push(label("inventory-trade-offer-empty".to_string(), "Nothing yet.".to_string(), 18.0, Vector2D::new(250.0, 565.0)));
+                    }
+
+                    for (i, gear) in trade.partner_offer.iter().enumerate() {
+                        let text = format!("{} {}", gear.rarity.name(), gear.kind.name());
+                        children.push(Box::new(Label::new()
+                            .with_id(&format!("inventory-trade-offer-{}-{}", i, text))
+                            .with_text(text)
+                            .with_fill(gear.rarity.color())
+                            .with_font(18.0)
+                            .with_stroke(Color::BLACK)
+                            .with_transform(translate!(150.0 + (i % 2) as f32 * 200.0, 565.0 + (i / 2) as f32 * 28.0))
+                            .with_events(Events::default().with_hoverable(false))
+                        ));
+                    }
+
+                    children.push(label(
+                        format!("inventory-trade-status-{}", trade.partner_confirmed),
+                        if trade.partner_confirmed { format!("{} has confirmed.", trade.partner_name) } else { format!("Waiting for {} to confirm.", trade.partner_name) },
+                        18.0, Vector2D::new(250.0, 695.0)
+                    ));
+
+                    children.push(button(
+                        format!("inventory-trade-confirm-{}", trade.confirmed),
+                        (if trade.confirmed { "Confirmed" } else { "Confirm" }).to_string(),
+                        if trade.confirmed { Color::GRAY } else { Color::SOFT_GREEN },
+                        Vector2D::new(150.0, 50.0),
+                        Vector2D::new(165.0, 750.0),
+                        (!trade.confirmed).then(|| send(|| packets::form_inventory_packet_trade(InventoryPacketOpcode::TradeConfirm)))
+                    ));
+
+                    children.push(button(
+                        "inventory-trade-cancel".to_string(),
+                        "Cancel".to_string(),
+                        Color::SOFT_RED,
+                        Vector2D::new(150.0, 50.0),
+                        Vector2D::new(335.0, 750.0),
+                        Some(send(|| packets::form_inventory_packet_trade(InventoryPacketOpcode::TradeCancel)))
+                    ));
+                } else {
+                    children.push(label("inventory-clanmates".to_string(), "Clanmates".to_string(), 28.0, Vector2D::new(250.0, 450.0)));
+
+                    let self_id = world.game.self_entity.id;
+                    let clanmates: Vec<u32> = world.game.clan_state.clans.iter()
+                        .find(|clan| clan.members.contains(&self_id))
+                        .map(|clan| clan.members.iter().copied().filter(|&id| id != self_id).collect())
+                        .unwrap_or_default();
+
+                    if clanmates.is_empty() {
+                        children.push(label("inventory-clanmates-empty".to_string(), "Join a clan to trade with its members.".to_string(), 20.0, Vector2D::new(250.0, 510.0)));
+                    }
+
+                    for (i, &id) in clanmates.iter().take(5).enumerate() {
+                        let requested_by = state.requests.iter().find(|&&(from, _)| from == id);
+                        let name = requested_by.map(|(_, name)| name.clone())
+                            .or_else(|| world.game.surroundings.get(&id).map(|entity| entity.display.name.clone()))
+                            .filter(|name| !name.is_empty())
+                            .unwrap_or_else(|| format!("Player #{}", id));
+
+                        let (text, fill) = if requested_by.is_some() {
+                            (format!("Accept trade from {}", name), Color::SOFT_YELLOW)
+                        } else if state.requested == Some(id) {
+                            (format!("Requested {}...", name), Color::ORANGE)
+                        } else {
+                            (format!("Trade with {}", name), Color::SOFT_GREEN)
+                        };
+
+                        children.push(button(
+                            format!("inventory-clanmate-{}-{}", id, text),
+                            text,
+                            fill,
+                            Vector2D::new(420.0, 50.0),
+                            Vector2D::new(250.0, 510.0 + i as f32 * 60.0),
+                            (state.requested != Some(id)).then(|| -> Box<OnClickScript> { Box::new(move |_| {
+                                spawn_local(async move {
+                                    get_world().connection.send_message(
+                                        packets::form_inventory_packet_trade_request(id),
+                                        ServerboundPackets::Inventory
+                                    );
+                                });
+                            })})
+                        ));
+                    }
+                }
+
+                let modal = Modal::new(*count == 0)
+                    .with_id("modal-inventory")
+                    .with_fill(Color::GRAY)
+                    .with_dimensions(modal_dimensions, *count == 0)
+                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0))
+                    .with_children(children)
+                    .with_close_button(Box::new(|_| {
+                        spawn_local(async {
+                            get_world().renderer.modals
+                                .retain(|&e| !matches!(e, ModalType::Inventory(_)));
+                        });
+                    }));
+
+                elements.push(Box::new(modal));
+                *count += 1;
+            }
+        }
+
+        elements
+    }
+
+    pub fn render_game(world: &mut World, delta_average: f64, is_dead: bool, dt: f32) {
+        world.renderer.canvas2d.fill_style(OUTBOUNDS_FILL);
+        world.renderer.canvas2d.fill_rect(0.0, 0.0, world.renderer.canvas2d.get_width(), world.renderer.canvas2d.get_height());
+
+        world.renderer.canvas2d.save();
+
+        let render_time = world.game.clock.render_time(window().performance().unwrap().now());
+
+        world.game.self_entity.lerp_all(dt);
+        world.game.self_entity.interpolate(render_time, true);
+        Simulation::apply(world, dt);
+
+        world.renderer.canvas2d.save();
+
+        let factor = world.renderer.canvas2d.compute_factor();
+        let (screen_width, screen_height) = (world.renderer.canvas2d.get_width() / factor, world.renderer.canvas2d.get_height() / factor);
+
+        world.renderer.canvas2d.translate(world.renderer.canvas2d.get_width() / 2.0, world.renderer.canvas2d.get_height() / 2.0);
+        world.renderer.canvas2d.scale(factor * world.game.self_entity.display.fov.value, factor * world.game.self_entity.display.fov.value);
+        world.renderer.canvas2d.translate(
+            -world.game.self_entity.physics.position.value.x, 
+            -world.game.self_entity.physics.position.value.y
+        );
+
+        world.renderer.canvas2d.fill_style(INBOUNDS_FILL);
+        world.renderer.canvas2d.fill_rect(0.0, 0.0, world.game.world_info.arena_size, world.game.world_info.arena_size);
+
+        GamePhase::render_grid(&mut world.renderer.canvas2d, world.game.self_entity.display.fov.value, world.game.world_info.arena_size);
 
         let mut entities: Vec<u32> = world.game.surroundings.iter_mut().map(|(k, v)| *k).collect();
-        entities.push(world.game.self_entity.id);
+        if world.game.spectator.is_none() {
+            entities.push(world.game.self_entity.id);
+        }
 
         entities.sort_by(|a, b| {
             let a_index = world.game.get_mut_entity(*a).display.z_index;
@@ -1348,17 +2189,30 @@ impl GamePhase {
         for id in entities.iter_mut() {
             let is_self = *id == world.game.self_entity.id;
             if !is_self {
-                world.game.get_mut_entity(*id).lerp_all(dt, false);
+                let entity = world.game.get_mut_entity(*id);
+                entity.lerp_all(dt);
+                entity.interpolate(render_time, false);
             }
 
             Entity::render(world, *id, dt);
         }
 
         entities.iter().for_each(|&id| Entity::render_health_bar(world, id, dt));
+        entities.iter().for_each(|&id| Entity::render_status_effects(world, id));
         entities.iter().for_each(|&id| if id != world.game.self_entity.id { Entity::render_nametag(world, id, dt) });
+        GamePhase::render_damage_numbers(world, dt);
+        GamePhase::render_distress_calls(world);
 
         if world.game.self_entity.stats.health_state == HealthState::Alive {
             GamePhase::send_packets(world);
+        } else {
+            world.simulation.reset();
+        }
+
+        let inputs = world.game.self_entity.physics.inputs;
+        if let Some(spectator) = world.game.spectator.as_mut() && spectator.sent_inputs != inputs.get_value() {
+            spectator.sent_inputs = inputs.get_value();
+            world.connection.send_message(packets::form_spectate_packet_pan(inputs), ServerboundPackets::Spectate);
         }
 
         world.renderer.canvas2d.restore();
@@ -1366,13 +2220,22 @@ impl GamePhase {
 
         GamePhase::render_notifications(world, dt);
 
+        for display in world.game.markers.iter_mut() {
+            display.position.value.lerp_towards(display.position.target, 0.15 * dt);
+            display.opacity.value = lerp!(display.opacity.value, display.opacity.target, 0.15 * dt);
+        }
+
+        world.game.markers.retain(|display| display.opacity.target > 0.0 || display.opacity.value > 0.01);
+
         GamePhase::render_minimap(
             &mut world.renderer.canvas2d,
-            world.game.self_entity.physics.position.value
+            world.game.self_entity.physics.position.value,
+            &world.game.markers,
+            world.game.world_info.arena_size
         );
 
         if !is_dead {
-            GamePhase::render_leader_arrow(world, dt);
+            GamePhase::render_marker_arrows(world);
         }
         
         world.renderer.canvas2d.save();
@@ -1396,17 +2259,10 @@ impl GamePhase {
         let inverse_transform = world.renderer.canvas2d.get_transform().get_inverse();
         inverse_transform.transform_point(&mut mouse);
         
-        world.connection.send_message(form_input_packet(
-            world.game.self_entity.physics.inputs, 
-            mouse
-        ), ServerboundPackets::Input);
-
-        if world.renderer.time.ticks % 60 == 0 {
-            world.connection.send_message(form_ping_packet(), ServerboundPackets::Ping);
-        }
+        Simulation::tick(world, mouse);
     }
 
-    fn render_minimap(context: &mut Canvas2d, position: Vector2D) {
+    fn render_minimap(context: &mut Canvas2d, position: Vector2D, markers: &[MarkerDisplay], arena_size: f32) {
         context.save();
 
         let factor = context.compute_factor();
@@ -1428,7 +2284,18 @@ impl GamePhase {
         context.fill();
         context.stroke();
 
-        let minimap_position = position * (MINIMAP_SIZE / ARENA_SIZE);
+        for display in markers.iter() {
+            let marker_position = display.position.value * (MINIMAP_SIZE / arena_size);
+
+            context.save();
+            context.fill_style(display.marker.color);
+            context.global_alpha(display.opacity.value);
+            context.begin_arc(marker_position.x, marker_position.y, if display.marker.kind == MarkerKind::Distress { 3.5 } else { 2.5 }, std::f32::consts::TAU);
+            context.fill();
+            context.restore();
+        }
+
+        let minimap_position = position * (MINIMAP_SIZE / arena_size);
         context.fill_style(MINIMAP_PLAYER_FILL);
         context.begin_arc(minimap_position.x, minimap_position.y, 2.5, std::f32::consts::TAU);
         context.fill();
@@ -1459,6 +2326,52 @@ impl GamePhase {
         context.restore();
     }
 
+    /// Renders the damage dealt to and by the self entity, floating upwards and fading out.
+    fn render_damage_numbers(world: &mut World, dt: f32) {
+        let context = &mut world.renderer.canvas2d;
+        context.save();
+        context.set_font("bold 24px Ubuntu");
+        context.set_text_align("center");
+        context.set_text_baseline("middle");
+        context.set_stroke_size(4.0);
+        context.stroke_style(Color::BLACK);
+
+        for number in world.game.damage_numbers.iter_mut() {
+            number.lifetime -= dt;
+
+            let progress = 1.0 - (number.lifetime / DAMAGE_NUMBER_LIFETIME).clamp(0.0, 1.0);
+            let text = (number.damage.ceil() as usize).to_string();
+
+            context.save();
+            context.global_alpha(1.0 - progress * progress);
+            context.translate(number.position.x, number.position.y - 60.0 - 40.0 * progress);
+            context.fill_style(if number.taken { Color::RED } else { Color::WHITE });
+            context.stroke_text(&text);
+            context.fill_text(&text);
+            context.restore();
+        }
+
+        context.restore();
+        world.game.damage_numbers.retain(|number| number.lifetime > 0.0);
+    }
+
+    /// Renders the calls for help from clanmates as pulsing rings.
+    fn render_distress_calls(world: &mut World) {
+        let context = &mut world.renderer.canvas2d;
+        context.save();
+        context.set_stroke_size(8.0);
+
+        let pulse = (world.renderer.time.ticks % 30) as f32 / 30.0;
+        for display in world.game.markers.iter().filter(|display| display.marker.kind == MarkerKind::Distress) {
+            context.stroke_style(display.marker.color);
+            context.global_alpha(display.opacity.value * (1.0 - pulse));
+            context.begin_arc(display.position.value.x, display.position.value.y, 60.0 + 120.0 * pulse, std::f32::consts::TAU);
+            context.stroke();
+        }
+
+        context.restore();
+    }
+
     fn render_notifications(world: &mut World, dt: f32) {
         world.renderer.canvas2d.save();
         let factor = world.renderer.canvas2d.compute_factor();
@@ -1553,238 +2466,279 @@ impl GamePhase {
         world.renderer.canvas2d.restore();
     }
 
-    fn render_leader_arrow(world: &mut World, dt: f32) {
-        world.game.leaderboard.intersection.value.lerp_towards(world.game.leaderboard.intersection.target, 0.15 * dt);
-        
-        world.game.leaderboard.angle.value = lerp_angle!(
-            world.game.leaderboard.angle.value,
-            world.game.leaderboard.angle.target,
-            0.15 * dt
-        );
+    /// Renders an arrow at the edge of the screen pointing towards every marker which is off-screen.
+    fn render_marker_arrows(world: &mut World) {
+        let context = &mut world.renderer.canvas2d;
+        context.save();
 
-        world.game.leaderboard.arrow_opacity.value = lerp_angle!(
-            world.game.leaderboard.arrow_opacity.value,
-            world.game.leaderboard.arrow_opacity.target,
-            0.15 * dt
-        );
+        let factor = context.compute_factor();
+        let screen_dimensions = Vector2D::new(context.get_width() / factor, context.get_height() / factor);
 
-        world.renderer.canvas2d.save();
+        context.translate(context.get_width() / 2.0, context.get_height() / 2.0);
+        context.scale(factor, factor);
+        context.translate(-context.get_width() / (2.0 * factor), -context.get_height() / (2.0 * factor));
 
-        let factor = world.renderer.canvas2d.compute_factor();
-        let screen_dimensions = Vector2D::new(world.renderer.canvas2d.get_width() / factor, world.renderer.canvas2d.get_height() / factor);
+        context.set_font("14px Ubuntu");
+        context.stroke_style(Color::BLACK);
+        context.set_stroke_size(14.0 / 5.0);
+        context.set_text_align("center");
+        context.set_text_baseline("alphabetic");
 
-        world.renderer.canvas2d.translate(world.renderer.canvas2d.get_width() / 2.0, world.renderer.canvas2d.get_height() / 2.0);
-        world.renderer.canvas2d.scale(factor, factor);
-        world.renderer.canvas2d.translate(-world.renderer.canvas2d.get_width() / (2.0 * factor), -world.renderer.canvas2d.get_height() / (2.0 * factor));
+        let center = screen_dimensions * (1.0 / 2.0);
+        let (self_position, fov) = (world.game.self_entity.physics.position.value, world.game.self_entity.display.fov.value);
+        let arrow_size = 50.0;
 
-        world.renderer.canvas2d.fill_style(LEADER_ARROW_COLOR);
-        world.renderer.canvas2d.global_alpha(0.3 * world.game.leaderboard.arrow_opacity.value);
-        world.renderer.canvas2d.set_stroke_size(10.0);
+        for display in world.game.markers.iter() {
+            let offset = (display.position.value - self_position) * fov;
+            if offset.x.abs() < center.x && offset.y.abs() < center.y {
+                continue;
+            }
 
-        let center = screen_dimensions * (1.0 / 2.0);
-        let ray_length = center.max();
-        let end = center + Vector2D::from_polar(ray_length, world.game.leaderboard.angle.value);
-        let slope = (world.game.leaderboard.angle.value).tan();
+            let angle = offset.angle();
+            let inset = center - Vector2D::new(50.0, 50.0);
+            let intersection = center + offset * (inset.x / offset.x.abs()).min(inset.y / offset.y.abs());
 
-        world.game.leaderboard.intersection.target = if slope.abs() > (screen_dimensions.y / screen_dimensions.x) {
-            Vector2D::new(if end.y > center.y {
-                center.x + (screen_dimensions.y - center.y) / slope
-            } else {
-                center.x - center.y / slope
-            }, if end.y > center.y {
-                screen_dimensions.y
-            } else {
-                0.0
-            })
-        } else {
-            Vector2D::new(if end.x > center.x {
-                screen_dimensions.x
-            } else {
-                0.0
-            }, if end.x > center.x {
-                center.y + (screen_dimensions.x - center.x) * slope
-            } else {
-                center.y - center.x * slope
-            })
-        } + Vector2D::new(
-            50.0 * if end.x > center.x { -1.0 } else { 1.0 },
-            50.0 * if end.y > center.y { -1.0 } else { 1.0 }
-        );
+            let (sin_minus, cos_minus) = (angle - std::f32::consts::PI / 6.0).sin_cos();
+            let point1 = Vector2D::new(intersection.x - arrow_size * cos_minus, intersection.y - arrow_size * sin_minus);
 
-        world.renderer.canvas2d.begin_path();
-        let intersection = &world.game.leaderboard.intersection.value;
-        let angle = world.game.leaderboard.angle.value;
-        let arrow_size = 50.0;
+            let (sin_plus, cos_plus) = (angle + std::f32::consts::PI / 6.0).sin_cos();
+            let point2 = Vector2D::new(intersection.x - arrow_size * cos_plus, intersection.y - arrow_size * sin_plus);
 
-        let (sin_minus, cos_minus) = (angle - std::f32::consts::PI / 6.0).sin_cos();
-        let point1 = Vector2D::new(
-            intersection.x - arrow_size * cos_minus,
-            intersection.y - arrow_size * sin_minus,
-        );
+            context.save();
+            context.fill_style(display.marker.color);
+            context.global_alpha(0.3 * display.opacity.value);
 
-        let (sin_plus, cos_plus) = (angle + std::f32::consts::PI / 6.0).sin_cos();
-        let point2 = Vector2D::new(
-            intersection.x - arrow_size * cos_plus,
-            intersection.y - arrow_size * sin_plus,
-        );
+            context.begin_path();
+            context.move_to(intersection.x, intersection.y);
+            context.line_to(point1.x, point1.y);
+            context.line_to(point2.x, point2.y);
+            context.line_to(intersection.x, intersection.y);
+            context.fill();
 
-        world.renderer.canvas2d.move_to(intersection.x, intersection.y);
-        world.renderer.canvas2d.line_to(point1.x, point1.y);
-        world.renderer.canvas2d.move_to(intersection.x, intersection.y);
-        world.renderer.canvas2d.line_to(point2.x, point2.y);
-        world.renderer.canvas2d.move_to(point1.x, point1.y);
-        world.renderer.canvas2d.line_to(point2.x, point2.y);
-        world.renderer.canvas2d.line_to(intersection.x, intersection.y);
-
-        world.renderer.canvas2d.fill();
-
-        world.renderer.canvas2d.set_font("14px Ubuntu");
-        world.renderer.canvas2d.fill_style(Color::WHITE);
-        world.renderer.canvas2d.stroke_style(Color::BLACK);
-        world.renderer.canvas2d.set_stroke_size(14.0 / 5.0);
-        world.renderer.canvas2d.global_alpha(0.95 * world.game.leaderboard.arrow_opacity.value);
-
-        let midpoint = Vector2D::new(
-            (point1.x + point2.x) / 2.0,
-            (point1.y + point2.y) / 2.0
-        );
-        
-        let mut dir_vector = midpoint - *intersection;
-        let padding_distance = 15.0;
-        let padded_midpoint = if dir_vector.magnitude() > 0.0 {
-            dir_vector.normalize();
-            midpoint + dir_vector * padding_distance
-        } else {
-            midpoint
-        };
-        
-        let delta_x = point2.x - point1.x;
-        let delta_y = point2.y - point1.y;
-        let text_angle = delta_y.atan2(delta_x);
-        
-        world.renderer.canvas2d.translate(padded_midpoint.x, padded_midpoint.y);
-        world.renderer.canvas2d.rotate(text_angle);
-        
-        world.renderer.canvas2d.set_text_align("center");
-        world.renderer.canvas2d.set_text_baseline("alphabetic");
-        
-        world.renderer.canvas2d.stroke_text("LEADER");
-        world.renderer.canvas2d.fill_text("LEADER");
-        
-        world.renderer.canvas2d.restore();
+            let text = match display.marker.kind {
+                MarkerKind::Leader => "LEADER".to_string(),
+                MarkerKind::Clanmate => world.game.clan_state.names.get(&display.marker.id)
+                    .filter(|name| !name.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| "CLANMATE".to_string()),
+                MarkerKind::Distress => "HELP".to_string(),
+                MarkerKind::Objective => "RADIANT".to_string()
+            };
+
+            let midpoint = (point1 + point2) * (1.0 / 2.0);
+            let mut direction = midpoint - intersection;
+            direction.normalize();
+
+            let padded_midpoint = midpoint + direction * 15.0;
+            let text_angle = (point2.y - point1.y).atan2(point2.x - point1.x);
+
+            context.fill_style(Color::WHITE);
+            context.global_alpha(0.95 * display.opacity.value);
+            context.translate(padded_midpoint.x, padded_midpoint.y);
+            context.rotate(text_angle);
+            context.stroke_text(&text);
+            context.fill_text(&text);
+            context.restore();
+        }
+
+        context.restore();
     }
 
     pub fn generate_death_elements(world: &mut World) -> Vec<Box<dyn UiElement>> {
         let dimensions = world.renderer.canvas2d.get_dimensions() * (1.0 / window().device_pixel_ratio() as f32);
-        let mut elements: Vec<Box<dyn UiElement>> = vec![
-            Box::new(
-                Label::new()
-                    .with_id("death_starter")
-                    .with_text("You died!".to_string())
-                    .with_fill(Color::WHITE)
-                    .with_font(21.0)
-                    .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 - 100.0))
-                    .with_events(Events::default().with_hoverable(false))
-            ),
-            Box::new(
-                Label::new()
-                    .with_id("score_tag")
-                    .with_text("Score:".to_string())
-                    .with_fill(Color::WHITE)
-                    .with_font(21.0)
-                    .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0 - 75.0, dimensions.y / 2.0 - 30.0))
-                    .with_events(Events::default().with_hoverable(false))
-                    .with_align("right")
-            ),
-            Box::new(
-                Label::new()
-                    .with_id("score_value")
-                    .with_text(to_locale!(world.game.self_entity.display.score.value as u32))
-                    .with_fill(Color::WHITE)
-                    .with_font(21.0)
-                    .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 - 30.0))
-                    .with_events(Events::default().with_hoverable(false))
-                    .with_align("center")
-            ),
-            Box::new(
-                Label::new()
-                    .with_id("kills_tag")
-                    .with_text("Kills:".to_string())
-                    .with_fill(Color::WHITE)
-                    .with_font(21.0)
-                    .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0 - 75.0, dimensions.y / 2.0))
-                    .with_events(Events::default().with_hoverable(false))
-                    .with_align("right")
-            ),
-            Box::new(
-                Label::new()
-                    .with_id("kills_value")
-                    .with_text(world.game.self_entity.display.kills.to_string())
-                    .with_fill(Color::WHITE)
-                    .with_font(21.0)
-                    .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0))
-                    .with_events(Events::default().with_hoverable(false))
-                    .with_align("center")
-            ),
+        let summary = world.game.death_summary.clone();
+
+        let label = |id: &str, text: String, font: f32, x: f32, y: f32, align: &'static str| -> Box<dyn UiElement> {
             Box::new(
                 Label::new()
-                    .with_id("time_alive_tag")
-                    .with_text("Time Alive:".to_string())
+                    .with_id(id)
+                    .with_text(text)
                     .with_fill(Color::WHITE)
-                    .with_font(21.0)
+                    .with_font(font)
                     .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0 - 75.0, dimensions.y / 2.0 + 30.0))
+                    .with_transform(translate!(dimensions.x / 2.0 + x, dimensions.y / 2.0 + y))
                     .with_events(Events::default().with_hoverable(false))
-                    .with_align("right")
-            ),
+                    .with_align(align)
+            )
+        };
+
+        let mut elements: Vec<Box<dyn UiElement>> = vec![
+            label("death_starter", "You died!".to_string(), 28.0, 0.0, -190.0, "center")
+        ];
+
+        if let Some(killer) = summary.killer.as_ref() {
+            elements.push(label("killer_tag", format!("Killed by {}", killer), 21.0, 0.0, -155.0, "center"));
+        }
+
+        if let Some((body, turret)) = summary.killer_tank {
+            elements.push(Box::new(
+                Tank::new()
+                    .with_id(&format!("killer_tank-{}-{}", body as usize, turret as usize))
+                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 - 110.0))
+                    .with_radius(25.0)
+                    .with_stroke(STROKE_SIZE * (25.0 / FICTITIOUS_TANK_RADIUS))
+                    .with_body_identity(std::convert::TryInto::<BodyIdentity>::try_into(body).unwrap())
+                    .with_turret_structure(std::convert::TryInto::<TurretStructure>::try_into(turret).unwrap())
+                    .with_angle(-std::f32::consts::FRAC_PI_2)
+            ));
+        }
+
+        let statistics = [
+            ("score", "Score:", to_locale!(summary.score as u32)),
+            ("kills", "Kills:", summary.kills.to_string()),
+            ("level", "Level:", summary.level.to_string()),
+            ("time_alive", "Time Alive:", prettify_ms!(summary.time_alive))
+        ];
+
+        for (i, (id, tag, value)) in statistics.into_iter().enumerate() {
+            let y = -55.0 + i as f32 * 30.0;
+            elements.push(label(&format!("{}_tag", id), tag.to_string(), 21.0, -75.0, y, "right"));
+            elements.push(label(&format!("{}_value", id), value, 21.0, 0.0, y, "center"));
+        }
+
+        if !summary.damage_taken.is_empty() {
+            elements.push(label("damage_taken_tag", "Damage Taken".to_string(), 18.0, 0.0, 85.0, "center"));
+        }
+
+        for (i, (source, damage)) in summary.damage_taken.iter().enumerate() {
+            elements.push(label(
+                &format!("damage_taken_{}", i),
+                format!("{}: {}", source, to_locale!(damage.ceil() as u32)),
+                15.0, 0.0, 110.0 + i as f32 * 22.0, "center"
+            ));
+        }
+
+        let button_offset = if summary.damage_taken.is_empty() {
+            100.0
+        } else {
+            145.0 + summary.damage_taken.len() as f32 * 22.0
+        };
+
+        elements.push(Box::new(
+            Button::new()
+                .with_id("start_button")
+                .with_fill(Color::GREEN)
+                .with_dimensions(Vector2D::new(150.0, 50.0))
+                .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 + button_offset))
+                .with_events(Events::default()
+                    .with_hover_effects(vec![
+                        HoverEffects::Inflation(1.1),
+                        HoverEffects::AdjustBrightness(0.0)
+                    ])
+                    .with_on_click(Box::new(|_| {
+                        spawn_local(async {
+                            let mut world = get_world();
+                            world.renderer.change_phase(GamePhase::Home(Box::default()));
+                        });
+                    }))
+                )
+                .with_children(vec![Box::new(
+                    Label::new()
+                        .with_id("cont_text")
+                        .with_text("Continue".to_string())
+                        .with_fill(Color::WHITE)
+                        .with_font(24.0)
+                        .with_stroke(Color::BLACK)
+                        .with_transform(translate!(0.0, 7.5))
+                        .with_events(Events::default()
+                            .with_hover_effects(vec![HoverEffects::Inflation(1.1)])
+                        )
+                )])
+        ));
+
+        elements
+    }
+    /// Describes how many players and spectators are in the arena.
+    fn format_population(world: &World) -> String {
+        let (players, spectators) = (world.game.leaderboard.players, world.game.leaderboard.spectators);
+        format!(
+            "{} player{}, {} spectating",
+            players, if players == 1 { "" } else { "s" },
+            spectators
+        )
+    }
+
+    pub fn generate_spectate_elements(world: &mut World) -> Vec<Box<dyn UiElement>> {
+        let dimensions = world.renderer.canvas2d.get_dimensions() * (1.0 / window().device_pixel_ratio() as f32);
+        let Some(spectator) = world.game.spectator.clone() else { return vec![]; };
+
+        let spectated = spectator.spectated
+            .and_then(|id| world.game.surroundings.get(&id))
+            .map(|entity| entity.display.name.clone());
+
+        let title = match (spectator.mode, spectated) {
+            (SpectateMode::Free, _) => "Free Camera".to_string(),
+            (SpectateMode::Leader, Some(name)) => format!("Spectating {} (Leader)", name),
+            (SpectateMode::Player, Some(name)) => format!("Spectating {}", name),
+            (_, None) => "Waiting for players...".to_string()
+        };
+
+        let label = |id: &str, text: String, font: f32, y: f32| -> Box<dyn UiElement> {
             Box::new(
                 Label::new()
-                    .with_id("time_alive_value")
-                    .with_text(prettify_ms!(world.game.self_entity.stats.life_timestamps.1 - world.game.self_entity.stats.life_timestamps.0))
+                    .with_id(id)
+                    .with_text(text)
                     .with_fill(Color::WHITE)
-                    .with_font(21.0)
+                    .with_font(font)
                     .with_stroke(Color::BLACK)
-                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 + 30.0))
+                    .with_transform(translate!(dimensions.x / 2.0, y))
                     .with_events(Events::default().with_hoverable(false))
-                    .with_align("center")
-            ),
-            Box::new(
+            )
+        };
+
+        let mut elements: Vec<Box<dyn UiElement>> = vec![
+            label("spectate_title", title, 28.0, 50.0),
+            label("spectate_population", GamePhase::format_population(world), 18.0, 80.0)
+        ];
+
+        if spectator.mode == SpectateMode::Free {
+            elements.push(label("spectate_hint", "Move the camera with WASD or the arrow keys.".to_string(), 16.0, 105.0));
+        }
+
+        let buttons: [(&str, Color, SpectatePacketOpcode); 5] = [
+            ("Previous", Color::MATERIAL_CYAN, SpectatePacketOpcode::Previous),
+            ("Leader", Color::MATERIAL_ORANGE, SpectatePacketOpcode::Leader),
+            ("Next", Color::MATERIAL_CYAN, SpectatePacketOpcode::Next),
+            ("Free Camera", Color::MATERIAL_PURPLE, SpectatePacketOpcode::Free),
+            ("Leave", Color::MATERIAL_RED, SpectatePacketOpcode::Stop)
+        ];
+
+        let width = 140.0;
+        let start = dimensions.x / 2.0 - (buttons.len() as f32 - 1.0) * (width + 15.0) / 2.0;
+
+        for (i, (text, color, opcode)) in buttons.into_iter().enumerate() {
+            elements.push(Box::new(
                 Button::new()
-                    .with_id("start_button")
-                    .with_fill(Color::GREEN)
-                    .with_dimensions(Vector2D::new(150.0, 50.0))
-                    .with_transform(translate!(dimensions.x / 2.0, dimensions.y / 2.0 + 100.0))
+                    .with_id(&format!("spectate_button-{}", i))
+                    .with_fill(color)
+                    .with_dimensions(Vector2D::new(width, 45.0))
+                    .with_transform(translate!(start + i as f32 * (width + 15.0), dimensions.y - 50.0))
                     .with_events(Events::default()
                         .with_hover_effects(vec![
                             HoverEffects::Inflation(1.1),
                             HoverEffects::AdjustBrightness(0.0)
                         ])
-                        .with_on_click(Box::new(|_| {
-                            spawn_local(async {
-                                let mut world = get_world();
-                                world.renderer.change_phase(GamePhase::Home(Box::default()));
+                        .with_on_click(Box::new(move |_| {
+                            let opcode = opcode.clone();
+                            spawn_local(async move {
+                                get_world().connection.send_message(packets::form_spectate_packet(opcode), ServerboundPackets::Spectate);
                             });
                         }))
                     )
                     .with_children(vec![Box::new(
                         Label::new()
-                            .with_id("cont_text")
-                            .with_text("Continue".to_string())
+                            .with_id(&format!("spectate_button_text-{}", i))
+                            .with_text(text.to_string())
                             .with_fill(Color::WHITE)
-                            .with_font(24.0)
+                            .with_font(20.0)
                             .with_stroke(Color::BLACK)
-                            .with_transform(translate!(0.0, 7.5))
+                            .with_transform(translate!(0.0, 7.0))
                             .with_events(Events::default()
                                 .with_hover_effects(vec![HoverEffects::Inflation(1.1)])
                             )
                     )])
-            ),
-        ];
+            ));
+        }
 
         elements
     }
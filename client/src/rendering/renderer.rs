@@ -2,11 +2,11 @@ use std::{collections::VecDeque};
 
 use gloo::console::{self, console};
 use gloo_utils::{document, window};
-use shared::{fuzzy_compare, game::theme::OUTBOUNDS_FILL, lerp, utils::{color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
+use shared::{connection::packets::ServerboundPackets, fuzzy_compare, game::theme::OUTBOUNDS_FILL, lerp, utils::{color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
 use ui::{canvas2d::{Canvas2d, Transform}, core::{ElementType, Events, HoverEffects, UiElement}, elements::{body::Body, button::Button, label::{Label, TextEffects}}, get_element_by_id_and_cast, gl::webgl::WebGl, storage_get, translate};
 use web_sys::{wasm_bindgen::{prelude::Closure, JsCast}, HtmlDivElement, HtmlInputElement, Performance};
 
-use crate::{connection::socket::ConnectionState, world::{self, get_world, World}, SHADERS_ENABLED};
+use crate::{connection::{packets::form_ping_packet, socket::ConnectionState}, world::{self, get_world, World}, SHADERS_ENABLED};
 
 use super::phases::GamePhase;
 
@@ -22,7 +22,12 @@ pub struct TimeInformation {
 pub enum ModalType {
     Settings(usize),
     Clans(usize),
-    ClanCreate(usize)
+    ClanCreate(usize),
+    /// The clan the self entity is a member of.
+    ClanView(usize),
+    Inventory(usize),
+    Profile(usize),
+    HighScores(usize)
 }
 
 pub struct Renderer {
@@ -105,7 +110,7 @@ impl Renderer {
                     world.sounds.get_mut_sound("soundtrack_home").play();
                     world.sounds.get_mut_sound("soundtrack_game").stop();
                 },
-                GamePhase::Game | GamePhase::Death => {
+                GamePhase::Game | GamePhase::Death | GamePhase::Spectate => {
                     world.sounds.get_mut_sound("soundtrack_lore").stop();
                     world.sounds.get_mut_sound("soundtrack_home").stop();
                     world.sounds.get_mut_sound("soundtrack_game").play();
@@ -177,6 +182,7 @@ impl Renderer {
             GamePhase::Home(_) => GamePhase::generate_homescreen_elements(world),
             GamePhase::Game => GamePhase::generate_game_elements(world),
             GamePhase::Death => GamePhase::generate_death_elements(world),
+            GamePhase::Spectate => GamePhase::generate_spectate_elements(world),
         };
         
         let mut element_ids: Vec<String> = elements.iter().map(|e| e.get_id()).collect();
@@ -203,7 +209,13 @@ impl Renderer {
             GamePhase::Lore(_) => Renderer::render_lore(world, delta_average),
             GamePhase::Home(_) => Renderer::render_homescreen(world, delta_average),
             GamePhase::Game => Renderer::render_game(world, delta_average, false),
-            GamePhase::Death => Renderer::render_game(world, delta_average, true)
+            GamePhase::Death => Renderer::render_game(world, delta_average, true),
+            GamePhase::Spectate => Renderer::render_game(world, delta_average, false)
+        }
+
+        // The server disconnects clients which stop pinging, so every phase pings.
+        if world.connection.state == ConnectionState::Connected && world.renderer.time.ticks.is_multiple_of(60) {
+            world.connection.send_message(form_ping_packet(), ServerboundPackets::Ping);
         }
 
         world.connection.latency.value = lerp!(world.connection.latency.value, world.connection.latency.target, 0.15 * dt as f64);
@@ -0,0 +1,263 @@
+use std::collections::VecDeque;
+use gloo_utils::window;
+use shared::{connection::packets::{Inputs, ServerboundPackets}, game::{ability::AbilityKind, entity::InputFlags, movement::{get_movement_direction, MovementState}, status::StatusEffectKind}, utils::{consts::TICK_RATE, vec2::Vector2D}};
+
+use crate::{connection::packets::form_input_packet, game::entity::base::Entity, world::World};
+
+pub mod snapshots;
+
+/// The duration of one server tick, in milliseconds.
+const TICK_DURATION: f64 = 1000.0 / TICK_RATE as f64;
+/// The maximum number of ticks simulated in a single frame.
+/// Anything beyond this is dropped so a stalled tab doesn't flood the server.
+const MAX_TICKS_PER_FRAME: usize = 5;
+/// The maximum number of unacknowledged inputs kept for replay.
+const MAX_HISTORY: usize = 128;
+/// The misprediction distance past which the tank snaps instead of smoothing.
+const SNAP_DISTANCE: f32 = 250.0;
+/// The fraction of the misprediction error corrected per frame (at 60fps).
+const ERROR_CORRECTION: f32 = 0.15;
+
+/// An input which has been applied locally but not yet acknowledged.
+#[derive(Debug, Clone, Copy)]
+struct PredictedInput {
+    sequence: u32,
+    inputs: InputFlags,
+    speed: f32,
+    /// The impulse of a dash used on this tick.
+    impulse: Vector2D
+}
+
+/// Predicts the movement of the player's own tank ahead of the server,
+/// reconciling against the authoritative state whenever an input is acknowledged.
+#[derive(Debug, Default)]
+pub struct Simulation {
+    /// The sequence number of the last input sent.
+    sequence: u32,
+    /// Inputs sent to the server which have not been acknowledged.
+    history: VecDeque<PredictedInput>,
+    /// The predicted state one tick ago.
+    previous: MovementState,
+    /// The predicted state after the last input.
+    current: MovementState,
+    /// Time not yet consumed by a tick, in milliseconds.
+    accumulator: f64,
+    last_timestamp: f64,
+    /// The offset between what was rendered and the corrected prediction.
+    error: Vector2D,
+    /// Whether the ability input was held on the last tick, so a dash is only predicted when it is pressed.
+    ability_held: bool,
+    active: bool
+}
+
+impl Simulation {
+    /// Advances the prediction by however many ticks have elapsed,
+    /// sending one input packet per tick.
+    pub fn tick(world: &mut World, mouse: Vector2D) {
+        let now = window().performance().unwrap().now();
+
+        if !world.simulation.active {
+            let physics = &world.game.self_entity.physics;
+            world.simulation.start(MovementState::new(physics.position.target, physics.velocity.target, physics.additional_velocity), now);
+        }
+
+        world.simulation.accumulator += now - world.simulation.last_timestamp;
+        world.simulation.last_timestamp = now;
+
+        let ticks = ((world.simulation.accumulator / TICK_DURATION) as usize).min(MAX_TICKS_PER_FRAME);
+        world.simulation.accumulator = (world.simulation.accumulator - ticks as f64 * TICK_DURATION).min(TICK_DURATION);
+
+        let (speed, inputs) = (world.game.self_entity.physics.acceleration, world.game.self_entity.physics.inputs);
+
+        for _ in 0..ticks {
+            let impulse = world.simulation.predict_dash(&mut world.game.self_entity);
+            let sequence = world.simulation.predict(inputs, speed, impulse, world.game.world_info.arena_size);
+            world.connection.send_message(form_input_packet(inputs, mouse, sequence), ServerboundPackets::Input);
+        }
+    }
+
+    /// Begins predicting from the given state.
+    fn start(&mut self, state: MovementState, now: f64) {
+        self.active = true;
+        self.previous = state;
+        self.current = state;
+        self.last_timestamp = now;
+    }
+
+    /// The impulse of the dash the server will perform this tick, if any.
+    /// The charge and energy are spent locally until the server reports them.
+    fn predict_dash(&mut self, entity: &mut Entity) -> Vector2D {
+        let pressed = entity.physics.inputs.is_set(Inputs::Ability);
+        let just_pressed = pressed && !self.ability_held;
+        self.ability_held = pressed;
+
+        let ability = entity.display.turret_identity.ability.or(entity.display.body_identity.ability);
+        let (Some(ability), Some(display)) = (ability, entity.display.ability.as_mut()) else { return Vector2D::ZERO; };
+
+        if !just_pressed
+            || ability.kind != AbilityKind::Dash
+            || display.kind != AbilityKind::Dash
+            || display.charges == 0
+            || entity.stats.energy.target < ability.energy_cost
+            || entity.display.status_effects.iter().any(|&(kind, _, _)| kind == StatusEffectKind::Stun)
+        {
+            return Vector2D::ZERO;
+        }
+
+        display.charges -= 1;
+        entity.stats.energy.target -= ability.energy_cost;
+
+        let movement = get_movement_direction(entity.physics.inputs);
+        let direction = if movement == Vector2D::ZERO { entity.physics.mouse.angle() } else { movement.angle() };
+        Vector2D::from_polar(ability.magnitude, direction)
+    }
+
+    /// Simulates one tick of input, returning its sequence number.
+    fn predict(&mut self, inputs: InputFlags, speed: f32, impulse: Vector2D, arena_size: f32) -> u32 {
+        self.sequence += 1;
+
+        self.previous = self.current;
+        self.current.tick_tank(inputs, speed, impulse, arena_size);
+
+        self.history.push_back(PredictedInput { sequence: self.sequence, inputs, speed, impulse });
+        if self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+        }
+
+        self.sequence
+    }
+
+    /// Writes the predicted state into the player's own tank for rendering.
+    pub fn apply(world: &mut World, dt: f32) {
+        let simulation = &mut world.simulation;
+        if !simulation.active {
+            return;
+        }
+
+        let alpha = (simulation.accumulator / TICK_DURATION) as f32;
+        let mut position = simulation.previous.position;
+        position.lerp_towards(simulation.current.position, alpha);
+
+        simulation.error *= (1.0 - ERROR_CORRECTION * dt).max(0.0);
+
+        let physics = &mut world.game.self_entity.physics;
+        physics.position.value = position + simulation.error;
+        physics.velocity.value = simulation.current.velocity;
+    }
+
+    /// Rewinds to the authoritative state and replays every unacknowledged input.
+    pub fn reconcile(&mut self, authoritative: MovementState, acknowledged: u32, arena_size: f32) {
+        if !self.active {
+            return;
+        }
+
+        while let Some(input) = self.history.front() && input.sequence <= acknowledged {
+            self.history.pop_front();
+        }
+
+        let mut state = authoritative;
+        for input in self.history.iter() {
+            state.tick_tank(input.inputs, input.speed, input.impulse, arena_size);
+        }
+
+        let shift = state.position - self.current.position;
+        self.previous.position += shift;
+        self.current = state;
+
+        self.error -= shift;
+        if self.error.magnitude() > SNAP_DISTANCE {
+            self.error = Vector2D::ZERO;
+        }
+    }
+
+    /// Discards the prediction, e.g. upon death.
+    pub fn reset(&mut self) {
+        *self = Simulation { sequence: self.sequence, ..Default::default() };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::connection::packets::Inputs;
+    use super::*;
+
+    const ARENA_SIZE: f32 = 1000.0;
+
+    fn predicted(inputs: &[Inputs]) -> (Simulation, Vec<MovementState>) {
+        let mut simulation = Simulation::default();
+        simulation.start(MovementState::new(Vector2D::new(500.0, 500.0), Vector2D::ZERO, Vector2D::ZERO), 0.0);
+
+        let mut states = vec![simulation.current];
+        for &input in inputs {
+            simulation.predict(InputFlags::new(input as u32), 2.0, Vector2D::ZERO, ARENA_SIZE);
+            states.push(simulation.current);
+        }
+
+        (simulation, states)
+    }
+
+    #[test]
+    fn agreeing_server_changes_nothing() {
+        let (mut simulation, states) = predicted(&[Inputs::Up, Inputs::Up, Inputs::Right, Inputs::Right]);
+        let current = simulation.current;
+
+        simulation.reconcile(states[2], 2, ARENA_SIZE);
+        assert_eq!(simulation.current, current);
+        assert_eq!(simulation.error, Vector2D::ZERO);
+        assert_eq!(simulation.history.iter().map(|input| input.sequence).collect::<Vec<_>>(), vec![3, 4]);
+    }
+
+    #[test]
+    fn disagreeing_server_replays_inputs() {
+        let (mut simulation, states) = predicted(&[Inputs::Up, Inputs::Up, Inputs::Right, Inputs::Right]);
+        let current = simulation.current;
+
+        let mut authoritative = states[2];
+        authoritative.position.x -= 10.0;
+        authoritative.additional_velocity = Vector2D::new(1.0, 0.0);
+        simulation.reconcile(authoritative, 2, ARENA_SIZE);
+
+        assert!((simulation.current.position.x - (current.position.x - 10.0 + 2.0)).abs() < 1e-4);
+        assert_eq!(simulation.current.position.y, current.position.y);
+        assert_eq!(simulation.error, current.position - simulation.current.position);
+    }
+
+    #[test]
+    fn large_errors_snap() {
+        let (mut simulation, states) = predicted(&[Inputs::Left, Inputs::Left]);
+
+        let mut authoritative = states[1];
+        authoritative.position.x += SNAP_DISTANCE * 2.0;
+        simulation.reconcile(authoritative, 1, ARENA_SIZE);
+
+        assert_eq!(simulation.error, Vector2D::ZERO);
+        assert!(simulation.current.position.x > states[2].position.x);
+    }
+
+    #[test]
+    fn dashes_are_predicted_once_and_replayed() {
+        use shared::game::body::{BodyIdentity, BodyIdentityIds};
+        use crate::game::entity::base::AbilityDisplay;
+
+        let mut entity = Entity::default();
+        entity.display.body_identity = BodyIdentity::try_from(BodyIdentityIds::Smasher).unwrap();
+        entity.display.ability = Some(AbilityDisplay { kind: AbilityKind::Dash, charges: 1, max_charges: 2, cooldown: 0.0, active: false });
+        entity.stats.energy.target = 100.0;
+        entity.physics.inputs = InputFlags::new(Inputs::Ability as u32 | Inputs::Right as u32);
+
+        let (mut simulation, states) = predicted(&[Inputs::Right]);
+        let impulse = simulation.predict_dash(&mut entity);
+        let magnitude = entity.display.body_identity.ability.unwrap().magnitude;
+        assert_eq!(impulse, Vector2D::new(magnitude, 0.0));
+        assert_eq!(entity.display.ability.as_ref().unwrap().charges, 0);
+        assert_eq!(simulation.predict_dash(&mut entity), Vector2D::ZERO);
+
+        simulation.predict(entity.physics.inputs, 2.0, impulse, ARENA_SIZE);
+        let current = simulation.current;
+
+        simulation.reconcile(states[1], 1, ARENA_SIZE);
+        assert_eq!(simulation.current, current);
+        assert!(simulation.current.velocity.x > magnitude);
+    }
+}
+
@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+use shared::{lerp_angle, utils::{consts::TICK_RATE, vec2::Vector2D}};
+
+/// The duration of one server tick, in milliseconds.
+const TICK_DURATION: f64 = 1000.0 / TICK_RATE as f64;
+/// How far in the past remote entities are rendered, in milliseconds.
+/// Roughly two and a half ticks, so one lost packet still leaves a pair to interpolate between.
+pub const INTERPOLATION_DELAY: f64 = TICK_DURATION * 2.5;
+/// The longest an entity is extrapolated past its newest snapshot, in milliseconds.
+const MAX_EXTRAPOLATION: f64 = 250.0;
+/// The maximum number of snapshots buffered per entity.
+const MAX_SNAPSHOTS: usize = 32;
+/// How quickly the clock offset drifts back down after a late packet.
+const OFFSET_DECAY: f64 = 0.01;
+
+/// Tracks the offset between the server's clock and the local one.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ServerClock {
+    /// The tick of the newest update received.
+    pub tick: u64,
+    /// The timestamp of the newest update received.
+    pub timestamp: f64,
+    /// `server time - local time`, biased towards the least delayed packet.
+    offset: Option<f64>
+}
+
+impl ServerClock {
+    /// Registers an update sent by the server at `timestamp` and received locally at `now`.
+    pub fn update(&mut self, tick: u64, timestamp: f64, now: f64) {
+        self.tick = tick;
+        self.timestamp = timestamp;
+
+        let sample = timestamp - now;
+        self.offset = Some(match self.offset {
+            Some(offset) if sample <= offset => offset + (sample - offset) * OFFSET_DECAY,
+            _ => sample
+        });
+    }
+
+    /// The server time which should be rendered at local time `now`.
+    pub fn render_time(&self, now: f64) -> f64 {
+        now + self.offset.unwrap_or_default() - INTERPOLATION_DELAY
+    }
+}
+
+/// The physical state of an entity at some server tick.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub timestamp: f64,
+    pub position: Vector2D,
+    pub velocity: Vector2D,
+    pub angle: f32
+}
+
+/// The result of sampling a snapshot buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct SampledState {
+    pub position: Vector2D,
+    pub velocity: Vector2D,
+    pub angle: f32
+}
+
+/// A short history of snapshots for one entity.
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotBuffer {
+    snapshots: VecDeque<Snapshot>,
+    /// Whether the entity left view, so the next snapshot starts a fresh history.
+    stale: bool
+}
+
+impl SnapshotBuffer {
+    /// Inserts a snapshot, ignoring duplicates and out of order ticks.
+    pub fn push(&mut self, snapshot: Snapshot) {
+        if self.stale {
+            self.stale = false;
+            self.snapshots.clear();
+        }
+
+        if let Some(newest) = self.snapshots.back() && newest.tick >= snapshot.tick {
+            return;
+        }
+
+        self.snapshots.push_back(snapshot);
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Marks the entity as having left view.
+    /// The existing snapshots can still be sampled until it re-enters.
+    pub fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Samples the entity's state at server time `time`.
+    /// Interpolates between the two surrounding snapshots, or extrapolates
+    /// along the newest velocity for a bounded amount of time if none is newer.
+    pub fn sample(&mut self, time: f64) -> Option<SampledState> {
+        while self.snapshots.len() > 2 && self.snapshots[1].timestamp <= time {
+            self.snapshots.pop_front();
+        }
+
+        let first = *self.snapshots.front()?;
+        if time <= first.timestamp {
+            return Some(SampledState { position: first.position, velocity: first.velocity, angle: first.angle });
+        }
+
+        if let Some(&second) = self.snapshots.get(1) && time <= second.timestamp {
+            let span = (second.timestamp - first.timestamp).max(f64::EPSILON);
+            let alpha = ((time - first.timestamp) / span) as f32;
+
+            let mut position = first.position;
+            position.lerp_towards(second.position, alpha);
+
+            let mut velocity = first.velocity;
+            velocity.lerp_towards(second.velocity, alpha);
+
+            return Some(SampledState {
+                position,
+                velocity,
+                angle: lerp_angle!(first.angle, second.angle, alpha)
+            });
+        }
+
+        let newest = *self.snapshots.back()?;
+        let elapsed = (time - newest.timestamp).clamp(0.0, MAX_EXTRAPOLATION);
+
+        Some(SampledState {
+            position: newest.position + newest.velocity * (elapsed / TICK_DURATION) as f32,
+            velocity: newest.velocity,
+            angle: newest.angle
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(tick: u64, x: f32) -> Snapshot {
+        Snapshot {
+            tick,
+            timestamp: tick as f64 * TICK_DURATION,
+            position: Vector2D::new(x, 0.0),
+            velocity: Vector2D::new(1.0, 0.0),
+            angle: 0.0
+        }
+    }
+
+    fn sample_x(buffer: &mut SnapshotBuffer, tick: f64) -> f32 {
+        buffer.sample(tick * TICK_DURATION).unwrap().position.x
+    }
+
+    #[test]
+    fn out_of_order_snapshots_are_ignored() {
+        let mut buffer = SnapshotBuffer::default();
+        assert!(buffer.sample(0.0).is_none());
+
+        buffer.push(snapshot(1, 10.0));
+        buffer.push(snapshot(1, 99.0));
+        buffer.push(snapshot(0, 99.0));
+        buffer.push(snapshot(2, 20.0));
+        assert_eq!(buffer.snapshots.len(), 2);
+
+        for tick in 3..MAX_SNAPSHOTS as u64 + 10 {
+            buffer.push(snapshot(tick, 0.0));
+        }
+
+        assert_eq!(buffer.snapshots.len(), MAX_SNAPSHOTS);
+    }
+
+    #[test]
+    fn sampling_interpolates_between_snapshots() {
+        let mut buffer = SnapshotBuffer::default();
+        buffer.push(snapshot(1, 10.0));
+        buffer.push(snapshot(2, 20.0));
+        buffer.push(snapshot(3, 40.0));
+
+        assert_eq!(sample_x(&mut buffer, 0.0), 10.0);
+        assert!((sample_x(&mut buffer, 1.5) - 15.0).abs() < 1e-3);
+        assert!((sample_x(&mut buffer, 2.25) - 25.0).abs() < 1e-3);
+        assert_eq!(buffer.snapshots.len(), 2);
+    }
+
+    #[test]
+    fn extrapolation_is_bounded() {
+        let mut buffer = SnapshotBuffer::default();
+        buffer.push(snapshot(1, 10.0));
+        buffer.push(snapshot(2, 20.0));
+
+        assert!((sample_x(&mut buffer, 3.0) - 21.0).abs() < 1e-3);
+
+        let limit = 20.0 + (MAX_EXTRAPOLATION / TICK_DURATION) as f32;
+        assert!((sample_x(&mut buffer, 1000.0) - limit).abs() < 1e-3);
+    }
+
+    #[test]
+    fn reentering_view_discards_history() {
+        let mut buffer = SnapshotBuffer::default();
+        buffer.push(snapshot(1, 10.0));
+        buffer.push(snapshot(2, 20.0));
+
+        buffer.mark_stale();
+        assert!((sample_x(&mut buffer, 1.5) - 15.0).abs() < 1e-3);
+
+        buffer.push(snapshot(40, 500.0));
+        assert_eq!(buffer.snapshots.len(), 1);
+        assert_eq!(sample_x(&mut buffer, 39.5), 500.0);
+    }
+}
+
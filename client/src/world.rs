@@ -5,7 +5,7 @@ use shared::utils::interpolatable::Interpolatable;
 use ui::utils::sound::{Sound, SoundHolder};
 use web_sys::{wasm_bindgen::{prelude::Closure, JsCast, JsValue}, BeforeUnloadEvent, Event, KeyboardEvent, MouseEvent, WheelEvent};
 
-use crate::{connection::socket::Connection, game::entity::base::Game, register_event, rendering::{events::{self, on_resize, EventType}, renderer::Renderer}, storage_get};
+use crate::{connection::socket::Connection, game::entity::base::Game, register_event, rendering::{events::{self, on_resize, EventType}, renderer::Renderer}, simulation::Simulation, storage_get};
 
 pub static WORLD: Mutex<Option<Box<SendWrapper<World>>>> = Mutex::new(None);
 
@@ -24,7 +24,8 @@ pub struct World {
     pub renderer: Renderer,
     pub sounds: SoundHolder,
     pub connection: Connection,
-    pub game: Game
+    pub game: Game,
+    pub simulation: Simulation
 }
 
 impl World {
@@ -42,7 +43,8 @@ impl World {
             renderer: Renderer::new(),
             sounds,
             connection: Connection::new(),
-            game: Game::default()
+            game: Game::default(),
+            simulation: Simulation::default()
         }
     }
 
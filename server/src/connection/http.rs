@@ -0,0 +1,13 @@
+use axum::{extract::State, http::{header, StatusCode}, response::IntoResponse};
+
+use crate::server::WrappedServer;
+
+/// Serves the daily, weekly and all-time high-score tables as JSON.
+pub async fn serve_high_scores(State(server): State<WrappedServer>) -> impl IntoResponse {
+    let full_server = server.lock().await;
+
+    match full_server.game_server.runs.get_tables().to_json() {
+        Ok(json) => (StatusCode::OK, [(header::CONTENT_TYPE, "application/json")], json),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, [(header::CONTENT_TYPE, "text/plain")], error.to_string())
+    }
+}
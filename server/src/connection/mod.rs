@@ -1,2 +1,3 @@
 pub(crate) mod wss;
-pub(crate) mod packets;
\ No newline at end of file
+pub(crate) mod packets;
+pub(crate) mod http;
\ No newline at end of file
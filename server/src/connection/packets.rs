@@ -1,5 +1,6 @@
-use shared::{connection::packets::{ClanPacketOpcode, ClientboundPackets}, game::{body::{BodyIdentity, BodyIdentityIds}, entity::{get_min_score_from_level, ClanInformation, InputFlags, Notification, MAX_STAT_INVESTMENT}, turret::{TurretIdentityIds, TurretStructure}}, utils::{codec::BinaryCodec, color::Color, consts::{CLAN_DESC_LENGTH, CLAN_MAX_MEMBERS, CLAN_NAME_LENGTH, SCREEN_HEIGHT, SCREEN_WIDTH}, vec2::Vector2D}};
-use crate::{game::{clans::ClanState, entity::base::{AliveState, Entity}, state::{EntityDataStructure, GameState}}, server::{ServerGuard, LEADER_ARROW_VIEW}};
+use shared::{connection::packets::{ClanPacketOpcode, ClientboundPackets, InventoryPacketOpcode, SpectatePacketOpcode}, game::{body::{BodyIdentity, BodyIdentityIds}, entity::{get_min_score_from_level, ClanInformation, ClanStanding, EntityType, GameplayEvent, InputFlags, Marker, Notification, SpectateMode, MAX_STAT_INVESTMENT}, gear::{Gear, GearSlot, INVENTORY_CAPACITY}, turret::{TurretIdentityIds, TurretStructure}, world::WorldInfo, profile::Profile, highscores::{HighScorePeriod, HighScoreTables}}, utils::{codec::BinaryCodec, color::Color, consts::{CLAN_DESC_LENGTH, CLAN_MAX_MEMBERS, CLAN_NAME_LENGTH, SCREEN_HEIGHT, SCREEN_WIDTH}, vec2::Vector2D}};
+use crate::{game::{clans::ClanState, trades::TradeState, entity::{base::{AliveState, Entity}, spectator::cycle_players}, state::{EntityDataStructure, GameState}}, server::{ServerGuard, DISTRESS_COOLDOWN}};
+use strum::IntoEnumIterator;
 
 pub fn handle_spawn_packet(
     full_server: &mut ServerGuard, 
@@ -13,16 +14,62 @@ pub fn handle_spawn_packet(
     let random_position = game_server.get_random_position();
     if let Some(mut entity) = game_server.get_entity(id) && entity.stats.alive != AliveState::Alive {
         let old_level = entity.display.level;
+        let inventory = std::mem::take(&mut entity.stats.inventory);
+        let quests = std::mem::take(&mut entity.display.quests);
+        let profile = entity.connection.profile;
         *entity = Entity::from_id(entity.id);
 
+        entity.stats.inventory = inventory;
+        entity.display.quests = quests;
+        entity.connection.profile = profile;
+        entity.stats.inventory_changed = true;
+
         entity.physics.position = random_position;
         entity.display.name = name;
         entity.stats.alive = AliveState::Alive;
     
         entity.stats.health = entity.display.body_identity.max_health;
         entity.stats.max_health = entity.display.body_identity.max_health;
+        entity.stats.energy = entity.display.body_identity.energy.max_energy;
+        entity.stats.max_energy = entity.display.body_identity.energy.max_energy;
 
         entity.display.score = get_min_score_from_level((old_level / 2).max(1));
+
+        let token = rand::random::<u64>().max(1);
+        entity.connection.resume_token = Some(token);
+        entity.connection.outgoing_packets.push(form_session_packet(Some(token)));
+    }
+
+    Ok(())
+}
+
+/// Links the client to the profile it remembers, or to a new one if it has none.
+pub fn handle_profile_packet(
+    full_server: &mut ServerGuard,
+    id: u32,
+    mut codec: BinaryCodec
+) -> Result<(), bool> {
+    let token = codec.decode_varuint().ok_or(true)?;
+    let token = full_server.game_server.profiles.get_token(token);
+    let packet = form_profile_packet(token, &full_server.game_server.profiles.get(token).cloned().unwrap_or_default());
+
+    if let Some(mut entity) = full_server.game_server.get_server().get_entity(id) {
+        entity.connection.profile = Some(token);
+        entity.connection.outgoing_packets.push(packet);
+    }
+
+    Ok(())
+}
+
+pub fn handle_high_scores_packet(
+    full_server: &mut ServerGuard,
+    id: u32,
+    _: BinaryCodec
+) -> Result<(), bool> {
+    let packet = form_high_scores_packet(&full_server.game_server.runs.get_tables());
+
+    if let Some(mut entity) = full_server.game_server.get_server().get_entity(id) {
+        entity.connection.outgoing_packets.push(packet);
     }
 
     Ok(())
@@ -40,9 +87,10 @@ pub fn handle_input_packet(
         codec.decode_f32().ok_or(true)?, 
         codec.decode_f32().ok_or(true)?
     );
+    let sequence = codec.decode_varuint().ok_or(true)? as u32;
 
     if let Some(mut entity) = game_server.get_entity(id) && entity.stats.alive == AliveState::Alive {
-        entity.physics.inputs = InputFlags::new(flags);
+        entity.queue_input(sequence, InputFlags::new(flags));
 
         let (screen_width, screen_height) = ((SCREEN_WIDTH / entity.display.fov) / 0.9, (SCREEN_HEIGHT / entity.display.fov) / 0.9);
         let screen_top_left = entity.physics.position - Vector2D::new(screen_width / 2.0, screen_height / 2.0);
@@ -118,11 +166,12 @@ pub fn handle_upgrade_packet(
                 entity.display.body_identity = upgrade;
                 entity.physics.absorption_factor = entity.display.body_identity.absorption_factor;
                 entity.display.upgrades.body.clear();
+                entity.refresh_identities();
             }
         } else if upgrade_type == 1 {
             if let Some(upgrade) = entity.display.upgrades.turret.get(upgrade_idx).cloned() {
                 let upgrade: TurretStructure = upgrade.try_into().unwrap();
-                entity.display.turret_identity = upgrade;
+                entity.display.turret_identity = upgrade.with_body(&entity.display.body_identity);
                 entity.display.upgrades.turret.clear();
             }
         }
@@ -192,37 +241,277 @@ pub fn handle_clan_packet(
                 (name, description, max_members)
             };
 
+            if game_server.clan_state.get_membership(entity_id).is_some() {
+                return Ok(());
+            }
+
             let id = game_server.clan_state.create_clan(name, description, max_members, entity_id);
             game_server.get_entity(entity_id).unwrap().display.clan_id = Some(id);
-       },
-       ClanPacketOpcode::Join => {
-            let id = codec.decode_varuint().ok_or(true)? as usize;
-            if game_server.get_entity(entity_id).unwrap().display.pending_clan_id.is_some() {
+        },
+        ClanPacketOpcode::Join => {
+            let id = codec.decode_varuint().ok_or(true)? as u32;
+            if game_server.clan_state.request(id, entity_id) {
+                game_server.get_entity(entity_id).unwrap().display.pending_clan_id = Some(id);
+            }
+        },
+        ClanPacketOpcode::Leave => {
+            game_server.clan_state.leave(entity_id, &game_server.entities);
+        },
+        ClanPacketOpcode::Kick | ClanPacketOpcode::Accept | ClanPacketOpcode::Reject | ClanPacketOpcode::Transfer => {
+            let member = codec.decode_varuint().ok_or(true)? as u32;
+            let (clans, entities) = (&mut game_server.clan_state, &game_server.entities);
+
+            match packet_type {
+                ClanPacketOpcode::Kick => clans.kick(entity_id, member, entities),
+                ClanPacketOpcode::Accept => clans.accept(entity_id, member, entities),
+                ClanPacketOpcode::Reject => clans.reject(entity_id, member, entities),
+                _ => clans.transfer(entity_id, member, entities)
+            };
+        },
+        ClanPacketOpcode::Distress => {
+            let Some(clan) = game_server.clan_state.get_membership(entity_id).filter(|clan| clan.members.contains(&entity_id)) else {
                 return Ok(());
+            };
+
+            let position = {
+                let mut entity = game_server.get_entity(entity_id).unwrap();
+                if entity.stats.alive != AliveState::Alive
+                    || entity.time.last_distress_tick.is_some_and(|tick| entity.time.ticks - tick < DISTRESS_COOLDOWN)
+                {
+                    return Ok(());
+                }
+
+                entity.time.last_distress_tick = Some(entity.time.ticks);
+                entity.physics.position
+            };
+
+            let clan_id = clan.id;
+            let name = game_server.get_entity(entity_id).unwrap().display.name.clone();
+            for &member in clan.members.iter().filter(|&&member| member != entity_id) {
+                if let Some(mut entity) = game_server.get_entity(member) {
+                    entity.display.notifications.push(Notification {
+                        message: format!("{} is calling for help!", if name.is_empty() { "A clanmate" } else { name.as_str() }),
+                        color: Color::RED,
+                        lifetime: 150,
+                        ..Default::default()
+                    });
+                }
             }
 
-            let clan = game_server.clan_state.clans.get_mut(id).ok_or(false)?;
+            game_server.clan_state.call_for_help(clan_id, entity_id, position);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn handle_inventory_packet(
+    full_server: &mut ServerGuard,
+    id: u32,
+    mut codec: BinaryCodec
+) -> Result<(), bool> {
+    let game_server = full_server.game_server.get_server();
+    let (name, clan_id) = {
+        let Some(entity) = game_server.get_entity(id) else { return Ok(()); };
+        if entity.stats.alive != AliveState::Alive { return Ok(()); }
+
+        (entity.display.name.clone(), entity.display.clan_id)
+    };
 
-            if clan.members.len() < clan.max_members {
-                clan.pending_members.push(entity_id);
-                game_server.get_entity(entity_id).unwrap().display.pending_clan_id = Some(id as u32);
+    let packet_type: InventoryPacketOpcode = codec.decode_varuint().ok_or(true)?.try_into().map_err(|_| true)?;
+
+    match packet_type {
+        InventoryPacketOpcode::Equip | InventoryPacketOpcode::Unequip | InventoryPacketOpcode::Discard => {
+            let index = codec.decode_varuint().ok_or(true)? as usize;
+            let changed = {
+                let mut entity = game_server.get_entity(id).unwrap();
+                let inventory = &mut entity.stats.inventory;
+
+                match packet_type {
+                    InventoryPacketOpcode::Equip => inventory.equip(index),
+                    InventoryPacketOpcode::Unequip => {
+                        let slot = GearSlot::try_from(u8::try_from(index).map_err(|_| true)?).map_err(|_| true)?;
+                        inventory.unequip(slot)
+                    },
+                    _ => inventory.discard(index).is_some()
+                }
+            };
+
+            if !changed {
+                return Ok(());
+            }
+
+            // Items are offered by index, so moving them around withdraws the offer.
+            let partner = game_server.trade_state.offer(id, vec![]);
+            update_traders(game_server, &[Some(id), partner], None);
+        },
+        InventoryPacketOpcode::TradeRequest => {
+            let target = codec.decode_varuint().ok_or(true)? as u32;
+            if target == id || clan_id.is_none() {
+                return Ok(());
+            }
+
+            let target_name = {
+                let Some(entity) = game_server.get_entity(target) else { return Ok(()); };
+                if entity.display.entity_type != EntityType::Player 
+                    || entity.stats.alive != AliveState::Alive 
+                    || entity.display.clan_id != clan_id 
+                {
+                    return Ok(());
+                }
+
+                entity.display.name.clone()
+            };
+
+            if game_server.trade_state.request(id, target) {
+                update_traders(game_server, &[Some(id)], Some((format!("You are now trading with {}.", target_name), Color::BLACK)));
+                update_traders(game_server, &[Some(target)], Some((format!("You are now trading with {}.", name), Color::BLACK)));
+            } else {
+                update_traders(game_server, &[Some(id)], None);
+                update_traders(game_server, &[Some(target)], Some((format!("{} wants to trade with you.", name), Color::BLACK)));
+            }
+        },
+        InventoryPacketOpcode::TradeOffer => {
+            let count = codec.decode_varuint().ok_or(true)? as usize;
+            if count > INVENTORY_CAPACITY {
+                return Err(true);
+            }
+
+            let mut indices = Vec::with_capacity(count);
+            for _ in 0..count {
+                indices.push(codec.decode_varuint().ok_or(true)? as usize);
+            }
+
+            let partner = game_server.trade_state.offer(id, indices);
+            update_traders(game_server, &[Some(id), partner], None);
+        },
+        InventoryPacketOpcode::TradeConfirm => {
+            if let Some(trade) = game_server.trade_state.confirm(id) {
+                let message = if TradeState::execute(&trade, &game_server.entities) {
+                    ("The trade was completed.".to_string(), Color::BLACK)
+                } else {
+                    ("The trade failed, as an item was missing or an inventory was full.".to_string(), Color::RED)
+                };
+
+                update_traders(game_server, &trade.parties.map(Some), Some(message));
+            } else {
+                let partner = game_server.trade_state.get_trade(id).map(|trade| trade.parties[1 - trade.get_side(id)]);
+                update_traders(game_server, &[Some(id), partner], None);
             }
-       },
-        _ => return Ok(())
+        },
+        InventoryPacketOpcode::TradeCancel => {
+            let partner = game_server.trade_state.get_trade(id).map(|trade| trade.parties[1 - trade.get_side(id)]);
+            let affected: Vec<_> = game_server.trade_state.cancel(id).into_iter().map(Some).collect();
+
+            update_traders(game_server, &affected, None);
+            update_traders(game_server, &[Some(id)], None);
+            update_traders(game_server, &[partner], Some(("The trade was cancelled.".to_string(), Color::RED)));
+        }
     }
 
     Ok(())
 }
 
+pub fn handle_spectate_packet(
+    full_server: &mut ServerGuard,
+    id: u32,
+    mut codec: BinaryCodec
+) -> Result<(), bool> {
+    let game_server = full_server.game_server.get_server();
+    let opcode: SpectatePacketOpcode = codec.decode_varuint().ok_or(true)?.try_into().map_err(|_| true)?;
+
+    let Some(mut entity) = game_server.get_entity(id) else { return Ok(()); };
+
+    if opcode == SpectatePacketOpcode::Start {
+        if entity.stats.alive == AliveState::Uninitialized {
+            entity.stats.alive = AliveState::Spectating;
+            entity.physics.collidable = false;
+            entity.physics.position = Vector2D::new(game_server.world.arena_size / 2.0, game_server.world.arena_size / 2.0);
+            entity.display.spectate_mode = SpectateMode::Leader;
+            entity.display.spectated = None;
+        }
+
+        return Ok(());
+    }
+
+    if entity.stats.alive != AliveState::Spectating {
+        return Ok(());
+    }
+
+    match opcode {
+        SpectatePacketOpcode::Leader => entity.display.spectate_mode = SpectateMode::Leader,
+        SpectatePacketOpcode::Next | SpectatePacketOpcode::Previous => {
+            entity.display.spectated = cycle_players(&game_server.entities, entity.display.spectated, opcode == SpectatePacketOpcode::Next);
+            entity.display.spectate_mode = SpectateMode::Player;
+        },
+        SpectatePacketOpcode::Free => entity.display.spectate_mode = SpectateMode::Free,
+        SpectatePacketOpcode::Pan => entity.physics.inputs = InputFlags::new(codec.decode_varuint().ok_or(true)? as u32),
+        SpectatePacketOpcode::Stop => {
+            entity.stats.alive = AliveState::Uninitialized;
+            entity.physics.inputs = InputFlags::new(0);
+        },
+        SpectatePacketOpcode::Start => ()
+    }
+
+    Ok(())
+}
+
+/// Resends the inventories of everyone in `ids`, notifying them if there is a message.
+fn update_traders(game_server: &GameState, ids: &[Option<u32>], message: Option<(String, Color)>) {
+    for &id in ids.iter().flatten() {
+        let Some(mut entity) = game_server.get_entity(id) else { continue; };
+        entity.stats.inventory_changed = true;
+
+        if let Some((message, color)) = message.clone() {
+            entity.display.notifications.push(Notification {
+                message,
+                color,
+                lifetime: 150,
+                ..Default::default()
+            });
+        }
+    }
+}
+
 pub fn form_update_packet(
     self_entity: &mut Entity, 
     entities: &EntityDataStructure,
-    clans: &ClanState
+    clans: &ClanState,
+    tick: u64,
+    timestamp: f64
 ) -> BinaryCodec {
     let mut codec = BinaryCodec::new();
     codec.encode_varuint(ClientboundPackets::Update as u64);
 
+    // CLOCK //
+    codec.encode_varuint(tick);
+    codec.encode_f64(timestamp);
+
     // CLANS //
+    let (self_id, self_name) = (self_entity.id, self_entity.display.name.clone());
+    let get_name = |id: u32| if id == self_id {
+        self_name.clone()
+    } else {
+        entities.get(&id).and_then(|entity| entity.try_borrow().ok()).map(|entity| entity.display.name.clone()).unwrap_or_default()
+    };
+
+    let sent_names = &mut self_entity.connection.clan_names;
+    sent_names.retain(|id, _| clans.clans.iter().any(|clan| clan.members.contains(id) || clan.pending_members.contains(id)));
+
+    // Names are only sent when they first appear or change.
+    let mut encode_member = |codec: &mut BinaryCodec, member: u32| {
+        codec.encode_varuint(member as u64);
+
+        let name = get_name(member);
+        let changed = sent_names.get(&member) != Some(&name);
+        codec.encode_bool(changed);
+
+        if changed {
+            codec.encode_string(name.clone());
+            sent_names.insert(member, name);
+        }
+    };
+
     codec.encode_varuint(clans.clans.len() as u64);
     for clan in clans.clans.iter() {
         codec.encode_varuint(clan.id as u64);
@@ -234,13 +523,18 @@ pub fn form_update_packet(
         codec.encode_varuint(clan.members.len() as u64);
         codec.encode_varuint(clan.pending_members.len() as u64);
 
-        for &member in clan.members.iter() {
-            codec.encode_varuint(member as u64);
+        for &member in clan.members.iter().chain(clan.pending_members.iter()) {
+            encode_member(&mut codec, member);
         }
+    }
 
-        for &member in clan.pending_members.iter() {
-            codec.encode_varuint(member as u64);
-        }
+    // SPECTATING //
+    let is_spectating = self_entity.stats.alive == AliveState::Spectating;
+    codec.encode_bool(is_spectating);
+
+    if is_spectating {
+        codec.encode_varuint(self_entity.display.spectate_mode as u64);
+        codec.encode_varuint(self_entity.display.spectated.unwrap_or(0) as u64);
     }
 
     // ENTITIES //
@@ -250,7 +544,7 @@ pub fn form_update_packet(
         if id == self_entity.id { return false; }
 
         if let Some(entity) = entities.get(&id) {
-            entity.borrow_mut().stats.alive != AliveState::Uninitialized
+            !matches!(entity.borrow_mut().stats.alive, AliveState::Uninitialized | AliveState::Spectating)
         } else {
             false
         }
@@ -283,6 +577,252 @@ pub fn form_notification_packet(
     codec
 }
 
+pub fn form_inventory_packet(
+    self_entity: &Entity,
+    entities: &EntityDataStructure,
+    trades: &TradeState
+) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::Inventory as u64);
+
+    let encode_gear = |codec: &mut BinaryCodec, gear: &Gear| {
+        codec.encode_varuint(gear.kind as u64);
+        codec.encode_varuint(gear.rarity as u64);
+    };
+
+    let get_name = |id: u32| entities.get(&id).map(|entity| entity.borrow().display.name.clone()).unwrap_or_default();
+
+    // ITEMS //
+    let inventory = &self_entity.stats.inventory;
+    codec.encode_varuint(inventory.items.len() as u64);
+    for gear in inventory.items.iter() {
+        encode_gear(&mut codec, gear);
+    }
+
+    for gear in inventory.equipped.iter() {
+        codec.encode_bool(gear.is_some());
+        if let Some(gear) = gear {
+            encode_gear(&mut codec, gear);
+        }
+    }
+
+    // REQUESTS //
+    let incoming: Vec<u32> = trades.requests.iter()
+        .filter(|&&(_, to)| to == self_entity.id)
+        .map(|&(from, _)| from)
+        .collect();
+
+    codec.encode_varuint(incoming.len() as u64);
+    for &from in incoming.iter() {
+        codec.encode_varuint(from as u64);
+        codec.encode_string(get_name(from));
+    }
+
+    codec.encode_varint(trades.requests.iter()
+        .find(|&&(from, _)| from == self_entity.id)
+        .map(|&(_, to)| to as i64)
+        .unwrap_or(-1));
+
+    // TRADE //
+    let trade = trades.get_trade(self_entity.id);
+    codec.encode_bool(trade.is_some());
+
+    if let Some(trade) = trade {
+        let side = trade.get_side(self_entity.id);
+        let partner = trade.parties[1 - side];
+
+        codec.encode_varuint(partner as u64);
+        codec.encode_string(get_name(partner));
+
+        codec.encode_varuint(trade.offers[side].len() as u64);
+        for &index in trade.offers[side].iter() {
+            codec.encode_varuint(index as u64);
+        }
+
+        let offered: Vec<Gear> = entities.get(&partner)
+            .map(|entity| {
+                let entity = entity.borrow();
+                trade.offers[1 - side].iter().filter_map(|&index| entity.stats.inventory.items.get(index).copied()).collect()
+            })
+            .unwrap_or_default();
+
+        codec.encode_varuint(offered.len() as u64);
+        for gear in offered.iter() {
+            encode_gear(&mut codec, gear);
+        }
+
+        codec.encode_bool(trade.confirmed[side]);
+        codec.encode_bool(trade.confirmed[1 - side]);
+    }
+
+    codec
+}
+
+pub fn form_events_packet(
+    events: &[GameplayEvent]
+) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::Events as u64);
+
+    codec.encode_varuint(events.len() as u64);
+    for event in events.iter() {
+        codec.encode_varuint(event.get_type() as u64);
+
+        match *event {
+            GameplayEvent::TurretFired { id, turret } => {
+                codec.encode_varuint(id as u64);
+                codec.encode_varuint(turret as u64);
+            },
+            GameplayEvent::Hit { attacker, victim, damage } => {
+                codec.encode_varint(attacker.map_or(-1, |attacker| attacker as i64));
+                codec.encode_varuint(victim as u64);
+                codec.encode_f32(damage);
+            },
+            GameplayEvent::LevelUp(level) => codec.encode_varuint(level as u64),
+            GameplayEvent::UpgradeAvailable => (),
+            GameplayEvent::ObjectiveCaptured { id } => codec.encode_varuint(id as u64)
+        }
+    }
+
+    codec
+}
+
+/// Forms the summary of a player's life, sent once it dies.
+/// Only the five sources which dealt the most damage are included.
+pub fn form_death_packet(
+    self_entity: &Entity,
+    entities: &EntityDataStructure
+) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::Death as u64);
+
+    // KILLER //
+    let killer = self_entity.display.killer.and_then(|killer| entities.get(&killer.into()));
+    codec.encode_bool(killer.is_some());
+
+    if let Some(killer) = killer {
+        let killer = killer.borrow();
+        let is_tank = killer.display.entity_type == EntityType::Player;
+
+        codec.encode_string(killer.get_source_name());
+        codec.encode_bool(is_tank);
+
+        if is_tank {
+            codec.encode_varuint(killer.display.body_identity.id as u64);
+            codec.encode_varuint(killer.display.turret_identity.id as u64);
+        }
+    }
+
+    // DAMAGE TAKEN //
+    let mut sources: Vec<(String, f32)> = vec![];
+    for (attacker, &damage) in self_entity.stats.damage_taken.iter() {
+        let name = entities.get(attacker)
+            .map(|entity| entity.borrow().get_source_name())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        match sources.iter_mut().find(|(source, _)| *source == name) {
+            Some((_, total)) => *total += damage,
+            None => sources.push((name, damage))
+        }
+    }
+
+    sources.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    sources.truncate(5);
+
+    codec.encode_varuint(sources.len() as u64);
+    for (name, damage) in sources {
+        codec.encode_string(name);
+        codec.encode_f32(damage);
+    }
+
+    // STATISTICS //
+    codec.encode_varuint(self_entity.display.kills as u64);
+    codec.encode_varuint(self_entity.display.level as u64);
+    codec.encode_varuint(self_entity.display.score as u64);
+    codec.encode_varuint(self_entity.time.ticks - self_entity.time.spawn_tick);
+
+    codec
+}
+
+pub fn form_world_info_packet(world: &WorldInfo) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::WorldInfo as u64);
+
+    codec.encode_f32(world.arena_size);
+    codec.encode_varuint(world.mode as u64);
+    codec.encode_varuint(world.tick_rate);
+    codec.encode_varuint(world.teams as u64);
+
+    codec.encode_varuint(world.walls.len() as u64);
+    for wall in world.walls.iter() {
+        codec.encode_f32(wall.position.x);
+        codec.encode_f32(wall.position.y);
+        codec.encode_f32(wall.width);
+        codec.encode_f32(wall.height);
+    }
+
+    codec
+}
+
+/// Forms a packet with the lifetime statistics of a profile.
+/// Only the ten tanks with the best scores are included.
+pub fn form_profile_packet(token: u64, profile: &Profile) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::Profile as u64);
+
+    codec.encode_varuint(token);
+    codec.encode_varuint(profile.kills as u64);
+    codec.encode_varuint(profile.playtime);
+    codec.encode_varuint(profile.runs as u64);
+
+    let best_scores = &profile.best_scores[..profile.best_scores.len().min(10)];
+    codec.encode_varuint(best_scores.len() as u64);
+    for record in best_scores.iter() {
+        codec.encode_varuint(record.body as u64);
+        codec.encode_varuint(record.turret as u64);
+        codec.encode_varuint(record.score as u64);
+    }
+
+    codec.encode_varuint(profile.achievements.len() as u64);
+    for achievement in profile.achievements.iter() {
+        codec.encode_string(achievement.clone());
+    }
+
+    codec
+}
+
+/// Forms a packet with the daily, weekly and all-time high-score tables, in that order.
+pub fn form_high_scores_packet(tables: &HighScoreTables) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::HighScores as u64);
+
+    for period in HighScorePeriod::iter() {
+        let table = tables.get(period);
+
+        codec.encode_varuint(table.len() as u64);
+        for recorded in table.iter() {
+            codec.encode_string(recorded.run.name.clone());
+            codec.encode_varuint(recorded.run.body as u64);
+            codec.encode_varuint(recorded.run.turret as u64);
+            codec.encode_varuint(recorded.run.score as u64);
+            codec.encode_varuint(recorded.run.kills as u64);
+            codec.encode_varuint(recorded.run.duration);
+            codec.encode_varuint(recorded.finished_at);
+        }
+    }
+
+    codec
+}
+
+/// Tells the client which token reclaims its tank, where `None` means it has no tank to reclaim.
+pub fn form_session_packet(token: Option<u64>) -> BinaryCodec {
+    let mut codec = BinaryCodec::new();
+    codec.encode_varuint(ClientboundPackets::Session as u64);
+    codec.encode_varuint(token.unwrap_or_default());
+
+    codec
+}
+
 pub fn form_pong_packet() -> BinaryCodec {
     let mut codec = BinaryCodec::new();
     codec.encode_varuint(ClientboundPackets::Pong as u64);
@@ -292,29 +832,55 @@ pub fn form_pong_packet() -> BinaryCodec {
 
 pub fn form_server_info_packet(
     state: &GameState, 
-    leaderboard: &[(usize, String, BodyIdentityIds, TurretIdentityIds, Vector2D)], 
-    reference_position: Vector2D, reference_fov: f32
+    leaderboard: &[(usize, String, BodyIdentityIds, TurretIdentityIds)], 
+    clan_standings: &[ClanStanding],
+    markers: &[Marker],
+    population: (usize, usize)
 ) -> BinaryCodec {
     let mut codec = BinaryCodec::new();
     codec.encode_varuint(ClientboundPackets::ServerInfo as u64);
     
     codec.encode_f32(state.mspt);
 
+    let (players, spectators) = population;
+    codec.encode_varuint(players as u64);
+    codec.encode_varuint(spectators as u64);
+
     codec.encode_varuint(leaderboard.len() as u64);
-    for (score, name, body_identity, turret_identity, _) in leaderboard.iter() {
+    for (score, name, body_identity, turret_identity) in leaderboard.iter() {
         codec.encode_varuint(*score as u64);
         codec.encode_string(name.clone());
         codec.encode_varuint(*body_identity as u64);
         codec.encode_varuint(*turret_identity as u64);
     }
 
-    codec.encode_f32(if let Some((_, _, _, _, position)) = leaderboard.first() 
-        && position.distance(reference_position) > LEADER_ARROW_VIEW * reference_fov
-    {
-        (reference_position - *position).angle()
-    } else {
-        -13.0
-    });
+    codec.encode_varuint(markers.len() as u64);
+    for marker in markers.iter() {
+        codec.encode_varuint(marker.kind as u64);
+        codec.encode_varuint(marker.id as u64);
+        codec.encode_f32(marker.position.x);
+        codec.encode_f32(marker.position.y);
+        codec.encode_varuint(marker.color.0 as u64);
+        codec.encode_varuint(marker.color.1 as u64);
+        codec.encode_varuint(marker.color.2 as u64);
+    }
+
+    codec.encode_varuint(clan_standings.len() as u64);
+    for standing in clan_standings.iter() {
+        codec.encode_string(standing.name.clone());
+        codec.encode_varuint(standing.members as u64);
+        codec.encode_varuint(standing.score as u64);
+        codec.encode_varuint(standing.kills as u64);
+        codec.encode_varuint(standing.war_kills as u64);
+    }
+
+    match &state.clan_state.war {
+        Some(war) => {
+            codec.encode_bool(true);
+            codec.encode_varuint(war.remaining);
+        },
+        None => codec.encode_bool(false)
+    }
 
     codec
 }
\ No newline at end of file
@@ -5,21 +5,25 @@ use axum::{
     response::IntoResponse
 };
 use futures::{stream::SplitSink, SinkExt, StreamExt};
-use shared::{connection::packets::ServerboundPackets, game::entity::EntityType, utils::codec::BinaryCodec};
+use shared::{connection::packets::ServerboundPackets, game::entity::{EntityType, InputFlags}, utils::codec::BinaryCodec};
 
-use crate::{game::entity::base::{AliveState, Entity}, server::{Server, ServerGuard, WrappedServer}};
+use crate::{game::entity::{base::{AliveState, Entity}, spectator::get_leaderboard}, server::{Server, ServerGuard, WrappedServer, PING_TIMEOUT}};
 
 use self::packets::form_server_info_packet;
 
 use super::packets;
 
 pub struct WebSocketClient {
-    pub sender: SplitSink<WebSocket, Message>
+    pub sender: SplitSink<WebSocket, Message>,
+    /// The ID the client connected with, which stays the same if it resumes another session.
+    pub connection: u32,
+    /// The tick at which the client last sent a message.
+    pub last_message: u64
 }
 
 pub struct WebSocketServer {
     clients: HashMap<u32, WebSocketClient>,
-    ticks: u32
+    ticks: u64
 }
 
 impl WebSocketServer {
@@ -41,32 +45,100 @@ impl WebSocketServer {
     }
 
     pub async fn accept_client(socket: WebSocket, server: WrappedServer) {
-        let (mut receiver, id) = {
+        let (mut receiver, mut id) = {
             let mut full_server = server.lock().await;
             let id = full_server.game_server.get_server().get_next_id();
     
             let (sender, receiver) = socket.split();
 
-            full_server.ws_server.clients.insert(id, WebSocketClient { sender });
-            full_server.game_server.get_server().insert_entity(Entity::from_id(id));
+            let ticks = full_server.ws_server.ticks;
+            full_server.ws_server.clients.insert(id, WebSocketClient { sender, connection: id, last_message: ticks });
+
+            let game_server = full_server.game_server.get_server();
+            let mut entity = Entity::from_id(id);
+            entity.connection.outgoing_packets.push(packets::form_world_info_packet(&game_server.world));
+            game_server.insert_entity(entity);
 
             (receiver, id)
         };
 
+        let connection = id;
         while let Some(Ok(message)) = receiver.next().await {
             let mut full_server = server.lock().await;
-            
+            let ticks = full_server.ws_server.ticks;
+
+            // The client was evicted, either by timing out or by another client resuming its session.
+            let Some(client) = full_server.ws_server.clients.get_mut(&id).filter(|client| client.connection == connection) else { return; };
+            client.last_message = ticks;
+
             if let Message::Binary(ref data) = message && let Some(&header) = data.first()
                 && header.try_into() == Ok(ServerboundPackets::Ping)
-                && let Some(client) = full_server.ws_server.clients.get_mut(&id)
             {
                 let _ = client.sender.send(Message::Binary(packets::form_pong_packet().out())).await;
             }
 
-            if let Err(ban) = WebSocketServer::handle_message(&mut full_server, message, id) {
+            let result = match message {
+                Message::Binary(ref data) if data.first().map(|&header| header.try_into()) == Some(Ok(ServerboundPackets::Resume)) =>
+                    WebSocketServer::resume_client(&mut full_server, id, BinaryCodec::from_bytes(data.clone())).map(|resumed| id = resumed),
+                message => WebSocketServer::handle_message(&mut full_server, message, id)
+            };
+
+            if let Err(ban) = result {
                 WebSocketServer::close_client(&mut full_server, id, ban);
+                return;
             }
         }
+
+        let mut full_server = server.lock().await;
+        if full_server.ws_server.clients.get(&id).is_some_and(|client| client.connection == connection) {
+            WebSocketServer::close_client(&mut full_server, id, false);
+        }
+    }
+
+    /// Hands the tank a player left behind over to the fresh client `id`. If the tank's old client
+    /// is still connected, as when its socket went stale without closing, that client is evicted.
+    /// Returns the ID the client is known by from now on.
+    pub fn resume_client(full_server: &mut ServerGuard, id: u32, mut codec: BinaryCodec) -> Result<u32, bool> {
+        codec.decode_varuint().ok_or(true)?;
+        let token = codec.decode_varuint().ok_or(true)?;
+
+        let game_server = full_server.game_server.get_server();
+        let Some(mut fresh) = game_server.get_entity(id) else { return Ok(id); };
+        if fresh.stats.alive != AliveState::Uninitialized {
+            return Ok(id);
+        }
+
+        let Some(resumed) = game_server.find_session(token) else {
+            fresh.connection.outgoing_packets.push(packets::form_session_packet(None));
+            return Ok(id);
+        };
+
+        drop(fresh);
+        game_server.remove_player(id);
+
+        if let Some(mut entity) = game_server.get_entity(resumed) {
+            entity.connection.disconnected_at = None;
+            entity.physics.inputs = InputFlags::new(0);
+            entity.physics.input_queue.clear();
+            entity.connection.clan_names.clear();
+            entity.connection.outgoing_packets = vec![
+                packets::form_session_packet(Some(token)),
+                packets::form_world_info_packet(&game_server.world)
+            ];
+            entity.stats.inventory_changed = true;
+        }
+
+        if let Some(mut stale) = full_server.ws_server.clients.remove(&resumed) {
+            println!("Client # {} was evicted from the session of # {}.", stale.connection, resumed);
+            tokio::spawn(async move { let _ = stale.sender.send(Message::Close(None)).await; });
+        }
+
+        if let Some(client) = full_server.ws_server.clients.remove(&id) {
+            full_server.ws_server.clients.insert(resumed, client);
+        }
+
+        println!("Client # {} resumed the session of # {}.", id, resumed);
+        Ok(resumed)
     }
 
     pub fn handle_message(full_server: &mut ServerGuard, message: Message, id: u32) -> Result<(), bool> {
@@ -84,7 +156,11 @@ impl WebSocketServer {
                     ServerboundPackets::Upgrade => packets::handle_upgrade_packet(full_server, id, codec),
                     ServerboundPackets::Chat => packets::handle_chat_packet(full_server, id, codec),
                     ServerboundPackets::Clan => packets::handle_clan_packet(full_server, id, codec),
-                    ServerboundPackets::Ping => Ok(())
+                    ServerboundPackets::Inventory => packets::handle_inventory_packet(full_server, id, codec),
+                    ServerboundPackets::Spectate => packets::handle_spectate_packet(full_server, id, codec),
+                    ServerboundPackets::Profile => packets::handle_profile_packet(full_server, id, codec),
+                    ServerboundPackets::HighScores => packets::handle_high_scores_packet(full_server, id, codec),
+                    ServerboundPackets::Ping | ServerboundPackets::Resume => Ok(())
                 }
             },
             Message::Close(_) => Err(false),
@@ -92,51 +168,84 @@ impl WebSocketServer {
         }
     }
 
-    /// Closes the client.
-    pub fn close_client(full_server: &mut ServerGuard, id: u32, ban: bool) {
-        println!("Client # {} is being {}.", id, if ban { "banned" } else { "closed forcefully" });
+    /// Closes the client. A living tank is kept for the grace period
+    /// so the player can reclaim it, unless the client was banned.
+    pub fn close_client(full_server: &mut Server, id: u32, ban: bool) {
+        if full_server.ws_server.clients.remove(&id).is_none() {
+            return;
+        }
+
+        println!("Client # {} is being {}.", id, if ban { "banned" } else { "closed" });
+
+        let game_server = full_server.game_server.get_server();
+        let ticks = game_server.ticks;
+        let Some(mut entity) = game_server.get_entity(id) else { return; };
 
-        full_server.game_server.get_server().delete_entity(id);
-        full_server.ws_server.clients.remove(&id);
+        if !ban && entity.stats.alive == AliveState::Alive && entity.connection.resume_token.is_some() {
+            entity.connection.disconnected_at = Some(ticks);
+            entity.physics.inputs = InputFlags::new(0);
+            entity.physics.input_queue.clear();
+        } else {
+            drop(entity);
+            game_server.remove_player(id);
+        }
     }
 
     pub async fn tick(full_server: &mut Server) {
         full_server.ws_server.ticks += 1;
 
-        let game_server = full_server.game_server.get_server();
-        let mut leaderboard: Vec<_> = game_server.entities
-            .values()
-            .filter(|e| e.borrow().display.entity_type == EntityType::Player && e.borrow().stats.alive == AliveState::Alive)
-            .collect::<Vec<_>>();
+        let ticks = full_server.ws_server.ticks;
+        let timed_out: Vec<u32> = full_server.ws_server.clients.iter()
+            .filter(|(_, client)| ticks - client.last_message >= PING_TIMEOUT)
+            .map(|(&id, _)| id)
+            .collect();
+
+        for id in timed_out {
+            if let Some(client) = full_server.ws_server.clients.get_mut(&id) {
+                let _ = client.sender.send(Message::Close(None)).await;
+            }
 
-        leaderboard.sort_by_key(|e| std::cmp::Reverse(e.borrow().display.score));
+            println!("Client # {} timed out.", id);
+            WebSocketServer::close_client(full_server, id, false);
+        }
 
+        let game_server = full_server.game_server.get_server();
+        let leaderboard = get_leaderboard(&game_server.entities);
+        let leader = leaderboard.first().copied();
         let leaderboard: Vec<_> = leaderboard
             .into_iter()
             .take(10)
-            .map(|e| {
-                let e = e.borrow();
+            .map(|id| {
+                let e = game_server.entities[&id].borrow();
                 (
                     e.display.score,
                     e.display.name.clone(),
                     e.display.body_identity.id,
-                    e.display.turret_identity.id,
-                    e.physics.position
+                    e.display.turret_identity.id
                 )
             })
             .collect();
 
+        let clan_standings: Vec<_> = game_server.clan_state.get_standings(&game_server.entities)
+            .into_iter()
+            .take(10)
+            .collect();
+
+        let (players, spectators): (Vec<_>, Vec<_>) = game_server.entities
+            .values()
+            .filter(|e| e.borrow().display.entity_type == EntityType::Player)
+            .partition(|e| e.borrow().stats.alive != AliveState::Spectating);
+        let population = (players.len(), spectators.len());
+
         for (id, ws_client) in full_server.ws_server.clients.iter_mut() {
             let mut outgoing_packets = {
-                let (reference_position, reference_fov) = {
-                    let Some(entity) = full_server.game_server.get_server().get_entity(*id) else { continue; };
-                    (entity.physics.position, entity.display.fov)
-                };
-
+                let markers = full_server.game_server.get_server().get_markers(*id, leader);
                 let server_info_packet = form_server_info_packet(
                     full_server.game_server.get_server(), 
                     &leaderboard,
-                    reference_position, reference_fov
+                    &clan_standings,
+                    &markers,
+                    population
                 );
 
                 let Some(mut entity) = full_server.game_server.get_server().get_entity(*id) else { continue; };
@@ -1,20 +1,74 @@
-use shared::game::entity::ClanInformation;
+use std::{cmp::Reverse, collections::HashMap};
+use shared::{game::{entity::{ClanInformation, ClanStanding, EntityType, Notification}, world::GameMode}, utils::{color::Color, vec2::Vector2D}};
+
+use crate::server::{CLAN_WAR_DURATION, DISTRESS_CALL_DURATION};
 
 use super::state::EntityDataStructure;
 
+/// A timed round, at the end of which the clan with the most kills wins.
+pub struct ClanWar {
+    /// How long each round lasts, in ticks.
+    pub duration: u64,
+    /// The number of ticks left in the current round.
+    pub remaining: u64,
+    /// The kills made by every clan during the current round.
+    pub kills: HashMap<u32, usize>
+}
+
+impl ClanWar {
+    pub fn new(duration: u64) -> ClanWar {
+        ClanWar { duration, remaining: duration, kills: HashMap::new() }
+    }
+}
+
+/// A member's call for help, pointed out to its clanmates until it expires.
+pub struct DistressCall {
+    pub clan: u32,
+    pub caller: u32,
+    pub position: Vector2D,
+    /// The number of ticks until the call expires.
+    pub remaining: u64
+}
+
 /// State that maintains clan operations.
 #[derive(Default)]
 pub struct ClanState {
     pub clans: Vec<ClanInformation>,
+    /// The number of players from other clans killed by every clan.
+    pub kills: HashMap<u32, usize>,
+    pub war: Option<ClanWar>,
+    pub distress_calls: Vec<DistressCall>,
     counter: u32
 }
 
+/// Sends a notification to the player `id`, if it still exists.
+fn notify(entities: &EntityDataStructure, id: u32, message: String, color: Color) {
+    let Some(entity) = entities.get(&id) else { return; };
+    let Ok(mut entity) = entity.try_borrow_mut() else { return; };
+
+    entity.display.notifications.push(Notification {
+        message,
+        color,
+        lifetime: 150,
+        ..Default::default()
+    });
+}
+
 impl ClanState {
     fn get_next_clan_id(&mut self) -> u32 {
         self.counter += 1;
         self.counter
     }
 
+    /// The clan `id` is a member of, or has requested to join.
+    pub fn get_membership(&self, id: u32) -> Option<&ClanInformation> {
+        self.clans.iter().find(|clan| clan.members.contains(&id) || clan.pending_members.contains(&id))
+    }
+
+    fn get_owned_clan_mut(&mut self, owner: u32) -> Option<&mut ClanInformation> {
+        self.clans.iter_mut().find(|clan| clan.owner == owner)
+    }
+
     pub fn create_clan(&mut self, name: String, description: String, max_members: usize, owner: u32) -> u32 {
         let id = self.get_next_clan_id();
 
@@ -31,18 +85,373 @@ impl ClanState {
         id
     }
 
-    pub fn tick(&mut self, entities: &EntityDataStructure) {
+    /// Requests for `member` to join the clan `id`, which its owner may accept or reject.
+    pub fn request(&mut self, id: u32, member: u32) -> bool {
+        if self.get_membership(member).is_some() {
+            return false;
+        }
+
+        let Some(clan) = self.clans.iter_mut().find(|clan| clan.id == id) else { return false; };
+        if clan.members.len() >= clan.max_members {
+            return false;
+        }
+
+        clan.pending_members.push(member);
+        true
+    }
+
+    /// Accepts the request of `member` to join the clan owned by `owner`.
+    pub fn accept(&mut self, owner: u32, member: u32, entities: &EntityDataStructure) -> bool {
+        let Some(clan) = self.get_owned_clan_mut(owner) else { return false; };
+        if !clan.pending_members.contains(&member) || clan.members.len() >= clan.max_members {
+            return false;
+        }
+
+        clan.pending_members.retain(|&id| id != member);
+        clan.members.push(member);
+
+        notify(entities, member, format!("You joined {}.", clan.name), Color::MATERIAL_GREEN);
+        true
+    }
+
+    /// Rejects the request of `member` to join the clan owned by `owner`.
+    pub fn reject(&mut self, owner: u32, member: u32, entities: &EntityDataStructure) -> bool {
+        let Some(clan) = self.get_owned_clan_mut(owner) else { return false; };
+        if !clan.pending_members.contains(&member) {
+            return false;
+        }
+
+        clan.pending_members.retain(|&id| id != member);
+
+        notify(entities, member, format!("Your request to join {} was declined.", clan.name), Color::RED);
+        true
+    }
+
+    /// Removes `member` from the clan owned by `owner`.
+    pub fn kick(&mut self, owner: u32, member: u32, entities: &EntityDataStructure) -> bool {
+        let Some(clan) = self.get_owned_clan_mut(owner) else { return false; };
+        if member == owner || !clan.members.contains(&member) {
+            return false;
+        }
+
+        clan.members.retain(|&id| id != member);
+
+        notify(entities, member, format!("You were kicked from {}.", clan.name), Color::RED);
+        true
+    }
+
+    /// Hands the clan owned by `owner` over to `member`.
+    pub fn transfer(&mut self, owner: u32, member: u32, entities: &EntityDataStructure) -> bool {
+        let Some(clan) = self.get_owned_clan_mut(owner) else { return false; };
+        if member == owner || !clan.members.contains(&member) {
+            return false;
+        }
+
+        clan.owner = member;
+
+        notify(entities, member, format!("You are now the owner of {}.", clan.name), Color::MATERIAL_GREEN);
+        true
+    }
+
+    /// Removes `member` from its clan, or withdraws its request to join one.
+    /// The clan is disbanded if `member` owned it.
+    pub fn leave(&mut self, member: u32, entities: &EntityDataStructure) -> bool {
+        let Some(idx) = self.clans.iter().position(|clan| clan.members.contains(&member) || clan.pending_members.contains(&member)) else {
+            return false;
+        };
+
+        let clan = &mut self.clans[idx];
+        if clan.owner == member {
+            self.disband(idx, entities);
+        } else {
+            clan.members.retain(|&id| id != member);
+            clan.pending_members.retain(|&id| id != member);
+        }
+
+        true
+    }
+
+    /// Calls the clanmates of `caller` to `position`, replacing any call it made before.
+    pub fn call_for_help(&mut self, clan: u32, caller: u32, position: Vector2D) {
+        self.distress_calls.retain(|call| call.caller != caller);
+        self.distress_calls.push(DistressCall { clan, caller, position, remaining: DISTRESS_CALL_DURATION });
+    }
+
+    fn disband(&mut self, idx: usize, entities: &EntityDataStructure) {
+        let clan = self.clans.remove(idx);
+        self.distress_calls.retain(|call| call.clan != clan.id);
+
+        self.kills.remove(&clan.id);
+        if let Some(war) = &mut self.war {
+            war.kills.remove(&clan.id);
+        }
+
+        for &id in clan.members.iter().chain(clan.pending_members.iter()).filter(|&&id| id != clan.owner) {
+            notify(entities, id, format!("{} was disbanded.", clan.name), Color::RED);
+        }
+    }
+
+    /// Forgets players who have left, disbanding the clans whose owners have left,
+    /// and tells every entity which clan it fights for. Clan wars are only held in `GameMode::ClanWars`.
+    pub fn tick(&mut self, entities: &EntityDataStructure, mode: GameMode) {
+        match mode {
+            GameMode::ClanWars => { self.war.get_or_insert_with(|| ClanWar::new(CLAN_WAR_DURATION)); },
+            GameMode::FreeForAll => self.war = None
+        }
+
+        while let Some(idx) = self.clans.iter().position(|clan| !entities.contains_key(&clan.owner)) {
+            self.disband(idx, entities);
+        }
+
         for clan in self.clans.iter_mut() {
-            let mut i = 0;
-            while i < clan.members.len() {
-                let member = &clan.members[i];
-                if let Some(entity) = entities.get(member) {
-                    entity.borrow_mut().display.clan_id = Some(clan.id);
-                    i += 1;
-                } else {
-                    clan.members.remove(i);
+            clan.members.retain(|id| entities.contains_key(id));
+            clan.pending_members.retain(|id| entities.contains_key(id));
+        }
+
+        for call in self.distress_calls.iter_mut() {
+            call.remaining = call.remaining.saturating_sub(1);
+        }
+
+        let clans = &self.clans;
+        self.distress_calls.retain(|call| call.remaining > 0
+            && clans.iter().any(|clan| clan.id == call.clan && clan.members.contains(&call.caller)));
+
+        for entity in entities.values() {
+            let mut entity = entity.borrow_mut();
+            let member = entity.display.owners.map(|owners| owners.deep).unwrap_or(entity.id);
+
+            entity.display.clan_id = self.clans.iter().find(|clan| clan.members.contains(&member)).map(|clan| clan.id);
+            if entity.display.entity_type != EntityType::Player {
+                continue;
+            }
+
+            entity.display.pending_clan_id = self.clans.iter().find(|clan| clan.pending_members.contains(&entity.id)).map(|clan| clan.id);
+
+            let kills = std::mem::take(&mut entity.display.clan_kills);
+            if kills > 0 && let Some(clan) = entity.display.clan_id {
+                *self.kills.entry(clan).or_default() += kills;
+                if let Some(war) = &mut self.war {
+                    *war.kills.entry(clan).or_default() += kills;
                 }
             }
         }
+
+        self.tick_war(entities);
+    }
+
+    /// Counts down the clan war, announcing the winner once the round is over.
+    fn tick_war(&mut self, entities: &EntityDataStructure) {
+        let Some(war) = &mut self.war else { return; };
+
+        war.remaining = war.remaining.saturating_sub(1);
+        if war.remaining > 0 {
+            return;
+        }
+
+        let best = war.kills.values().copied().max().unwrap_or_default();
+        let leaders: Vec<&ClanInformation> = self.clans.iter()
+            .filter(|clan| best > 0 && war.kills.get(&clan.id) == Some(&best))
+            .collect();
+
+        let message = match leaders.as_slice() {
+            // Nobody is told about a round in which no clan scored.
+            [] => None,
+            [winner] => Some(format!("{} won the clan war with {} kill{}!", winner.name, best, if best == 1 { "" } else { "s" })),
+            _ => Some("The clan war ended in a draw.".to_string())
+        };
+
+        war.kills.clear();
+        war.remaining = war.duration;
+
+        let Some(message) = message else { return; };
+        for entity in entities.values() {
+            let Ok(mut entity) = entity.try_borrow_mut() else { continue; };
+            if entity.display.entity_type != EntityType::Player {
+                continue;
+            }
+
+            entity.display.notifications.push(Notification {
+                message: message.clone(),
+                color: Color::MATERIAL_PURPLE,
+                lifetime: 250,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// The standings of every clan, from the highest combined score to the lowest.
+    pub fn get_standings(&self, entities: &EntityDataStructure) -> Vec<ClanStanding> {
+        let mut standings: Vec<ClanStanding> = self.clans.iter()
+            .map(|clan| ClanStanding {
+                name: clan.name.clone(),
+                members: clan.members.len(),
+                score: clan.members.iter()
+                    .filter_map(|id| entities.get(id))
+                    .map(|entity| entity.borrow().display.score)
+                    .sum(),
+                kills: self.kills.get(&clan.id).copied().unwrap_or_default(),
+                war_kills: self.war.as_ref().and_then(|war| war.kills.get(&clan.id).copied()).unwrap_or_default()
+            })
+            .collect();
+
+        standings.sort_by_key(|standing| Reverse(standing.score));
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use crate::game::entity::base::Entity;
+    use super::*;
+
+    fn players(ids: &[u32]) -> EntityDataStructure {
+        ids.iter().map(|&id| (id, RefCell::new(Entity::from_id(id)))).collect()
+    }
+
+    fn notifications(entities: &EntityDataStructure, id: u32) -> Vec<String> {
+        entities[&id].borrow().display.notifications.iter().map(|notification| notification.message.clone()).collect()
+    }
+
+    /// A clan of two owned by 1, with 3 waiting to join.
+    fn clan(entities: &EntityDataStructure) -> (ClanState, u32) {
+        let mut state = ClanState::default();
+        let id = state.create_clan("Clan".to_string(), String::new(), 3, 1);
+
+        assert!(state.request(id, 2));
+        assert!(state.accept(1, 2, entities));
+        assert!(state.request(id, 3));
+
+        (state, id)
+    }
+
+    #[test]
+    fn owners_accept_and_reject_requests() {
+        let entities = players(&[1, 2, 3, 4]);
+        let (mut state, id) = clan(&entities);
+
+        assert!(!state.request(id, 2));
+        assert!(!state.accept(2, 3, &entities));
+        assert_eq!(notifications(&entities, 2), vec!["You joined Clan.".to_string()]);
+
+        assert!(state.reject(1, 3, &entities));
+        assert!(!state.reject(1, 3, &entities));
+        assert!(state.get_membership(3).is_none());
+        assert_eq!(notifications(&entities, 3), vec!["Your request to join Clan was declined.".to_string()]);
+
+        assert!(state.request(id, 3));
+        assert!(state.accept(1, 3, &entities));
+        assert!(!state.request(id, 4));
+        assert_eq!(state.clans[0].members, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn owners_kick_members_and_transfer_ownership() {
+        let entities = players(&[1, 2, 3]);
+        let (mut state, _) = clan(&entities);
+
+        assert!(!state.kick(1, 1, &entities));
+        assert!(!state.kick(1, 3, &entities));
+        assert!(!state.transfer(1, 3, &entities));
+
+        assert!(state.transfer(1, 2, &entities));
+        assert!(!state.kick(1, 2, &entities));
+        assert!(state.kick(2, 1, &entities));
+
+        assert_eq!(state.clans[0].owner, 2);
+        assert_eq!(state.clans[0].members, vec![2]);
+        assert!(notifications(&entities, 1).contains(&"You were kicked from Clan.".to_string()));
+    }
+
+    #[test]
+    fn members_leave_and_owners_disband() {
+        let entities = players(&[1, 2, 3]);
+        let (mut state, id) = clan(&entities);
+        state.call_for_help(id, 2, Vector2D::ZERO);
+
+        assert!(state.leave(2, &entities));
+        assert!(!state.leave(2, &entities));
+        assert_eq!(state.clans[0].members, vec![1]);
+
+        assert!(state.leave(1, &entities));
+        assert!(state.clans.is_empty() && state.distress_calls.is_empty());
+        assert!(state.get_membership(3).is_none());
+        assert_eq!(notifications(&entities, 3), vec!["Clan was disbanded.".to_string()]);
+    }
+
+    #[test]
+    fn clans_are_disbanded_when_their_owner_leaves() {
+        let mut entities = players(&[1, 2, 3]);
+        let (mut state, id) = clan(&entities);
+
+        state.tick(&entities, GameMode::FreeForAll);
+        assert_eq!(entities[&2].borrow().display.clan_id, Some(id));
+        assert_eq!(entities[&3].borrow().display.pending_clan_id, Some(id));
+
+        entities.remove(&1);
+        state.tick(&entities, GameMode::FreeForAll);
+        assert!(state.clans.is_empty());
+        assert_eq!(entities[&2].borrow().display.clan_id, None);
+        assert_eq!(notifications(&entities, 2).last().unwrap(), "Clan was disbanded.");
+    }
+
+    #[test]
+    fn wars_follow_the_game_mode() {
+        let entities = players(&[1]);
+        let mut state = ClanState::default();
+
+        state.tick(&entities, GameMode::FreeForAll);
+        assert!(state.war.is_none());
+
+        state.tick(&entities, GameMode::ClanWars);
+        assert_eq!(state.war.as_ref().unwrap().remaining, CLAN_WAR_DURATION - 1);
+
+        state.tick(&entities, GameMode::FreeForAll);
+        assert!(state.war.is_none());
+    }
+
+    #[test]
+    fn wars_announce_their_winner_and_restart() {
+        let entities = players(&[1, 2, 3]);
+        let (mut state, id) = clan(&entities);
+        let rival = state.create_clan("Rival".to_string(), String::new(), 3, 3);
+        state.war = Some(ClanWar::new(2));
+
+        state.tick_war(&entities);
+        state.tick_war(&entities);
+        assert!(notifications(&entities, 1).is_empty() && state.war.as_ref().unwrap().remaining == 2);
+
+        state.war.as_mut().unwrap().kills.extend([(id, 2), (rival, 1)]);
+        state.tick_war(&entities);
+        state.tick_war(&entities);
+        assert_eq!(notifications(&entities, 3).last().unwrap(), "Clan won the clan war with 2 kills!");
+        assert!(state.war.as_ref().unwrap().kills.is_empty());
+
+        state.war.as_mut().unwrap().kills.extend([(id, 1), (rival, 1)]);
+        state.tick_war(&entities);
+        state.tick_war(&entities);
+        assert_eq!(notifications(&entities, 3).last().unwrap(), "The clan war ended in a draw.");
+    }
+
+    #[test]
+    fn standings_are_ordered_by_score() {
+        let entities = players(&[1, 2, 3]);
+        let (mut state, id) = clan(&entities);
+        state.leave(3, &entities);
+        let rival = state.create_clan("Rival".to_string(), String::new(), 3, 3);
+
+        for (id, score) in [(1, 100), (2, 200), (3, 500)] {
+            entities[&id].borrow_mut().display.score = score;
+        }
+
+        state.kills.insert(id, 4);
+        state.war = Some(ClanWar::new(2));
+        state.war.as_mut().unwrap().kills.insert(rival, 1);
+
+        let standings = state.get_standings(&entities);
+        assert_eq!(standings.iter().map(|standing| standing.name.as_str()).collect::<Vec<_>>(), vec!["Rival", "Clan"]);
+        assert_eq!((standings[1].members, standings[1].score, standings[1].kills, standings[1].war_kills), (2, 300, 4, 0));
+        assert_eq!((standings[0].members, standings[0].score, standings[0].kills, standings[0].war_kills), (1, 500, 0, 1));
     }
-}
\ No newline at end of file
+}
+
@@ -0,0 +1,61 @@
+use std::{path::PathBuf, time::SystemTime};
+use shared::game::definitions::{set_definitions, DefinitionFile, Definitions};
+use strum::IntoEnumIterator;
+
+use crate::{seconds_to_ticks, server::FPS};
+
+/// The environment variable naming a directory of definition files to hot-load.
+/// If unset, the definitions embedded at build time are used.
+pub const DEFINITIONS_DIR_VAR: &str = "DEFINITIONS_DIR";
+/// How often the definition files are checked for changes.
+const POLL_INTERVAL: u64 = seconds_to_ticks!(2);
+
+/// Watches a directory of definition files, reloading them whenever they change.
+/// Clients keep rendering with their embedded definitions.
+#[derive(Default)]
+pub struct DefinitionWatcher {
+    directory: Option<PathBuf>,
+    last_modified: Option<SystemTime>,
+    ticks: u64
+}
+
+impl DefinitionWatcher {
+    pub fn from_env() -> DefinitionWatcher {
+        DefinitionWatcher {
+            directory: std::env::var_os(DEFINITIONS_DIR_VAR).map(PathBuf::from),
+            ..Default::default()
+        }
+    }
+
+    /// Checks for changes, returning whether new definitions were installed.
+    pub fn tick(&mut self) -> bool {
+        let Some(directory) = &self.directory else { return false; };
+
+        self.ticks += 1;
+        if self.ticks % POLL_INTERVAL != 1 {
+            return false;
+        }
+
+        let modified = DefinitionFile::iter()
+            .filter_map(|file| std::fs::metadata(directory.join(file.file_name())).and_then(|m| m.modified()).ok())
+            .max();
+
+        if modified.is_none() || modified == self.last_modified {
+            return false;
+        }
+
+        self.last_modified = modified;
+
+        match Definitions::load_dir(directory) {
+            Ok(definitions) => {
+                set_definitions(definitions);
+                println!("Loaded definitions from {}.", directory.display());
+                true
+            },
+            Err(error) => {
+                println!("Failed to load definitions, keeping the old ones: {}", error);
+                false
+            }
+        }
+    }
+}
\ No newline at end of file
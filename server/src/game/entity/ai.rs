@@ -45,7 +45,7 @@ impl AI {
         }
     }
 
-    fn get_target(&mut self, entities: &EntityDataStructure, position: Vector2D, surroundings: Vec<u32>) -> Option<u32> {
+    fn get_target(&mut self, entities: &EntityDataStructure, position: Vector2D, surroundings: Vec<u32>, allied_clan: Option<u32>) -> Option<u32> {
         if let AIState::Active(id) = self.state {
             if !surroundings.contains(&id) || entities.get(&id).is_none() {
                 self.state = AIState::Idle;
@@ -59,7 +59,9 @@ impl AI {
             .filter(|&&id| !self.ownership.has_owner(id) && entities.get(&id).is_some())
             .map(|id| entities.get(id).unwrap().borrow_mut())
             .filter(|entity| {
-                if entity.stats.alive != AliveState::Alive || !matches!(entity.display.entity_type, EntityType::Player | EntityType::Orb) {
+                if entity.stats.alive != AliveState::Alive || !matches!(entity.display.entity_type, EntityType::Player | EntityType::Orb)
+                    || (allied_clan.is_some() && entity.display.clan_id == allied_clan)
+                {
                     return false;
                 } else if let Some(owners) = entity.display.owners {                    
                     if self.ownership.has_owner(owners.shallow) || self.ownership.has_owner(owners.deep)
@@ -97,12 +99,13 @@ impl AI {
         None
     }
 
-    pub fn tick(&mut self, entities: &EntityDataStructure, self_position: Vector2D, owner_position: Vector2D, surroundings: Vec<u32>) {
+    /// Aims at the nearest target, ignoring members of `allied_clan`.
+    pub fn tick(&mut self, entities: &EntityDataStructure, self_position: Vector2D, owner_position: Vector2D, surroundings: Vec<u32>, allied_clan: Option<u32>) {
         if let AIState::Possessed(mouse) = self.state {
             self.aim = mouse;
             self.movement = Vector2D::from_polar(1.0, (self.aim - self_position).angle());
         } else {
-            let Some(target) = self.get_target(entities, owner_position, surroundings) else { return; };
+            let Some(target) = self.get_target(entities, owner_position, surroundings, allied_clan) else { return; };
             let entity = entities.get(&target).unwrap().borrow_mut();
 
             if self.prediction {
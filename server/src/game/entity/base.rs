@@ -1,9 +1,9 @@
-use std::{collections::HashSet, num::NonZeroU32};
+use std::{collections::{HashMap, HashSet, VecDeque}, num::NonZeroU32};
 use derive_new::new as New;
-use shared::{game::{body::{get_body_base_identity, BodyIdentity}, entity::{get_min_score_from_level, EntityType, InputFlags, Notification, Ownership, TankUpgrades, UpgradeStats, BASE_TANK_RADIUS}, orb::{get_orb_basic_identity, OrbIdentity}, turret::{get_turret_base_identity, TurretStructure}}, utils::{codec::BinaryCodec, color::Color, consts::{ARENA_SIZE, FRICTION, MAX_LEVEL}, vec2::Vector2D}};
+use shared::{game::{ability::{AbilityKind, AbilityState}, gear::Inventory, ledger::DamageLedger, quests::{QuestEvent, QuestProgress}, body::{BodyIdentity, BodyIdentityIds}, entity::{get_min_score_from_level, EntityType, GameplayEvent, InputFlags, Notification, Ownership, SpectateMode, TankUpgrades, UpgradeStats, BASE_TANK_RADIUS}, movement::integrate, orb::{OrbIdentity, OrbIdentityIds}, status::{StatusEffect, StatusEffects}, turret::{Detonation, TurretIdentityIds, TurretStructure}}, utils::{codec::BinaryCodec, color::Color, consts::MAX_LEVEL, vec2::Vector2D}};
 use strum::EnumCount;
 
-use crate::{game::state::{EntityDataStructure, GameState}, seconds_to_ticks, server::FPS};
+use crate::{connection::packets, game::state::{EntityDataStructure, GameState}, seconds_to_ticks, server::{ASSIST_NOTIFICATION_MIN_SCORE, EFFECT_COOLDOWN, FPS}};
 
 use super::ai::AI;
 
@@ -12,7 +12,9 @@ pub enum AliveState {
     #[default]
     Uninitialized,
     Alive,
-    Dead
+    Dead,
+    /// Watching the arena without ever spawning.
+    Spectating
 }
 
 #[derive(Default, Clone)]
@@ -23,7 +25,13 @@ pub struct PhysicsComponent {
     pub angle: f32,
     pub mouse: Vector2D,
     pub inputs: InputFlags,
+    /// Inputs received from the client which have not been processed yet, keyed by sequence number.
+    pub input_queue: VecDeque<(u32, InputFlags)>,
+    /// The sequence number of the last input processed.
+    pub input_sequence: u32,
     pub has_moved: bool,
+    /// Whether the ability input was held last tick, so holding it only spends one charge.
+    pub ability_held: bool,
     pub collidable: bool,
     pub absorption_factor: f32,
     pub push_factor: f32,
@@ -56,13 +64,27 @@ pub struct DisplayComponent {
     pub orb_identity: OrbIdentity,
     pub turret_idx: isize,
     pub owners: Option<Ownership>,
+    /// The clan of the entity, or of the player who owns it.
     pub clan_id: Option<u32>,
     pub pending_clan_id: Option<u32>,
     pub owned_entities: Vec<u32>,
     pub radius: f32,
 
     pub typing: bool,
-    pub messages: Vec<(String, u64)>
+    pub messages: Vec<(String, u64)>,
+
+    /// Progress towards every quest this session.
+    pub quests: QuestProgress,
+    /// Events which happened to the entity this tick, sent to nearby players once the tick ends.
+    pub events: Vec<GameplayEvent>,
+    /// The number of players killed this life.
+    pub kills: usize,
+    /// The number of players from other clans killed since the clan state last counted them.
+    pub clan_kills: usize,
+    /// What the camera follows while spectating.
+    pub spectate_mode: SpectateMode,
+    /// The player being followed while spectating.
+    pub spectated: Option<u32>
 }
 
 #[derive(Default, Clone)]
@@ -70,12 +92,21 @@ pub struct TimeComponent {
     pub ticks: u64,
     pub spawn_tick: u64,
     pub last_damage_tick: u64,
-    pub last_switch_tick: u64
+    pub last_switch_tick: u64,
+    pub last_distress_tick: Option<u64>
 }
 
 #[derive(Default, Clone, New)]
 pub struct ConnectionComponent {
-    pub outgoing_packets: Vec<BinaryCodec>
+    pub outgoing_packets: Vec<BinaryCodec>,
+    /// The token which lets the player reclaim its tank after disconnecting.
+    pub resume_token: Option<u64>,
+    /// The tick at which the player disconnected, if it is waiting to be resumed.
+    pub disconnected_at: Option<u64>,
+    /// The token of the profile the player's runs are recorded in.
+    pub profile: Option<u64>,
+    /// The name of every clan member as last sent to the player.
+    pub clan_names: HashMap<u32, String>
 }
 
 #[derive(Default, Clone)]
@@ -91,6 +122,26 @@ pub struct StatsComponent {
     pub reload: f32,
     pub speed: f32,
     pub lifetime: isize,
+    /// How the entity explodes, if it is a mine.
+    pub detonation: Option<Detonation>,
+    /// The effects inflicted on whatever the entity damages.
+    pub effects: Vec<StatusEffect>,
+    /// The tick each entity was last afflicted with this entity's effects.
+    pub afflicted: HashMap<u32, u64>,
+    /// The effects the entity is afflicted with.
+    pub status_effects: StatusEffects,
+    pub ability: AbilityState,
+    pub energy: f32,
+    pub max_energy: f32,
+    /// The tick energy was last spent on, which delays regeneration.
+    pub last_energy_tick: u64,
+    pub inventory: Inventory,
+    /// Whether the inventory, or a trade involving it, has changed since it was last sent.
+    pub inventory_changed: bool,
+    /// The total damage taken from every attacker this life.
+    pub damage_taken: HashMap<u32, f32>,
+    /// The damage recently taken, used to split the reward for a kill.
+    pub ledger: DamageLedger,
 
     pub alive: AliveState
 }
@@ -120,7 +171,14 @@ pub enum EntityConstruction {
         kb_factors: (f32, f32),
         ai: Option<AI>,
         projectile_type: EntityType,
-        bound_to_walls: bool
+        bound_to_walls: bool,
+        /// The turrets the projectile carries, under the id of the structure which fired it.
+        turret_identity: TurretStructure,
+        /// The stats of the shooter, used by carried turrets.
+        stat_investments: [usize; UpgradeStats::COUNT],
+        reload: f32,
+        detonation: Option<Detonation>,
+        effects: Vec<StatusEffect>
     }
 }
 
@@ -135,9 +193,12 @@ impl Entity {
                 angle: 0.0,
                 mouse: Vector2D::ZERO,
                 inputs: InputFlags::new(0),
+                input_queue: VecDeque::new(),
+                input_sequence: 0,
                 has_moved: false,
+                ability_held: false,
                 collidable: true,
-                absorption_factor: get_body_base_identity().absorption_factor,
+                absorption_factor: BodyIdentity::try_from(BodyIdentityIds::Base).unwrap().absorption_factor,
                 push_factor: 8.0,
                 collisions: HashSet::new(),
                 ai: None,
@@ -157,9 +218,9 @@ impl Entity {
                 notifications: vec![],
                 killer: NonZeroU32::new(0),
                 entity_type: EntityType::Player,
-                body_identity: get_body_base_identity(),
-                turret_identity: get_turret_base_identity(),
-                orb_identity: get_orb_basic_identity(),
+                body_identity: BodyIdentity::try_from(BodyIdentityIds::Base).unwrap(),
+                turret_identity: TurretStructure::try_from(TurretIdentityIds::Base).unwrap(),
+                orb_identity: OrbIdentity::try_from(OrbIdentityIds::Basic).unwrap(),
                 turret_idx: -1,
                 owners: None,
                 clan_id: None,
@@ -167,7 +228,13 @@ impl Entity {
                 owned_entities: vec![],
                 radius: BASE_TANK_RADIUS,
                 typing: false,
-                messages: Vec::with_capacity(3)
+                messages: Vec::with_capacity(3),
+                quests: QuestProgress::default(),
+                events: vec![],
+                kills: 0,
+                clan_kills: 0,
+                spectate_mode: SpectateMode::default(),
+                spectated: None
             },
             stats: StatsComponent {
                 health: 0.0, max_health: 0.0, alive: AliveState::Uninitialized, 
@@ -176,16 +243,33 @@ impl Entity {
                 damage_per_tick: 0.0,
                 reload: 0.0,
                 speed: 0.0,
-                lifetime: -1
+                lifetime: -1,
+                detonation: None,
+                effects: vec![],
+                afflicted: HashMap::new(),
+                status_effects: StatusEffects::default(),
+                ability: AbilityState::default(),
+                energy: 0.0,
+                max_energy: 0.0,
+                last_energy_tick: 0,
+                inventory: Inventory::default(),
+                inventory_changed: false,
+                damage_taken: HashMap::new(),
+                ledger: DamageLedger::default()
             },
             time: TimeComponent {
                 ticks: 0,
                 spawn_tick: 0,
                 last_damage_tick: 0,
-                last_switch_tick: 0
+                last_switch_tick: 0,
+                last_distress_tick: None
             },
             connection: ConnectionComponent {
-                outgoing_packets: vec![]
+                outgoing_packets: vec![],
+                resume_token: None,
+                disconnected_at: None,
+                profile: None,
+                clan_names: HashMap::new()
             }
         }
     }
@@ -193,11 +277,22 @@ impl Entity {
     pub fn take_census(&self, codec: &mut BinaryCodec, is_self: bool) {
         match self.display.entity_type {
             EntityType::Player => self.take_tank_census(codec, is_self),
-            EntityType::Bullet | EntityType::Drone | EntityType::Trap => self.take_projectile_census(codec),
+            EntityType::Bullet | EntityType::Drone | EntityType::Trap
+                | EntityType::Missile | EntityType::Minion | EntityType::Mine => self.take_projectile_census(codec),
             EntityType::Orb => self.take_orb_census(codec)
         }
     }
 
+    /// Encodes every status effect the entity is afflicted with, along with how much of it remains.
+    pub fn encode_status_effects(&self, codec: &mut BinaryCodec) {
+        codec.encode_varuint(self.stats.status_effects.len() as u64);
+        for effect in self.stats.status_effects.iter() {
+            codec.encode_varuint(effect.kind as u64);
+            codec.encode_varuint(effect.stacks as u64);
+            codec.encode_f32(effect.remaining());
+        }
+    }
+
     pub fn tick(state: &mut GameState, id: u32) {
         if !state.entities.contains_key(&id) { return; }
 
@@ -205,11 +300,13 @@ impl Entity {
             let mut entity = state.entities.get(&id).unwrap().borrow_mut();
             entity.time.ticks += 1;
             entity.physics.collisions.clear();
+            entity.tick_status_effects(&state.entities);
 
             let constructions = match entity.display.entity_type {
-                EntityType::Player => entity.tick_tank(&state.entities, &state.shg, &state.clan_state),
-                EntityType::Bullet | EntityType::Drone | EntityType::Trap => entity.tick_projectile(&state.entities),
-                EntityType::Orb => entity.tick_orb(&state.entities)
+                EntityType::Player => entity.tick_tank(state),
+                EntityType::Bullet | EntityType::Drone | EntityType::Trap
+                    | EntityType::Missile | EntityType::Minion | EntityType::Mine => entity.tick_projectile(&state.entities, &state.shg, state.friendly_fire, state.world.arena_size),
+                EntityType::Orb => entity.tick_orb(&state.entities, state.world.arena_size)
             };
 
             let (self_position, owner_position, surroundings) = 
@@ -219,14 +316,22 @@ impl Entity {
                     (entity.physics.position, entity.physics.position, entity.display.surroundings.clone())
                 };
 
+            let allied_clan = if state.friendly_fire { None } else { entity.display.clan_id };
             if let Some(ai) = &mut entity.physics.ai {
-                ai.tick(&state.entities, self_position, owner_position, surroundings);
+                ai.tick(&state.entities, self_position, owner_position, surroundings, allied_clan);
             }
 
             (constructions, entity.stats.alive)
         };
 
         if alive_state == AliveState::Dead {
+            if let Some(mut entity) = state.get_entity(id) && entity.display.entity_type == EntityType::Player {
+                let death_packet = packets::form_death_packet(&entity, &state.entities);
+                entity.connection.outgoing_packets.push(death_packet);
+            }
+
+            state.finish_run(id);
+
             state.delete_entity(id);
         }
 
@@ -235,7 +340,9 @@ impl Entity {
 
             match construction {
                 EntityConstruction::ProjectileConstruction { owners, .. } => {
-                    state.insert_entity(Entity::generate_projectile_entity(id, construction));
+                    let mut projectile = Entity::generate_projectile_entity(id, construction);
+                    projectile.display.clan_id = state.entities.get(&owners.deep).and_then(|owner| owner.borrow().display.clan_id);
+                    state.insert_entity(projectile);
                     
                     if let Some(owner) = state.entities.get(&owners.shallow) {
                         owner.borrow_mut().display.owned_entities.push(id);
@@ -247,9 +354,9 @@ impl Entity {
         }
     }
 
-    pub fn base_tick(&mut self) {
-        if self.stats.lifetime != -1 && self.time.ticks >= self.stats.lifetime as u64
-            || self.stats.health <= 0.0
+    pub fn base_tick(&mut self, arena_size: f32) {
+        if self.stats.alive != AliveState::Spectating
+            && (self.stats.lifetime != -1 && self.time.ticks >= self.stats.lifetime as u64 || self.stats.health <= 0.0)
         {
             self.stats.alive = AliveState::Dead;   
         } else if self.stats.health < self.stats.max_health && matches!(self.display.entity_type, EntityType::Player | EntityType::Orb) {
@@ -259,19 +366,114 @@ impl Entity {
             }
         }
 
-        self.physics.velocity *= 1.0 - FRICTION;
-        self.physics.position += self.physics.velocity + self.physics.additional_velocity;
-        
-        if self.physics.bound_to_walls {
-            self.physics.position.constrain(0.0, ARENA_SIZE);
+        integrate(
+            &mut self.physics.position,
+            &mut self.physics.velocity,
+            self.physics.additional_velocity,
+            self.physics.bound_to_walls.then_some(arena_size)
+        );
+    }
+
+    /// Counts down the entity's status effects and deals any poison damage,
+    /// crediting whoever inflicted it if the entity dies.
+    fn tick_status_effects(&mut self, entities: &EntityDataStructure) {
+        self.stats.status_effects.tick();
+
+        let (damage, source) = self.stats.status_effects.poison_per_tick();
+        if damage == 0.0 || self.stats.alive != AliveState::Alive || self.stats.health <= 0.0 {
+            return;
+        }
+
+        self.take_damage(damage * self.get_damage_taken_multiplier(), source);
+
+        if self.stats.health <= 0.0
+            && let Some(source) = source
+            && let Some(entity) = entities.get(&source)
+            && let Ok(mut entity) = entity.try_borrow_mut()
+        {
+            entity.credit_kill(entities, self);
+        }
+    }
+
+    /// Afflicts `victim` with the effects this entity inflicts, on behalf of its deep owner.
+    /// A victim which stays in contact is only afflicted again once the cooldown has passed.
+    pub fn inflict_effects(&mut self, victim: &mut Entity) {
+        if victim.display.entity_type.is_projectile() || self.stats.effects.is_empty() {
+            return;
+        }
+
+        let ticks = self.time.ticks;
+        self.stats.afflicted.retain(|_, &mut tick| ticks - tick < EFFECT_COOLDOWN);
+        if self.stats.afflicted.contains_key(&victim.id) {
+            return;
+        }
+
+        self.stats.afflicted.insert(victim.id, ticks);
+
+        let source = self.get_deep_owner();
+        for effect in self.stats.effects.iter() {
+            victim.stats.status_effects.apply(effect, Some(source));
         }
     }
 
+    /// The entity ultimately responsible for whatever this entity does.
+    pub fn get_deep_owner(&self) -> u32 {
+        self.display.owners.map_or(self.id, |owners| owners.deep)
+    }
+
+    /// The name of the entity as a source of damage, as shown to the players it damages.
+    pub fn get_source_name(&self) -> String {
+        match self.display.entity_type {
+            EntityType::Orb => format!("{} Orb", self.display.orb_identity.id),
+            _ if self.display.name.is_empty() => "An unnamed tank".to_string(),
+            _ => self.display.name.clone()
+        }
+    }
+
+    /// Deals `damage` to the entity, queueing a hit event for clients unless the entity is a projectile.
+    pub fn take_damage(&mut self, damage: f32, attacker: Option<u32>) {
+        self.stats.health -= damage;
+        self.stats.last_damage_tick = self.time.ticks;
+
+        if !self.display.entity_type.is_projectile() {
+            self.display.events.push(GameplayEvent::Hit { attacker, victim: self.id, damage });
+
+            if let Some(attacker) = attacker {
+                *self.stats.damage_taken.entry(attacker).or_default() += damage;
+                self.stats.ledger.record(attacker, damage, self.time.ticks);
+            }
+        }
+    }
+
+    /// The multiplier for any damage dealt to the entity, from armor breaks and shields.
+    pub fn get_damage_taken_multiplier(&self) -> f32 {
+        let mut multiplier = self.stats.status_effects.damage_taken_multiplier();
+
+        if self.stats.ability.is_active()
+            && self.stats.ability.kind == Some(AbilityKind::Shield)
+            && let Some(ability) = self.get_ability()
+        {
+            multiplier *= (1.0 - ability.magnitude).clamp(0.0, 1.0);
+        }
+
+        multiplier
+    }
+
+    /// Whether both entities fight for the same clan.
+    pub fn is_clanmate(&self, other: &Entity) -> bool {
+        self.display.clan_id.is_some() && self.display.clan_id == other.display.clan_id
+    }
+
+    /// Whether this entity may hurt `other`.
+    pub fn can_damage(&self, other: &Entity, friendly_fire: bool) -> bool {
+        friendly_fire || !self.is_clanmate(other)
+    }
+
     pub fn should_collide(&self, other: &Entity) -> bool {
         if !self.physics.collidable || !other.physics.collidable {
             return false;
         }
-        
+
         if let Some(owners_self) = self.display.owners {
             if let Some(owners_other) = other.display.owners {
                 // CASE 1: Both have owners.
@@ -297,7 +499,7 @@ impl Entity {
         true
     }
 
-    pub fn collide(&mut self, entities: &EntityDataStructure, other: &mut Entity) {
+    pub fn collide(&mut self, entities: &EntityDataStructure, other: &mut Entity, friendly_fire: bool) {
         if (self.stats.health <= 0.0 || other.stats.health <= 0.0) ||
             (self.stats.alive != AliveState::Alive || other.stats.alive != AliveState::Alive) ||
             (self.physics.collisions.contains(&other.id) || other.physics.collisions.contains(&self.id)) ||
@@ -320,8 +522,16 @@ impl Entity {
             (other.physics.position - self.physics.position).angle()
         );
 
-        let mut df1 = self.stats.damage_per_tick * other.stats.damage_reduction;
-        let mut df2 = other.stats.damage_per_tick * self.stats.damage_reduction;
+        self.physics.collisions.insert(other.id);
+        other.physics.collisions.insert(self.id);
+
+        // Clanmates still push each other around, but never hurt each other.
+        if !self.can_damage(other, friendly_fire) {
+            return;
+        }
+
+        let mut df1 = self.stats.damage_per_tick * other.stats.damage_reduction * other.get_damage_taken_multiplier();
+        let mut df2 = other.stats.damage_per_tick * self.stats.damage_reduction * self.get_damage_taken_multiplier();
 
         if self.display.entity_type == EntityType::Player && other.display.entity_type == EntityType::Player {
             df1 *= 1.5;
@@ -329,51 +539,79 @@ impl Entity {
         }
 
         if df1 != 0.0 {
-            other.stats.last_damage_tick = other.time.ticks;
-            other.stats.health -= df1;
+            other.take_damage(df1, Some(self.get_deep_owner()));
+            self.inflict_effects(other);
         }
 
         if df2 != 0.0 {
-            self.stats.last_damage_tick = self.time.ticks;
-            self.stats.health -= df2;
+            self.take_damage(df2, Some(other.get_deep_owner()));
+            other.inflict_effects(self);
         }
 
-        self.physics.collisions.insert(other.id);
-        other.physics.collisions.insert(self.id);
-
         if self.stats.health <= 0.0 {
-            other.kill(self);
-
-            if let Some(owners) = other.display.owners {
-                if owners.shallow != other.id && let Some(entity) = entities.get(&owners.shallow) {
-                    entity.borrow_mut().kill(self);
-                }
-                
-                if owners.deep != other.id && owners.deep != owners.shallow && let Some(entity) = entities.get(&owners.deep) {
-                    entity.borrow_mut().kill(self);
-                }
-            }
+            other.credit_kill(entities, self);
         }
         
         if other.stats.health <= 0.0 {
-            self.kill(other);
-
-            if let Some(owners) = self.display.owners {
-                if owners.shallow != self.id && let Some(entity) = entities.get(&owners.shallow) {
-                    entity.borrow_mut().kill(other);
-                }
-                
-                if owners.deep != self.id && owners.deep != owners.shallow && let Some(entity) = entities.get(&owners.deep) {
-                    entity.borrow_mut().kill(other);
-                }
-            }
+            self.credit_kill(entities, other);
         }
 
         other.time.last_damage_tick = other.time.ticks;
         self.time.last_damage_tick = self.time.ticks;
     }
 
-    pub fn kill(&mut self, other: &mut Entity) {
+    /// Kills `victim`, crediting this entity and every entity which owns it.
+    /// The deep owner is credited last, so it is recorded as the killer.
+    /// The reward is split with every other tank which recently damaged the victim, by how much damage it dealt.
+    pub fn credit_kill(&mut self, entities: &EntityDataStructure, victim: &mut Entity) {
+        let killer = self.get_deep_owner();
+        let shares = victim.stats.ledger.get_shares(victim.time.ticks, |id| {
+            id == killer || entities.get(&id)
+                .and_then(|entity| entity.try_borrow().ok())
+                .is_some_and(|entity| entity.display.entity_type == EntityType::Player && entity.stats.alive == AliveState::Alive)
+        });
+
+        let share = match shares.iter().find(|&&(id, _)| id == killer) {
+            Some(&(_, share)) => share,
+            None if shares.is_empty() => 1.0,
+            None => 0.0
+        };
+
+        self.kill(victim, share);
+
+        if let Some(owners) = self.display.owners {
+            if owners.shallow != self.id && let Some(entity) = entities.get(&owners.shallow) {
+                entity.borrow_mut().kill(victim, share);
+            }
+            
+            if owners.deep != self.id && owners.deep != owners.shallow && let Some(entity) = entities.get(&owners.deep) {
+                entity.borrow_mut().kill(victim, share);
+            }
+        }
+
+        for (id, share) in shares {
+            if id != killer
+                && let Some(entity) = entities.get(&id)
+                && let Ok(mut entity) = entity.try_borrow_mut()
+            {
+                entity.assist(victim, share);
+            }
+        }
+    }
+
+    /// The score given for killing `other`, before it is split between everyone who damaged it.
+    fn get_kill_reward(other: &Entity) -> usize {
+        match other.display.entity_type {
+            EntityType::Player => other.display.score.min(get_min_score_from_level(MAX_LEVEL)),
+            EntityType::Orb => other.display.orb_identity.exp_yield,
+            _ => 0
+        }
+    }
+
+    /// Kills `other`, receiving `share` of the reward for it.
+    pub fn kill(&mut self, other: &mut Entity, share: f32) {
+        let reward = (Entity::get_kill_reward(other) as f32 * share).round() as usize;
+
         if self.display.entity_type == EntityType::Player && other.display.entity_type == EntityType::Player {
             self.display.notifications.push(Notification {
                 message: format!("You killed {}", other.display.name),
@@ -382,14 +620,53 @@ impl Entity {
                 ..Default::default()
             });
 
-            self.display.score += other.display.score.min(get_min_score_from_level(MAX_LEVEL));
+            self.display.score += reward;
+            self.display.kills += 1;
+            if other.display.clan_id.is_some() && !self.is_clanmate(other) {
+                self.display.clan_kills += 1;
+            }
+
+            self.handle_quest_event(QuestEvent::PlayerKilled {
+                body: self.display.body_identity.id,
+                turret: self.display.turret_identity.id
+            });
         } else if other.display.entity_type == EntityType::Orb {
-            self.display.score += other.display.orb_identity.exp_yield;
+            self.display.score += reward;
+            self.stats.energy = (self.stats.energy + other.display.orb_identity.energy_yield * share).min(self.stats.max_energy);
+
+            if self.display.entity_type == EntityType::Player {
+                self.roll_gear_drop(other.display.orb_identity.gear_drop_chance * share);
+                self.handle_quest_event(QuestEvent::OrbDestroyed(other.display.orb_identity.id));
+
+                if other.display.orb_identity.id == OrbIdentityIds::Radiant {
+                    self.display.events.push(GameplayEvent::ObjectiveCaptured { id: self.id });
+                }
+            }
         }
 
         other.display.killer = NonZeroU32::new(self.id);
     }
 
+    /// Receives `share` of the reward for helping kill `other`, without being credited with the kill.
+    pub fn assist(&mut self, other: &Entity, share: f32) {
+        let reward = (Entity::get_kill_reward(other) as f32 * share).round() as usize;
+        self.display.score += reward;
+
+        if other.display.entity_type == EntityType::Orb {
+            self.stats.energy = (self.stats.energy + other.display.orb_identity.energy_yield * share).min(self.stats.max_energy);
+            self.roll_gear_drop(other.display.orb_identity.gear_drop_chance * share);
+        }
+
+        if other.display.entity_type == EntityType::Player || reward >= ASSIST_NOTIFICATION_MIN_SCORE {
+            self.display.notifications.push(Notification {
+                message: format!("You assisted in killing {} (+{} score)", other.get_source_name(), reward),
+                color: Color::BLACK,
+                lifetime: 150,
+                ..Default::default()
+            });
+        }
+    }
+
     fn generate_projectile_entity(id: u32, construction: EntityConstruction) -> Entity {
         let EntityConstruction::ProjectileConstruction { 
             speed, 
@@ -404,7 +681,12 @@ impl Entity {
             kb_factors,
             mut ai,
             projectile_type,
-            bound_to_walls
+            bound_to_walls,
+            turret_identity,
+            stat_investments,
+            reload,
+            detonation,
+            effects
         } = construction;
 
         if let Some(ref mut ai) = ai {
@@ -420,7 +702,10 @@ impl Entity {
                 angle,
                 mouse: Vector2D::ZERO,
                 inputs: InputFlags::new(0),
+                input_queue: VecDeque::new(),
+                input_sequence: 0,
                 has_moved: false,
+                ability_held: false,
                 collidable: true,
                 absorption_factor: kb_factors.0,
                 push_factor: kb_factors.1,
@@ -433,7 +718,7 @@ impl Entity {
                 level: 1,
                 score: 0,
                 invincible: false,
-                stat_investments: [0; _],
+                stat_investments,
                 available_stat_points: 0,
                 upgrades: TankUpgrades::default(),
                 opacity: 1.0,
@@ -442,9 +727,9 @@ impl Entity {
                 notifications: vec![],
                 killer: NonZeroU32::new(0),
                 entity_type: projectile_type,
-                body_identity: get_body_base_identity(),
-                turret_identity: get_turret_base_identity(),
-                orb_identity: get_orb_basic_identity(),
+                body_identity: BodyIdentity::try_from(BodyIdentityIds::Base).unwrap(),
+                turret_identity,
+                orb_identity: OrbIdentity::try_from(OrbIdentityIds::Basic).unwrap(),
                 turret_idx,
                 owners: Some(owners),
                 clan_id: None,
@@ -452,26 +737,97 @@ impl Entity {
                 owned_entities: vec![],
                 radius,
                 typing: false,
-                messages: Vec::with_capacity(3)
+                messages: Vec::with_capacity(3),
+                quests: QuestProgress::default(),
+                events: vec![],
+                kills: 0,
+                clan_kills: 0,
+                spectate_mode: SpectateMode::default(),
+                spectated: None
             },
             stats: StatsComponent {
                 health: penetration, max_health: penetration, alive: AliveState::Alive, 
                 last_damage_tick: 0, damage_reduction: 0.25,
                 regen_per_tick: 0.0,
                 damage_per_tick: damage,
-                reload: 0.0,
+                reload,
                 speed: speed.0,
-                lifetime
+                lifetime,
+                detonation,
+                effects,
+                afflicted: HashMap::new(),
+                status_effects: StatusEffects::default(),
+                ability: AbilityState::default(),
+                energy: 0.0,
+                max_energy: 0.0,
+                last_energy_tick: 0,
+                inventory: Inventory::default(),
+                inventory_changed: false,
+                damage_taken: HashMap::new(),
+                ledger: DamageLedger::default()
             },
             time: TimeComponent {
                 ticks: 0,
                 spawn_tick: 0,
                 last_damage_tick: 0,
-                last_switch_tick: 0
+                last_switch_tick: 0,
+                last_distress_tick: None
             },
             connection: ConnectionComponent {
-                outgoing_packets: vec![]
+                outgoing_packets: vec![],
+                resume_token: None,
+                disconnected_at: None,
+                profile: None,
+                clan_names: HashMap::new()
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::game::status::StatusEffectKind;
+    use super::*;
+
+    #[test]
+    fn effects_are_inflicted_once_per_cooldown() {
+        let mut source = Entity::from_id(1);
+        source.stats.effects = vec![StatusEffect { kind: StatusEffectKind::Poison, duration: 5.0, magnitude: 1.0 }];
+        let mut victim = Entity::from_id(2);
+
+        source.inflict_effects(&mut victim);
+        source.time.ticks += 1;
+        source.inflict_effects(&mut victim);
+        assert_eq!(victim.stats.status_effects.get(StatusEffectKind::Poison).unwrap().stacks, 1);
+
+        source.time.ticks += EFFECT_COOLDOWN;
+        source.inflict_effects(&mut victim);
+        assert_eq!(victim.stats.status_effects.get(StatusEffectKind::Poison).unwrap().stacks, 2);
+    }
+
+    #[test]
+    fn clanmates_collide_without_damage() {
+        let entities = EntityDataStructure::new();
+        let tank = |id, x| {
+            let mut entity = Entity::from_id(id);
+            entity.stats.alive = AliveState::Alive;
+            (entity.stats.health, entity.stats.max_health, entity.stats.damage_per_tick) = (100.0, 100.0, 10.0);
+            entity.display.clan_id = Some(1);
+            entity.physics.position = Vector2D::new(x, 0.0);
+
+            entity
+        };
+
+        let (mut a, mut b) = (tank(1, 0.0), tank(2, 10.0));
+        assert!(a.should_collide(&b));
+
+        a.collide(&entities, &mut b, false);
+        assert_eq!((a.stats.health, b.stats.health), (100.0, 100.0));
+        assert!(a.physics.velocity.x < 0.0 && b.physics.velocity.x > 0.0);
+
+        let (mut a, mut b) = (tank(1, 0.0), tank(2, 10.0));
+        a.collide(&entities, &mut b, true);
+        assert!(a.stats.health < 100.0 && b.stats.health < 100.0);
+    }
+}
+
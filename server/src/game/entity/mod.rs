@@ -2,4 +2,5 @@ pub(crate) mod base;
 pub(crate) mod tank;
 pub(crate) mod projectile;
 pub(crate) mod ai;
-pub(crate) mod orb;
\ No newline at end of file
+pub(crate) mod orb;
+pub(crate) mod spectator;
\ No newline at end of file
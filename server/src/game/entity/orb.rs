@@ -1,4 +1,4 @@
-use shared::{connection::packets::CensusProperties, game::orb::OrbIdentityIds, normalize_angle, rand, utils::{codec::BinaryCodec, consts::ARENA_SIZE, vec2::Vector2D}};
+use shared::{connection::packets::CensusProperties, game::orb::OrbIdentityIds, normalize_angle, rand, utils::{codec::BinaryCodec, vec2::Vector2D}};
 use rand::Rng;
 use strum::IntoEnumIterator;
 
@@ -6,16 +6,16 @@ use crate::game::state::EntityDataStructure;
 use super::base::{AliveState, Entity, EntityConstruction};
 
 impl Entity {
-    pub fn tick_orb(&mut self, _: &EntityDataStructure) -> Vec<EntityConstruction> {
+    pub fn tick_orb(&mut self, _: &EntityDataStructure, arena_size: f32) -> Vec<EntityConstruction> {
         let constructions = vec![];
 
-        self.base_tick();
+        self.base_tick(arena_size);
 
         if self.display.orb_identity.id == OrbIdentityIds::Flickering {
             self.display.opacity = rand!(0.0, 0.7) + 0.3;
         }
 
-        let (soft_border_left, soft_border_right) = (ARENA_SIZE / 7.0, 6.0 * ARENA_SIZE / 7.0);
+        let (soft_border_left, soft_border_right) = (arena_size / 7.0, 6.0 * arena_size / 7.0);
         if self.physics.position.x < soft_border_left
             || self.physics.position.x > soft_border_right
         {
@@ -28,7 +28,7 @@ impl Entity {
 
         self.physics.angle = normalize_angle!(self.physics.angle + 0.01 * self.display.orb_identity.angular_speed);
         self.physics.velocity += Vector2D::from_polar(
-            0.03 * self.display.orb_identity.linear_speed,
+            0.03 * self.display.orb_identity.linear_speed * self.stats.status_effects.speed_multiplier(),
             self.physics.angle,
         );
 
@@ -46,7 +46,7 @@ impl Entity {
             return;
         }
 
-        codec.encode_varuint(9);
+        codec.encode_varuint(10);
         for property in CensusProperties::iter() {
             codec.encode_varuint(property.clone() as u64);
 
@@ -68,6 +68,7 @@ impl Entity {
                 CensusProperties::Identity => {
                     codec.encode_varuint(self.display.orb_identity.id as u64);
                 },
+                CensusProperties::StatusEffects => self.encode_status_effects(codec),
                 _ => codec.backspace(),
             }
         }
@@ -1,15 +1,15 @@
-use shared::{connection::packets::{CensusProperties, Inputs}, utils::{codec::BinaryCodec, vec2::Vector2D}};
+use shared::{connection::packets::{CensusProperties, Inputs}, game::entity::EntityType, utils::{codec::BinaryCodec, vec2::Vector2D}};
 use strum::IntoEnumIterator;
 
-use crate::game::state::EntityDataStructure;
+use crate::{game::{physics::shg::SpatialHashGrid, state::EntityDataStructure}, server::{FPS, MINION_DISTANCE}};
 
 use super::{ai::AIState, base::{AliveState, Entity, EntityConstruction}};
 
 impl Entity {
-    pub fn tick_projectile(&mut self, entities: &EntityDataStructure) -> Vec<EntityConstruction> {
-        let constructions = vec![];
+    pub fn tick_projectile(&mut self, entities: &EntityDataStructure, shg: &SpatialHashGrid, friendly_fire: bool, arena_size: f32) -> Vec<EntityConstruction> {
+        let mut constructions = vec![];
 
-        self.base_tick();
+        self.base_tick(arena_size);
 
         if let Some(ai) = &mut self.physics.ai
             && let Some(owner) = entities.get(&self.display.owners.unwrap().deep)
@@ -29,7 +29,15 @@ impl Entity {
 
             if ai.state != AIState::Idle {
                 self.physics.angle = ai.movement.angle() + if is_repelling { std::f32::consts::PI } else { 0.0 };
-                let push_vec = ai.movement * ai.speed * if is_repelling { -1.0 } else { 1.0 };
+                let mut push_vec = ai.movement * ai.speed * if is_repelling { -1.0 } else { 1.0 };
+
+                // Minions hold their distance from targets so they can shoot them.
+                if self.display.entity_type == EntityType::Minion
+                    && matches!(ai.state, AIState::Active(_))
+                    && ai.aim.distance(self.physics.position) < MINION_DISTANCE
+                {
+                    push_vec *= -0.5;
+                }
 
                 self.physics.additional_velocity.lerp_towards(push_vec, 0.15);
             } else {
@@ -61,9 +69,83 @@ impl Entity {
             }
         }
 
+        if self.stats.alive == AliveState::Alive {
+            match self.display.entity_type {
+                EntityType::Missile => constructions.append(&mut self.handle_shooting(true, false)),
+                EntityType::Minion => {
+                    let has_target = self.physics.ai.as_ref().is_some_and(|ai| ai.state != AIState::Idle);
+                    constructions.append(&mut self.handle_shooting(has_target, false));
+                },
+                EntityType::Mine => self.tick_mine(entities, shg, friendly_fire),
+                _ => ()
+            }
+        }
+
         constructions
     }
 
+    /// Sets the mine off once it is armed and an enemy tank comes close,
+    /// damaging everything within its blast radius.
+    fn tick_mine(&mut self, entities: &EntityDataStructure, shg: &SpatialHashGrid, friendly_fire: bool) {
+        let Some(detonation) = self.stats.detonation.clone() else { return; };
+        if self.time.ticks < (detonation.arm_time * FPS as f32) as u64 {
+            return;
+        }
+
+        let nearby = shg.query_radius(self.id, self.physics.position, detonation.blast_radius.max(detonation.trigger_radius));
+        let triggered = nearby.iter()
+            .filter_map(|id| entities.get(id))
+            .any(|entity| {
+                let entity = entity.borrow();
+                entity.display.entity_type == EntityType::Player
+                    && entity.stats.alive == AliveState::Alive
+                    && self.should_collide(&entity)
+                    && self.can_damage(&entity, friendly_fire)
+                    && entity.physics.position.distance(self.physics.position) - entity.display.radius <= detonation.trigger_radius
+            });
+
+        if !triggered {
+            return;
+        }
+
+        for id in nearby {
+            let Some(entity) = entities.get(&id) else { continue; };
+            let mut entity = entity.borrow_mut();
+
+            if entity.stats.alive != AliveState::Alive
+                || !matches!(entity.display.entity_type, EntityType::Player | EntityType::Orb)
+                || !self.should_collide(&entity)
+            {
+                continue;
+            }
+
+            let distance = (entity.physics.position.distance(self.physics.position) - entity.display.radius).max(0.0);
+            if distance > detonation.blast_radius {
+                continue;
+            }
+
+            let falloff = 1.0 - 0.5 * (distance / detonation.blast_radius);
+            if self.can_damage(&entity, friendly_fire) {
+                let damage = self.stats.damage_per_tick * detonation.damage * falloff
+                    * entity.stats.damage_reduction * entity.get_damage_taken_multiplier();
+                entity.take_damage(damage, Some(self.get_deep_owner()));
+                self.inflict_effects(&mut entity);
+            }
+
+            let knockback = Vector2D::from_polar(
+                self.physics.push_factor * entity.physics.absorption_factor * falloff,
+                (entity.physics.position - self.physics.position).angle()
+            );
+            entity.physics.velocity += knockback;
+
+            if entity.stats.health <= 0.0 {
+                self.credit_kill(entities, &mut entity);
+            }
+        }
+
+        self.stats.alive = AliveState::Dead;
+    }
+
     pub fn take_projectile_census(&self, codec: &mut BinaryCodec) {
         codec.encode_varuint(self.id as u64);
         codec.encode_varuint(self.display.entity_type as u64);        
@@ -73,7 +155,7 @@ impl Entity {
             return;
         }
 
-        codec.encode_varuint(9);
+        codec.encode_varuint(10);
         for property in CensusProperties::iter() {
             codec.encode_varuint(property.clone() as u64);
 
@@ -98,6 +180,7 @@ impl Entity {
                     codec.encode_varuint(self.display.turret_idx as u64);
                 },
                 CensusProperties::Ticks => codec.encode_varuint(self.time.ticks),
+                CensusProperties::Identity => codec.encode_varuint(self.display.turret_identity.id as u64),
                 _ => codec.backspace(),
             }
         }
@@ -0,0 +1,140 @@
+use shared::{game::{entity::{EntityType, SpectateMode}, movement::{accelerate, get_movement_direction}}, utils::vec2::Vector2D};
+
+use crate::{game::state::EntityDataStructure, server::SPECTATOR_SPEED};
+
+use super::base::{AliveState, Entity};
+
+fn is_alive_player(entity: &Entity) -> bool {
+    entity.display.entity_type == EntityType::Player && entity.stats.alive == AliveState::Alive
+}
+
+/// Every alive player, from the highest score to the lowest.
+pub fn get_leaderboard(entities: &EntityDataStructure) -> Vec<u32> {
+    let mut players: Vec<(u32, usize)> = entities.values()
+        .filter_map(|entity| entity.try_borrow().ok())
+        .filter(|entity| is_alive_player(entity))
+        .map(|entity| (entity.id, entity.display.score))
+        .collect();
+
+    players.sort_by_key(|&(id, score)| (std::cmp::Reverse(score), id));
+    players.into_iter().map(|(id, _)| id).collect()
+}
+
+/// The alive player with the highest score.
+pub fn get_leader(entities: &EntityDataStructure) -> Option<u32> {
+    get_leaderboard(entities).first().copied()
+}
+
+/// The alive player after `current` in order of ID, or before it if `forward` is false.
+/// Wraps around once the end is reached.
+pub fn cycle_players(entities: &EntityDataStructure, current: Option<u32>, forward: bool) -> Option<u32> {
+    let mut players: Vec<u32> = entities.values()
+        .filter_map(|entity| entity.try_borrow().ok())
+        .filter(|entity| is_alive_player(entity))
+        .map(|entity| entity.id)
+        .collect();
+
+    players.sort_unstable();
+
+    let next = if forward {
+        players.iter().find(|&&id| current.is_none_or(|current| id > current)).or(players.first())
+    } else {
+        players.iter().rev().find(|&&id| current.is_none_or(|current| id < current)).or(players.last())
+    };
+
+    next.copied()
+}
+
+impl Entity {
+    /// Moves a spectator's camera onto the player it follows, or along its inputs while panning freely.
+    pub fn tick_spectator(&mut self, entities: &EntityDataStructure) {
+        let is_following = |id: u32| entities.get(&id)
+            .and_then(|entity| entity.try_borrow().ok())
+            .is_some_and(|entity| is_alive_player(&entity));
+
+        self.display.spectated = match self.display.spectate_mode {
+            SpectateMode::Leader => get_leader(entities),
+            SpectateMode::Player if self.display.spectated.is_some_and(is_following) => self.display.spectated,
+            SpectateMode::Player => cycle_players(entities, self.display.spectated, true),
+            SpectateMode::Free => None
+        };
+
+        let target = self.display.spectated
+            .and_then(|id| entities.get(&id))
+            .and_then(|entity| entity.try_borrow().ok());
+
+        if let Some(target) = target {
+            self.physics.position = target.physics.position;
+            self.physics.velocity = target.physics.velocity;
+            self.display.fov = target.display.fov;
+        } else if self.display.spectate_mode == SpectateMode::Free {
+            accelerate(&mut self.physics.velocity, get_movement_direction(self.physics.inputs), SPECTATOR_SPEED);
+        } else {
+            self.physics.velocity = Vector2D::ZERO;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use super::*;
+
+    /// Alive players with the given IDs and scores, along with a dead player 99.
+    fn players(scores: &[(u32, usize)]) -> EntityDataStructure {
+        let mut entities: EntityDataStructure = scores.iter()
+            .map(|&(id, score)| {
+                let mut entity = Entity::from_id(id);
+                entity.stats.alive = AliveState::Alive;
+                entity.display.score = score;
+
+                (id, RefCell::new(entity))
+            })
+            .collect();
+
+        let mut dead = Entity::from_id(99);
+        dead.display.score = usize::MAX;
+        entities.insert(99, RefCell::new(dead));
+
+        entities
+    }
+
+    #[test]
+    fn leaders_have_the_highest_score() {
+        let entities = players(&[(1, 100), (2, 300), (3, 200)]);
+        assert_eq!(get_leaderboard(&entities), vec![2, 3, 1]);
+        assert_eq!(get_leader(&entities), Some(2));
+
+        assert_eq!(get_leader(&players(&[])), None);
+    }
+
+    #[test]
+    fn cycling_wraps_around() {
+        let entities = players(&[(1, 0), (4, 0), (7, 0)]);
+
+        assert_eq!(cycle_players(&entities, None, true), Some(1));
+        assert_eq!(cycle_players(&entities, Some(1), true), Some(4));
+        assert_eq!(cycle_players(&entities, Some(7), true), Some(1));
+        assert_eq!(cycle_players(&entities, Some(5), true), Some(7));
+
+        assert_eq!(cycle_players(&entities, None, false), Some(7));
+        assert_eq!(cycle_players(&entities, Some(4), false), Some(1));
+        assert_eq!(cycle_players(&entities, Some(1), false), Some(7));
+    }
+
+    #[test]
+    fn empty_arenas_have_no_one_to_follow() {
+        let entities = players(&[]);
+        assert_eq!(cycle_players(&entities, None, true), None);
+        assert_eq!(cycle_players(&entities, Some(3), false), None);
+
+        let mut spectator = Entity::from_id(100);
+        spectator.display.spectate_mode = SpectateMode::Player;
+        spectator.display.spectated = Some(3);
+        spectator.physics.velocity = Vector2D::new(1.0, 1.0);
+
+        spectator.tick_spectator(&entities);
+        assert_eq!(spectator.display.spectated, None);
+        assert_eq!(spectator.physics.velocity, Vector2D::ZERO);
+    }
+}
@@ -1,15 +1,21 @@
-use shared::{connection::packets::{CensusProperties, Inputs}, game::{body::BodyIdentity, entity::{get_min_score_from_level, EntityType, Ownership, UpgradeStats, BASE_TANK_RADIUS, FICTITIOUS_TANK_RADIUS}, turret::{TurretIdentityIds, TurretStructure}}, rand, utils::{codec::BinaryCodec, consts::{MAX_LEVEL, SCREEN_HEIGHT, SCREEN_WIDTH}, vec2::Vector2D}};
+use shared::{connection::packets::{CensusProperties, Inputs}, game::{ability::{Ability, AbilityKind, AbilityState}, gear::{Gear, GearKind, GearRarity}, definitions::get_definitions, quests::QuestEvent, balance::{get_body_damage, get_fov, get_max_health, get_projectile_base_speed, get_projectile_damage, get_projectile_lifetime, get_projectile_penetration, get_projectile_push_factor, get_projectile_speeds, get_ramming_multiplier, get_regen_per_tick, get_reload, get_tank_radius}, body::BodyIdentity, entity::{get_min_score_from_level, EntityType, GameplayEvent, InputFlags, Notification, Ownership, UpgradeStats, BASE_TANK_RADIUS, FICTITIOUS_TANK_RADIUS}, movement::{accelerate, get_movement_direction, get_tank_speed}, turret::{AutoTurretTargets, TurretIdentity, TurretIdentityIds, TurretStructure}}, rand, utils::{codec::BinaryCodec, color::Color, consts::{MAX_LEVEL, SCREEN_HEIGHT, SCREEN_WIDTH}, vec2::Vector2D}};
 use strum::{EnumCount, IntoEnumIterator};
 use rand::Rng;
-use crate::{connection::packets, game::{clans::ClanState, physics::shg::SpatialHashGrid, state::EntityDataStructure}, server::{MESSAGE_EXPIRY, SPAWN_INVINCIBILITY_TIME, SWITCH_TIME_THRESHOLD}};
+use crate::{connection::packets, game::state::{EntityDataStructure, GameState}, server::{AUTO_TURRET_AIM_TOLERANCE, MAX_BUFFERED_INPUTS, MESSAGE_EXPIRY, MIN_VISIBLE_OPACITY, SMOKE_FADE_RATE, SPAWN_INVINCIBILITY_TIME, SWITCH_TIME_THRESHOLD}};
 
 use super::{ai::AI, base::{AliveState, Entity, EntityConstruction}};
 
 impl Entity {
-    pub fn tick_tank(&mut self, entities: &EntityDataStructure, shg: &SpatialHashGrid, clans: &ClanState) -> Vec<EntityConstruction> {
+    pub fn tick_tank(&mut self, state: &GameState) -> Vec<EntityConstruction> {
+        let (entities, shg) = (&state.entities, &state.shg);
         let mut constructions = vec![];
 
-        self.base_tick();
+        if let Some((sequence, inputs)) = self.physics.input_queue.pop_front() {
+            self.physics.inputs = inputs;
+            self.physics.input_sequence = sequence;
+        }
+
+        self.base_tick(state.world.arena_size);
 
         let (screen_width, screen_height) = (SCREEN_WIDTH / self.display.fov / 0.9, SCREEN_HEIGHT / self.display.fov / 0.9);
         let screen_top_left = self.physics.position - Vector2D::new(screen_width / 2.0, screen_height / 2.0);
@@ -19,7 +25,7 @@ impl Entity {
             .into_iter()
             .filter(|entity| {
                 if let Some(entity) = entities.get(entity) {
-                    if entity.borrow().display.opacity <= 0.05 {
+                    if entity.borrow().display.opacity <= MIN_VISIBLE_OPACITY {
                         return false;
                     }
 
@@ -36,22 +42,18 @@ impl Entity {
             .collect();
 
         if self.stats.alive == AliveState::Alive {
-            let mut movement = Vector2D::ZERO;
+            let movement = get_movement_direction(self.physics.inputs);
 
             for flag in Inputs::iter() {
                 if self.physics.inputs.is_set(flag) {
                     match flag {
-                        Inputs::Up => movement.y -= 1.0,
-                        Inputs::Down => movement.y += 1.0,
-                        Inputs::Left => movement.x -= 1.0,
-                        Inputs::Right => movement.x += 1.0,
                         Inputs::LevelUp => self.display.score = get_min_score_from_level(self.display.level + 1).max(self.display.score),
                         Inputs::Switch => {
                             if (self.time.ticks - self.time.last_switch_tick) > SWITCH_TIME_THRESHOLD {
                                 let new_id: TurretIdentityIds = ((self.display.turret_identity.id as usize % TurretIdentityIds::COUNT) + 1)
                                     .try_into().unwrap();
 
-                                self.display.turret_identity = new_id.try_into().unwrap();
+                                self.display.turret_identity = TurretStructure::try_from(new_id).unwrap().with_body(&self.display.body_identity);
                                 self.time.last_switch_tick = self.time.ticks;
 
                                 self.display.upgrades.turret.clear();
@@ -66,23 +68,41 @@ impl Entity {
                 self.physics.has_moved = true;
             }
 
-            constructions.append(&mut self.handle_shooting());
+            self.tick_ability(entities, movement);
+
+            // Stunned tanks can neither aim nor shoot.
+            if !self.stats.status_effects.is_stunned() {
+                self.aim_auto_turrets(entities, state.friendly_fire);
+                constructions.append(&mut self.handle_shooting(self.is_shooting(), self.is_repelling()));
+            }
     
-            movement.set_magnitude(self.stats.speed);
-            self.physics.velocity += movement;
+            let acceleration = self.get_acceleration();
+            accelerate(&mut self.physics.velocity, movement, acceleration);
     
             self.update_display();
+        } else if self.stats.alive == AliveState::Spectating {
+            self.tick_spectator(entities);
         } else if let Some(killer) = self.display.killer && let Some(entity) = entities.get(&killer.into()) {
             let entity = entity.borrow_mut();
             self.physics.position = entity.physics.position;
         }
 
-        let update_packet = packets::form_update_packet(self, entities, clans);
+        // Nothing is sent to a disconnected player, whose packets would only pile up.
+        if self.connection.disconnected_at.is_some() {
+            return constructions;
+        }
+
+        let update_packet = packets::form_update_packet(self, entities, &state.clan_state, state.ticks, state.timestamp);
         let notifications_packet = packets::form_notification_packet(self);
 
         self.connection.outgoing_packets.push(update_packet);
         self.connection.outgoing_packets.push(notifications_packet);
 
+        if self.stats.inventory_changed {
+            self.stats.inventory_changed = false;
+            self.connection.outgoing_packets.push(packets::form_inventory_packet(self, entities, &state.trade_state));
+        }
+
         constructions
     }
 
@@ -94,108 +114,312 @@ impl Entity {
         self.physics.inputs.is_set(Inputs::Repel)
     }
 
-    fn handle_shooting(&mut self) -> Vec<EntityConstruction> {
+    /// The ability granted by the turret, or else the body.
+    pub fn get_ability(&self) -> Option<Ability> {
+        self.display.turret_identity.ability.or(self.display.body_identity.ability)
+    }
+
+    /// Restores ability charges and uses the ability when its input is pressed.
+    fn tick_ability(&mut self, entities: &EntityDataStructure, movement: Vector2D) {
+        let pressed = self.physics.inputs.is_set(Inputs::Ability);
+        let just_pressed = pressed && !self.physics.ability_held;
+        self.physics.ability_held = pressed;
+
+        let Some(ability) = self.get_ability() else {
+            self.stats.ability = AbilityState::default();
+            return;
+        };
+
+        if self.stats.ability.kind != Some(ability.kind) {
+            self.stats.ability = AbilityState::new(&ability);
+        }
+
+        self.stats.ability.tick(&ability);
+
+        if !just_pressed
+            || self.stats.status_effects.is_stunned()
+            || self.stats.energy < ability.energy_cost
+            || !self.stats.ability.try_use(&ability)
+        {
+            return;
+        }
+
+        self.spend_energy(ability.energy_cost);
+
+        match ability.kind {
+            AbilityKind::Dash => {
+                let direction = if movement == Vector2D::ZERO { self.physics.angle } else { movement.angle() };
+                self.physics.velocity += Vector2D::from_polar(ability.magnitude, direction);
+            },
+            AbilityKind::DroneRecall => {
+                // Only the tank itself is borrowed while it ticks, so every drone it owns can be.
+                for id in self.display.owned_entities.iter().filter(|&&id| id != self.id) {
+                    let Some(entity) = entities.get(id) else { continue; };
+                    let mut entity = entity.borrow_mut();
+
+                    if entity.display.entity_type.is_drone() {
+                        entity.physics.position = self.physics.position + Vector2D::from_polar(self.display.radius, rand!(0.0, std::f32::consts::TAU));
+                        entity.physics.velocity = Vector2D::ZERO;
+                    }
+                }
+            },
+            AbilityKind::Smoke => self.display.opacity = 0.0,
+            AbilityKind::Shield => ()
+        }
+    }
+
+    /// Gives the tank a random piece of gear with the given chance, if it has room for it.
+    pub fn roll_gear_drop(&mut self, chance: f32) {
+        if rand!(0.0, 1.0) >= chance {
+            return;
+        }
+
+        let gear = Gear {
+            kind: GearKind::try_from(rand!(0, GearKind::COUNT - 1) as u8).unwrap(),
+            rarity: GearRarity::from_roll(rand!(0.0, 1.0))
+        };
+
+        let (message, color) = if self.stats.inventory.add(gear) {
+            self.stats.inventory_changed = true;
+            (format!("You found {} {}!", gear.rarity.name(), gear.kind.name()), gear.rarity.color())
+        } else {
+            (format!("Your inventory is full, so the {} {} was lost.", gear.rarity.name(), gear.kind.name()), Color::RED)
+        };
+
+        self.display.notifications.push(Notification {
+            message,
+            color,
+            lifetime: 150,
+            ..Default::default()
+        });
+    }
+
+    /// Progresses the tank's quests, rewarding it for every quest completed.
+    pub fn handle_quest_event(&mut self, event: QuestEvent) {
+        let definitions = get_definitions();
+
+        for quest in self.display.quests.handle_event(&definitions.quests, event) {
+            self.display.score += quest.score_reward;
+
+            let mut message = format!("{}: {}", if quest.achievement { "Achievement unlocked" } else { "Quest complete" }, quest.name);
+            if quest.score_reward > 0 {
+                message += &format!(" (+{} score)", quest.score_reward);
+            }
+
+            self.display.notifications.push(Notification {
+                message,
+                color: Color::MATERIAL_YELLOW,
+                lifetime: 150,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// How fast the tank accelerates this tick, slowed by its status effects.
+    pub fn get_acceleration(&self) -> f32 {
+        self.stats.speed * self.stats.status_effects.speed_multiplier()
+    }
+
+    /// Takes energy from the tank, delaying its regeneration.
+    /// Returns whether the tank had enough energy to spend.
+    pub fn spend_energy(&mut self, amount: f32) -> bool {
+        if amount <= 0.0 {
+            return true;
+        }
+
+        if self.stats.energy < amount {
+            return false;
+        }
+
+        self.stats.energy -= amount;
+        self.stats.last_energy_tick = self.time.ticks;
+        true
+    }
+
+    /// Fires every turret which is ready. Also used by missiles and minions for the turrets they carry,
+    /// in which case their projectiles are owned by both the carrier and its tank.
+    pub fn handle_shooting(&mut self, is_shooting: bool, is_repelling: bool) -> Vec<EntityConstruction> {
         let mut constructions = vec![];
-        let (is_shooting, is_repelling) = (self.is_shooting(), self.is_repelling());
+        let owners = match self.display.owners {
+            Some(owners) => Ownership::new(self.id, owners.deep),
+            None => Ownership::from_single_owner(self.id)
+        };
 
-        for (i, turret) in self.display.turret_identity.turrets.iter_mut().enumerate() {
+        // The turrets are taken out while firing so the tank can spend energy on them.
+        let mut turrets = std::mem::take(&mut self.display.turret_identity.turrets);
+        for (i, turret) in turrets.iter_mut().enumerate() {
             let projectile_type = turret.projectile_identity.projectile_type;
 
-            if !turret.can_fire(self.stats.reload, turret.force_shoot || if turret.repel_to_shoot {
+            if !turret.can_fire(self.stats.reload, turret.force_shoot || if turret.auto.is_some() {
+                turret.auto_target.is_some()
+            } else if turret.repel_to_shoot {
                 is_repelling
             } else {
                 is_shooting
             }) { continue; }
 
             if turret.max_projectiles != -1 && turret.projectiles_spawned >= turret.max_projectiles { continue; }
-            turret.projectiles_spawned += 1;
 
-            let base_speed = (20.0 
-                + (1.5 * self.display.stat_investments[UpgradeStats::ProjectileSpeed as usize] as f32))
-                * turret.projectile_identity.speed;
+            let projectile = &turret.projectile_identity;
+            let base_speed = get_projectile_base_speed(projectile, self.display.stat_investments[UpgradeStats::ProjectileSpeed as usize])
+                * self.stats.inventory.get_multiplier(GearKind::Accelerator);
+
+            let (Ok(speed), Ok(lifetime)) = (get_projectile_speeds(projectile, base_speed, rand!(0.0, 1.0)), get_projectile_lifetime(projectile)) else {
+                continue;
+            };
 
-            let initial_speed = base_speed + 20.0 - rand!(0.0, 1.0) * turret.projectile_identity.scatter_rate;
+            // Only tanks hold energy, so turrets carried by projectiles fire freely.
+            if self.display.entity_type == EntityType::Player && !self.spend_energy(turret.energy_cost) { continue; }
 
-            let penetration = (1.5 * self.display.stat_investments[UpgradeStats::ProjectilePenetration as usize] as f32 + 2.0)
-                * turret.projectile_identity.health;
-            let damage = (7.0 + self.display.stat_investments[UpgradeStats::ProjectileDamage as usize] as f32 * 3.0)
-                * turret.projectile_identity.damage;
+            turret.projectiles_spawned += 1;
+            self.display.events.push(GameplayEvent::TurretFired { id: self.id, turret: i });
+
+            let projectile = &turret.projectile_identity;
+            let penetration = get_projectile_penetration(projectile, self.display.stat_investments[UpgradeStats::ProjectilePenetration as usize]);
+            let damage = get_projectile_damage(projectile, self.display.stat_investments[UpgradeStats::ProjectileDamage as usize])
+                * self.stats.inventory.get_multiplier(GearKind::Barrel);
 
             let radius = (turret.width / 2.0) * (self.display.radius / FICTITIOUS_TANK_RADIUS) * turret.projectile_identity.size_factor;
 
-            let projectile_angle = self.physics.angle + turret.angle + (std::f32::consts::PI / 180.0)
+            let projectile_angle = self.physics.angle + turret.get_angle() + (std::f32::consts::PI / 180.0)
                 * turret.projectile_identity.scatter_rate
                 * (rand!(0.0, 1.0) - 0.5)
                 * 5.0;
 
-            let push_factor = ((7.0 / 3.0) + self.display.stat_investments[UpgradeStats::ProjectileDamage as usize] as f32) 
-                * turret.projectile_identity.damage 
-                * turret.projectile_identity.absorption_factor;
+            let push_factor = get_projectile_push_factor(projectile, self.display.stat_investments[UpgradeStats::ProjectileDamage as usize]);
 
             let mut position = self.physics.position;
             position += Vector2D::from_polar(turret.length * (FICTITIOUS_TANK_RADIUS / BASE_TANK_RADIUS) * (self.display.radius / BASE_TANK_RADIUS), projectile_angle);
-            position -= *Vector2D::from_polar(turret.y_offset * (self.display.radius / BASE_TANK_RADIUS), projectile_angle).swap();
-            position += Vector2D::from_polar(turret.x_offset, projectile_angle);
+            if turret.auto.is_some() {
+                position += Vector2D::from_polar(turret.x_offset * (self.display.radius / FICTITIOUS_TANK_RADIUS), self.physics.angle + turret.angle);
+            } else {
+                position -= *Vector2D::from_polar(turret.y_offset * (self.display.radius / BASE_TANK_RADIUS), projectile_angle).swap();
+                position += Vector2D::from_polar(turret.x_offset, projectile_angle);
+            }
 
             self.physics.velocity -= Vector2D::from_polar(turret.recoil, projectile_angle);
 
             constructions.push(EntityConstruction::ProjectileConstruction {
                 angle: projectile_angle,
-                speed: match projectile_type {
-                    EntityType::Bullet => (base_speed, initial_speed),
-                    EntityType::Drone => (base_speed, base_speed / 2.0),
-                    EntityType::Trap => (0.0, base_speed),
-                    _ => unreachable!("invalid projectile type")
-                },
+                speed,
                 penetration,
                 damage,
                 radius,
                 position,
-                lifetime: if turret.projectile_identity.lifetime == -1.0 { -1 } else {
-                    match projectile_type {
-                        EntityType::Bullet => (turret.projectile_identity.lifetime * 72.0) as isize,
-                        EntityType::Drone => (turret.projectile_identity.lifetime * 88.0) as isize,
-                        EntityType::Trap => (turret.projectile_identity.lifetime * 75.0) as isize,
-                        _ => unreachable!("invalid projectile type")
-                    }
-                },
-                owners: Ownership::from_single_owner(self.id),
+                lifetime,
+                owners,
                 turret_idx: i as isize,
                 kb_factors: (turret.projectile_identity.absorption_factor, push_factor),
                 ai: match projectile_type {
-                    EntityType::Bullet => None,
-                    EntityType::Drone => Some(AI::new(Ownership::from_single_owner(self.id), false, turret.mouse_controllable)),
-                    EntityType::Trap => None,
+                    EntityType::Drone | EntityType::Minion => Some(AI::new(owners, false, turret.mouse_controllable)),
+                    EntityType::Bullet | EntityType::Trap | EntityType::Missile | EntityType::Mine => None,
                     _ => unreachable!("invalid projectile type")
                 },
                 bound_to_walls: match projectile_type {
-                    EntityType::Drone => true,
-                    EntityType::Bullet | EntityType::Trap => false,
+                    EntityType::Drone | EntityType::Minion => true,
+                    EntityType::Bullet | EntityType::Trap | EntityType::Missile | EntityType::Mine => false,
                     _ => unreachable!("invalid projectile type")
                 },
-                projectile_type
+                projectile_type,
+                turret_identity: TurretStructure {
+                    id: self.display.turret_identity.id,
+                    turrets: turret.projectile_identity.turrets.clone(),
+                    ..Default::default()
+                },
+                stat_investments: self.display.stat_investments,
+                reload: self.stats.reload,
+                detonation: turret.projectile_identity.detonation.clone(),
+                effects: turret.projectile_identity.effects.clone()
             });
         }
 
+        self.display.turret_identity.turrets = turrets;
         constructions
     }
 
+    /// Turns every automatic turret towards the nearest enemy within its arc and range,
+    /// or back to its resting angle if there is none.
+    fn aim_auto_turrets(&mut self, entities: &EntityDataStructure, friendly_fire: bool) {
+        if self.display.turret_identity.turrets.iter().all(|turret| turret.auto.is_none()) {
+            return;
+        }
+
+        let targets: Vec<(u32, Vector2D, EntityType)> = self.display.surroundings.iter()
+            .filter_map(|id| entities.get(id))
+            .map(|entity| entity.borrow())
+            .filter(|entity| entity.stats.alive == AliveState::Alive
+                && entity.display.opacity > MIN_VISIBLE_OPACITY
+                && matches!(entity.display.entity_type, EntityType::Player | EntityType::Orb)
+                && !entity.display.owners.is_some_and(|owners| owners.has_owner(self.id))
+                && self.can_damage(entity, friendly_fire)
+            )
+            .map(|entity| (entity.id, entity.physics.position, entity.display.entity_type))
+            .collect();
+
+        let (position, angle, radius) = (self.physics.position, self.physics.angle, self.display.radius);
+
+        for turret in self.display.turret_identity.turrets.iter_mut() {
+            let Some(auto) = &turret.auto else { continue; };
+
+            let mount = position + Vector2D::from_polar(turret.x_offset * (radius / FICTITIOUS_TANK_RADIUS), angle + turret.angle);
+            let rest_angle = angle + turret.angle;
+
+            let nearest = |entity_type: Option<EntityType>| targets.iter()
+                .filter(|&&(_, target, target_type)| {
+                    entity_type.is_none_or(|entity_type| entity_type == target_type)
+                        && target.distance(mount) <= auto.range
+                        && (auto.arc >= std::f32::consts::TAU || angle_difference((target - mount).angle(), rest_angle).abs() <= auto.arc / 2.0)
+                })
+                .min_by(|a, b| a.1.distance_squared(mount).total_cmp(&b.1.distance_squared(mount)));
+
+            let target = match auto.targets {
+                AutoTurretTargets::Any => nearest(None),
+                AutoTurretTargets::PreferPlayers => nearest(Some(EntityType::Player)).or_else(|| nearest(Some(EntityType::Orb))),
+                AutoTurretTargets::Players => nearest(Some(EntityType::Player)),
+                AutoTurretTargets::Orbs => nearest(Some(EntityType::Orb))
+            };
+
+            let desired_angle = match target {
+                Some(&(_, target, _)) => (target - mount).angle() - angle,
+                None => turret.angle
+            };
+
+            let current_angle = turret.get_angle();
+            let mut new_angle = current_angle + angle_difference(desired_angle, current_angle).clamp(-auto.turn_rate, auto.turn_rate);
+            if auto.arc < std::f32::consts::TAU {
+                new_angle = turret.angle + angle_difference(new_angle, turret.angle).clamp(-auto.arc / 2.0, auto.arc / 2.0);
+            }
+
+            turret.auto_target = target
+                .filter(|_| angle_difference(desired_angle, new_angle).abs() <= AUTO_TURRET_AIM_TOLERANCE)
+                .map(|&(id, _, _)| id);
+            turret.auto_angle = Some(angle_difference(new_angle, 0.0));
+        }
+    }
+
     fn update_display(&mut self) {
         // Invisibility
         let true_shooting = self.is_shooting() && !matches!(self.display.turret_identity.id, TurretIdentityIds::Manager);
 
-        if self.physics.velocity.is_zero(5.0) && !true_shooting {
-            if self.display.turret_identity.invisibility_rate != -1.0 && self.display.opacity > 0.0 {
-                self.display.opacity -= self.display.turret_identity.invisibility_rate;
+        let invisibility_rate = self.display.turret_identity.invisibility_rate.max(self.display.body_identity.invisibility_rate);
+        let smoked = self.stats.ability.is_active() && self.stats.ability.kind == Some(AbilityKind::Smoke);
+
+        if smoked {
+            self.display.opacity = 0.0;
+        } else if self.physics.velocity.is_zero(5.0) && !true_shooting && invisibility_rate != -1.0 {
+            if self.display.opacity > 0.0 {
+                self.display.opacity -= invisibility_rate;
                 self.display.opacity = self.display.opacity.clamp(0.0, 1.0);
             }
-        } else if self.display.turret_identity.invisibility_rate != -1.0 && self.display.opacity < 1.0 {
-            self.display.opacity += self.display.turret_identity.invisibility_rate;
+        } else if self.display.opacity < 1.0 {
+            // Tanks which cannot go invisible may still have been hidden by smoke.
+            self.display.opacity += if invisibility_rate == -1.0 { SMOKE_FADE_RATE } else { invisibility_rate };
             self.display.opacity = self.display.opacity.clamp(0.0, 1.0);
         }
 
         // Invincibility
-        self.display.invincible = !self.physics.has_moved && (self.time.ticks - self.time.spawn_tick) <= SPAWN_INVINCIBILITY_TIME;
+        self.display.invincible = self.connection.disconnected_at.is_some()
+            || !self.physics.has_moved && (self.time.ticks - self.time.spawn_tick) <= SPAWN_INVINCIBILITY_TIME;
 
         // Upgrade Level
         let mut new_level = self.display.level;
@@ -206,21 +430,26 @@ impl Entity {
                 self.display.available_stat_points += 1;
             }
         }
-        self.update_level(new_level);
+        if new_level != self.display.level {
+            self.update_level(new_level);
+            self.display.events.push(GameplayEvent::LevelUp(self.display.level));
+            self.handle_quest_event(QuestEvent::LevelReached(self.display.level));
+        }
         self.check_for_upgrades();
 
         // Health Regen
-        self.stats.regen_per_tick = (self.stats.max_health 
-            * 4.0 
-            * (self.display.stat_investments[UpgradeStats::HealthRegen as usize] as f32)
-            + self.stats.max_health
-        ) / 25000.0;
+        self.stats.regen_per_tick = get_regen_per_tick(
+            self.stats.max_health,
+            self.display.stat_investments[UpgradeStats::HealthRegen as usize]
+        ) * self.stats.inventory.get_multiplier(GearKind::Medkit);
 
         // Max Health
         let prev_health_ratio = self.stats.health / self.stats.max_health;
-        self.stats.max_health = self.display.body_identity.max_health 
-            + (2.0 * (self.display.level - 1) as f32)
-            + (20.0 * self.display.stat_investments[UpgradeStats::MaxHealth as usize] as f32);
+        self.stats.max_health = get_max_health(
+            self.display.body_identity.max_health,
+            self.display.level,
+            self.display.stat_investments[UpgradeStats::MaxHealth as usize]
+        ) * self.stats.inventory.get_multiplier(GearKind::Plating);
         self.stats.health = if self.display.invincible {
             self.stats.max_health
         } else {
@@ -228,19 +457,37 @@ impl Entity {
         };
 
         // Body Damage
-        self.stats.damage_per_tick = (self.display.stat_investments[UpgradeStats::BodyDamage as usize] as f32
-            * 6.0 + 20.0) * self.display.body_identity.body_damage;
+        self.stats.damage_per_tick = get_body_damage(
+            self.display.body_identity.body_damage,
+            self.display.stat_investments[UpgradeStats::BodyDamage as usize]
+        ) * self.stats.inventory.get_multiplier(GearKind::Spikes) * get_ramming_multiplier(
+            self.display.body_identity.ramming_factor,
+            self.physics.velocity.magnitude(),
+            self.stats.speed
+        );
 
         // Reload
-        self.stats.reload = 15.0 * 0.914_f32.powf(self.display.stat_investments[UpgradeStats::Reload as usize] as f32);
+        self.stats.reload = get_reload(self.display.stat_investments[UpgradeStats::Reload as usize])
+            * self.stats.status_effects.reload_multiplier()
+            / self.stats.inventory.get_multiplier(GearKind::Autoloader);
 
         // Movement Speed
-        self.stats.speed = self.display.body_identity.speed * 2.55 *
-            1.07_f32.powf(self.display.stat_investments[UpgradeStats::MovementSpeed as usize] as f32)
-            / 1.015_f32.powf((self.display.level - 1) as f32);
+        self.stats.speed = get_tank_speed(
+            self.display.body_identity.speed,
+            self.display.stat_investments[UpgradeStats::MovementSpeed as usize],
+            self.display.level
+        ) * self.stats.inventory.get_multiplier(GearKind::Thrusters);
+
+        // Energy
+        let energy = self.display.body_identity.energy;
+        self.stats.max_energy = energy.max_energy * self.stats.inventory.get_multiplier(GearKind::Capacitor);
+        self.stats.energy = (self.stats.energy + energy.get_regen_per_tick(
+            self.time.ticks - self.stats.last_energy_tick,
+            self.physics.velocity.is_zero(5.0)
+        )).clamp(0.0, self.stats.max_energy);
 
         // FoV
-        self.display.fov = (0.55 * self.display.turret_identity.fov) / 1.01f32.powf((self.display.level as f32 - 1.0) / 2.0);
+        self.display.fov = get_fov(self.display.turret_identity.fov, self.display.level);
 
         // Messages
         while !self.display.messages.is_empty() {
@@ -263,7 +510,7 @@ impl Entity {
         }
 
         if is_self {
-            codec.encode_varuint(17);
+            codec.encode_varuint(23);
             for property in CensusProperties::iter() {
                 codec.encode_varuint(property.clone() as u64);
     
@@ -316,11 +563,24 @@ impl Entity {
                         }
                     },
                     CensusProperties::Clan => codec.encode_varint(self.display.clan_id.map(|n| n as i64).unwrap_or(-1)),
+                    CensusProperties::InputSequence => codec.encode_varuint(self.physics.input_sequence as u64),
+                    CensusProperties::Ability => self.encode_ability(codec),
+                    CensusProperties::Energy => {
+                        codec.encode_f32(self.stats.energy);
+                        codec.encode_f32(self.stats.max_energy);
+                    },
+                    CensusProperties::Movement => {
+                        codec.encode_f32(self.physics.additional_velocity.x);
+                        codec.encode_f32(self.physics.additional_velocity.y);
+                        codec.encode_f32(self.get_acceleration());
+                    },
+                    CensusProperties::TurretAngles => self.encode_turret_angles(codec),
+                    CensusProperties::StatusEffects => self.encode_status_effects(codec),
                     _ => codec.backspace(),
                 }
             }
         } else {
-            codec.encode_varuint(14);
+            codec.encode_varuint(16);
             for property in CensusProperties::iter() {
                 codec.encode_varuint(property.clone() as u64);
     
@@ -355,19 +615,89 @@ impl Entity {
                         }
                     },
                     CensusProperties::Clan => codec.encode_varint(self.display.clan_id.map(|n| n as i64).unwrap_or(-1)),
+                    CensusProperties::TurretAngles => self.encode_turret_angles(codec),
+                    CensusProperties::StatusEffects => self.encode_status_effects(codec),
                     _ => codec.backspace(),
                 }
             }
         }
     }
 
+    /// Encodes the tank's ability along with its charges and cooldown.
+    fn encode_ability(&self, codec: &mut BinaryCodec) {
+        let Some(ability) = self.get_ability() else {
+            codec.encode_bool(false);
+            return;
+        };
+
+        codec.encode_bool(true);
+        codec.encode_varuint(ability.kind as u64);
+        codec.encode_varuint(self.stats.ability.charges as u64);
+        codec.encode_varuint(ability.charges as u64);
+        codec.encode_f32(self.stats.ability.get_cooldown(&ability));
+        codec.encode_bool(self.stats.ability.is_active());
+    }
+
+    /// Encodes the absolute angle of every automatic turret.
+    fn encode_turret_angles(&self, codec: &mut BinaryCodec) {
+        let turrets: Vec<(usize, &TurretIdentity)> = self.display.turret_identity.turrets.iter()
+            .enumerate()
+            .filter(|(_, turret)| turret.auto.is_some())
+            .collect();
+
+        codec.encode_varuint(turrets.len() as u64);
+        for (i, turret) in turrets {
+            codec.encode_varuint(i as u64);
+            codec.encode_f32(self.physics.angle + turret.get_angle());
+        }
+    }
+
+    /// Queues the inputs numbered `sequence`, which must come after every input already received.
+    /// Duplicated or reordered inputs are dropped, so the acknowledged sequence never goes backwards.
+    pub fn queue_input(&mut self, sequence: u32, inputs: InputFlags) -> bool {
+        let latest = self.physics.input_queue.back().map_or(self.physics.input_sequence, |&(sequence, _)| sequence);
+        if sequence <= latest {
+            return false;
+        }
+
+        self.physics.input_queue.push_back((sequence, inputs));
+        while self.physics.input_queue.len() > MAX_BUFFERED_INPUTS {
+            self.physics.input_queue.pop_front();
+        }
+
+        true
+    }
+
+    /// Replaces the tank's identities with the current definitions, remounting the body's turrets
+    /// and keeping the shooting state of any turrets which still exist.
+    pub fn refresh_identities(&mut self) {
+        if let Ok(body_identity) = BodyIdentity::try_from(self.display.body_identity.id) {
+            self.display.body_identity = body_identity;
+        }
+
+        if let Ok(turret_identity) = TurretStructure::try_from(self.display.turret_identity.id) {
+            let mut turret_identity = turret_identity.with_body(&self.display.body_identity);
+            for (new, old) in turret_identity.turrets.iter_mut().zip(self.display.turret_identity.turrets.iter()) {
+                new.reload_time = old.reload_time;
+                new.cycle_position = old.cycle_position;
+                new.projectiles_spawned = old.projectiles_spawned;
+                new.auto_angle = old.auto_angle;
+            }
+
+            self.display.turret_identity = turret_identity;
+        }
+    }
+
     fn check_for_upgrades(&mut self) {
+        let mut available = false;
+
         for &upgrade in self.display.body_identity.upgrades.iter() {
             let upgrade_identity: BodyIdentity = upgrade.try_into().unwrap();
             if self.display.level >= upgrade_identity.level_requirement
                 && !self.display.upgrades.body.contains(&upgrade)
             {
                 self.display.upgrades.body.push(upgrade);
+                available = true;
             }
         }
 
@@ -377,8 +707,13 @@ impl Entity {
                 && !self.display.upgrades.turret.contains(&upgrade)
             {
                 self.display.upgrades.turret.push(upgrade);
+                available = true;
             }
         }
+
+        if available {
+            self.display.events.push(GameplayEvent::UpgradeAvailable);
+        }
     }
 
     pub fn update_level(&mut self, level: usize) {
@@ -387,6 +722,106 @@ impl Entity {
         }
 
         self.display.level = level;
-        self.display.radius = BASE_TANK_RADIUS * 1.007_f32.powf((self.display.level - 1) as f32);
+        self.display.radius = get_tank_radius(self.display.level);
+    }
+}
+
+/// The shortest signed angle from `b` to `a`, in `[-PI, PI)`.
+fn angle_difference(a: f32, b: f32) -> f32 {
+    (a - b + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::{game::{balance::{TankBalance, TankBuild}, body::BodyIdentityIds, entity::MAX_STAT_INVESTMENT}, utils::consts::TICK_RATE};
+    use super::*;
+
+    fn spawn(build: &TankBuild) -> Entity {
+        let mut entity = Entity::from_id(1);
+        entity.display.body_identity = BodyIdentity::try_from(build.body).unwrap();
+        entity.display.turret_identity = TurretStructure::try_from(build.turret).unwrap().with_body(&entity.display.body_identity);
+        entity.display.stat_investments = build.stat_investments;
+        entity.update_level(build.level);
+        entity.update_display();
+
+        entity
+    }
+
+    /// Fires every turret for a minute, expiring projectiles once their lifetime runs out.
+    fn count_shots(entity: &mut Entity) -> Vec<usize> {
+        let mut shots = vec![0; entity.display.turret_identity.turrets.len()];
+        let mut expiries: Vec<(isize, usize)> = vec![];
+
+        for tick in 0..TICK_RATE as isize * 60 {
+            expiries.retain(|&(expiry, i)| {
+                if expiry == tick {
+                    entity.display.turret_identity.turrets[i].projectiles_spawned -= 1;
+                }
+
+                expiry != tick
+            });
+
+            for construction in entity.handle_shooting(true, false) {
+                let EntityConstruction::ProjectileConstruction { turret_idx, lifetime, .. } = construction;
+                shots[turret_idx as usize] += 1;
+
+                if lifetime != -1 {
+                    expiries.push((tick + lifetime, turret_idx as usize));
+                }
+            }
+        }
+
+        shots
+    }
+
+    #[test]
+    fn inputs_are_only_queued_in_order() {
+        let mut entity = Entity::from_id(1);
+        assert!(entity.queue_input(2, InputFlags::new(1)));
+        assert!(!entity.queue_input(2, InputFlags::new(2)));
+        assert!(!entity.queue_input(1, InputFlags::new(2)));
+        assert!(entity.queue_input(3, InputFlags::new(3)));
+
+        entity.physics.input_queue.pop_front();
+        entity.physics.input_queue.pop_front();
+        entity.physics.input_sequence = 3;
+        assert!(!entity.queue_input(3, InputFlags::new(3)));
+
+        for sequence in 4..4 + MAX_BUFFERED_INPUTS as u32 + 2 {
+            entity.queue_input(sequence, InputFlags::new(0));
+        }
+
+        assert_eq!(entity.physics.input_queue.len(), MAX_BUFFERED_INPUTS);
+        assert_eq!(entity.physics.input_queue.front().unwrap().0, 6);
+    }
+
+    #[test]
+    fn balance_matches_the_server() {
+        let turrets = [TurretIdentityIds::Mono, TurretIdentityIds::Couplet, TurretIdentityIds::Sniper, TurretIdentityIds::Spawner, TurretIdentityIds::Minelayer];
+        let builds = [[0; UpgradeStats::COUNT], [MAX_STAT_INVESTMENT; UpgradeStats::COUNT]];
+
+        for turret in turrets {
+            for stat_investments in builds {
+                let build = TankBuild { turret, body: BodyIdentityIds::Base, level: 30, stat_investments };
+                let balance = TankBalance::compute(&build).unwrap();
+                let mut entity = spawn(&build);
+
+                assert_eq!(balance.max_health, entity.stats.max_health, "{:?}", turret);
+                assert_eq!(balance.fov, entity.display.fov, "{:?}", turret);
+
+                let shots = count_shots(&mut entity);
+                for (i, turret_balance) in balance.turrets.iter().enumerate() {
+                    assert_eq!(turret_balance.shots_per_second, shots[i] as f32 / 60.0, "{:?} turret {}", turret, i);
+                }
+
+                let mut entity = spawn(&build);
+                for construction in std::iter::repeat_with(|| entity.handle_shooting(true, false)).take(100).flatten() {
+                    let EntityConstruction::ProjectileConstruction { turret_idx, damage, penetration, .. } = construction;
+                    assert_eq!(balance.turrets[turret_idx as usize].damage, damage, "{:?}", turret);
+                    assert_eq!(balance.turrets[turret_idx as usize].penetration, penetration, "{:?}", turret);
+                }
+            }
+        }
     }
-}
\ No newline at end of file
+}
+
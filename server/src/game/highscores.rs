@@ -0,0 +1,55 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use shared::game::{highscores::{HighScoreTables, RunHistory}, profile::Run};
+
+use super::store::{Document, JsonStore};
+
+/// The environment variable naming the file finished runs are kept in.
+pub const RUNS_FILE_VAR: &str = "RUNS_FILE";
+const DEFAULT_RUNS_FILE: &str = "runs.json";
+
+/// The current time, in seconds since the UNIX epoch.
+pub fn get_unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+impl Document for RunHistory {
+    fn parse(json: &str) -> Result<RunHistory, String> {
+        RunHistory::parse(json).map_err(|error| error.to_string())
+    }
+
+    fn to_json(&self) -> Result<String, String> {
+        RunHistory::to_json(self).map_err(|error| error.to_string())
+    }
+}
+
+/// Keeps the runs which make up the high-score tables in a file which survives restarts.
+pub struct RunStore {
+    store: JsonStore<RunHistory>
+}
+
+impl RunStore {
+    pub fn from_env() -> RunStore {
+        RunStore { store: JsonStore::from_env("runs", RUNS_FILE_VAR, DEFAULT_RUNS_FILE) }
+    }
+
+    /// Records a run, which is only written to disk if it changed the history.
+    pub fn record(&mut self, run: Run) {
+        let now = get_unix_time();
+        self.store.update(|history| history.record(run, now));
+    }
+
+    pub fn get_tables(&self) -> HighScoreTables<'_> {
+        self.store.get().get_tables(get_unix_time())
+    }
+
+    pub fn tick(&mut self) {
+        self.store.tick();
+    }
+
+    pub fn shutdown(&mut self) {
+        self.store.shutdown();
+    }
+}
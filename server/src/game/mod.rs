@@ -1,4 +1,9 @@
 pub(crate) mod state;
 pub(crate) mod clans;
+pub(crate) mod trades;
 pub(crate) mod entity;
-pub(crate) mod physics;
\ No newline at end of file
+pub(crate) mod physics;
+pub(crate) mod definitions;
+pub(crate) mod profiles;
+pub(crate) mod highscores;
+pub(crate) mod store;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use shared::game::profile::{Profile, ProfileBook, Run};
+
+use super::store::{Document, JsonStore};
+
+/// The environment variable naming the file profiles are kept in.
+pub const PROFILES_FILE_VAR: &str = "PROFILES_FILE";
+const DEFAULT_PROFILES_FILE: &str = "profiles.json";
+
+impl Document for ProfileBook {
+    fn parse(json: &str) -> Result<ProfileBook, String> {
+        ProfileBook::parse(json).map_err(|error| error.to_string())
+    }
+
+    fn to_json(&self) -> Result<String, String> {
+        ProfileBook::to_json(self).map_err(|error| error.to_string())
+    }
+}
+
+/// Keeps the profile of every player in a file which survives restarts.
+pub struct ProfileStore {
+    store: JsonStore<ProfileBook>
+}
+
+impl ProfileStore {
+    pub fn from_env() -> ProfileStore {
+        ProfileStore { store: JsonStore::from_env("profiles", PROFILES_FILE_VAR, DEFAULT_PROFILES_FILE) }
+    }
+
+    pub fn get(&self, token: u64) -> Option<&Profile> {
+        self.store.get().get(token)
+    }
+
+    /// The token of the profile `token` belongs to, or a fresh token if there is none.
+    /// The profile is only created once a run is recorded under the token.
+    pub fn get_token(&self, token: u64) -> u64 {
+        if self.get(token).is_some() {
+            return token;
+        }
+
+        loop {
+            let token = rand::random::<u64>();
+            if token != 0 && self.get(token).is_none() {
+                return token;
+            }
+        }
+    }
+
+    /// Records a finished run, creating the profile of `token` if it has none.
+    pub fn record(&mut self, token: u64, run: &Run) {
+        self.store.update(|book| {
+            book.create(token);
+
+            let Some(profile) = book.get_mut(token) else { return false; };
+            profile.record(run);
+            true
+        });
+    }
+
+    pub fn tick(&mut self) {
+        self.store.tick();
+    }
+
+    pub fn shutdown(&mut self) {
+        self.store.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::game::{body::BodyIdentityIds, turret::TurretIdentityIds};
+    use super::*;
+
+    #[test]
+    fn profiles_are_only_created_by_finished_runs() {
+        let path = std::env::temp_dir().join(format!("profiles-{}.json", rand::random::<u32>()));
+        let mut profiles = ProfileStore { store: JsonStore::open("profiles", path.clone()) };
+
+        let token = profiles.get_token(42);
+        assert!(token != 0 && profiles.get(token).is_none());
+        assert_ne!(profiles.get_token(42), token);
+
+        profiles.record(token, &Run {
+            name: "Player".to_string(),
+            body: BodyIdentityIds::Base,
+            turret: TurretIdentityIds::Mono,
+            score: 1000,
+            kills: 1,
+            duration: 60,
+            achievements: vec![]
+        });
+
+        assert_eq!(profiles.get_token(token), token);
+        assert_eq!(profiles.get(token).unwrap().runs, 1);
+
+        profiles.shutdown();
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,20 +1,26 @@
 use std::{cell::{RefCell, RefMut}, collections::HashMap};
-use shared::{game::{entity::{ClanInformation, EntityType}, orb::*}, rand, utils::{consts::ARENA_SIZE, vec2::Vector2D}};
+use shared::{game::{definitions::get_definitions, profile::Run, entity::{ClanInformation, EntityType, GameplayEvent, Marker, MarkerKind}, ability::AbilityState, gear::Inventory, ledger::DamageLedger, orb::*, status::StatusEffects, theme::{ALLY_FILL, LEADER_ARROW_COLOR, ORB_RADIANT_FILL}, world::{get_arena_size, get_orb_count, WorldInfo}}, rand, utils::{color::Color, vec2::Vector2D}};
 use rand::Rng;
-use crate::game::entity::base::AliveState;
+use crate::{connection::packets, game::entity::base::AliveState, server::{FPS, MAX_OBJECTIVE_MARKERS, OBJECTIVE_MARKER_RANGE, RESUME_GRACE_PERIOD}};
 
-use super::{clans::ClanState, entity::base::{DisplayComponent, Entity, PhysicsComponent, StatsComponent}, physics::{collision::detect_collision, shg::SpatialHashGrid}};
+use super::{clans::ClanState, trades::TradeState, definitions::DefinitionWatcher, highscores::RunStore, profiles::ProfileStore, entity::base::{DisplayComponent, Entity, PhysicsComponent, StatsComponent}, physics::{collision::detect_collision, shg::SpatialHashGrid}};
 
 pub type EntityDataStructure = HashMap<u32, RefCell<Entity>>;
 
 pub struct GameServer {
     states: Vec<GameState>,
+    definitions: DefinitionWatcher,
+    pub profiles: ProfileStore,
+    pub runs: RunStore
 }
 
 impl GameServer {
     pub fn new(states: Vec<GameState>) -> GameServer {
         GameServer {
-            states
+            states,
+            definitions: DefinitionWatcher::from_env(),
+            profiles: ProfileStore::from_env(),
+            runs: RunStore::from_env()
         }
     }
 
@@ -24,9 +30,36 @@ impl GameServer {
     }
 
     pub fn tick(&mut self) {
+        let reloaded = self.definitions.tick();
+
         for state in self.states.iter_mut() {
+            if reloaded {
+                state.refresh_identities();
+            }
+
             state.tick();
+
+            for (id, token, run) in std::mem::take(&mut state.finished_runs) {
+                if let Some(token) = token {
+                    self.profiles.record(token, &run);
+
+                    if let Some(mut entity) = state.get_entity(id) && let Some(profile) = self.profiles.get(token) {
+                        entity.connection.outgoing_packets.push(packets::form_profile_packet(token, profile));
+                    }
+                }
+
+                self.runs.record(run);
+            }
         }
+
+        self.profiles.tick();
+        self.runs.tick();
+    }
+
+    /// Saves everything which must survive a restart.
+    pub fn shutdown(&mut self) {
+        self.profiles.shutdown();
+        self.runs.shutdown();
     }
 }
 
@@ -35,9 +68,20 @@ pub struct GameState {
     pub entities: EntityDataStructure,
     pub shg: SpatialHashGrid,
     pub clan_state: ClanState,
+    pub trade_state: TradeState,
     pub counter: u32,
     pub mspt: f32,
-    pub desired_orb_count: usize
+    /// The number of orbs in the arena at its largest.
+    pub desired_orb_count: usize,
+    pub world: WorldInfo,
+    /// Whether clanmates can damage each other.
+    pub friendly_fire: bool,
+    /// The number of ticks elapsed since the arena started.
+    pub ticks: u64,
+    /// The time at which the current tick started, in milliseconds since the UNIX epoch.
+    pub timestamp: f64,
+    /// Runs which ended this tick, with the player who made them and the profile they count towards.
+    pub finished_runs: Vec<(u32, Option<u64>, Run)>
 }
 
 impl GameState {
@@ -47,7 +91,8 @@ impl GameState {
     }
 
     pub fn get_random_position(&self) -> Vector2D {
-        let (mut position, mut iterations) = (Vector2D::new(rand!(0.0, ARENA_SIZE), rand!(0.0, ARENA_SIZE)), -1);
+        let arena_size = self.world.arena_size;
+        let (mut position, mut iterations) = (Vector2D::new(rand!(0.0, arena_size), rand!(0.0, arena_size)), -1);
         let (collision_detection, collision_radius) = (300.0, 50.0);
 
         while iterations >= 20 {
@@ -59,7 +104,7 @@ impl GameState {
             for nearby_entity in nearby_entities {
                 let entity = self.entities[&nearby_entity].borrow();
                 if (collision_radius + entity.display.radius) - position.distance(entity.physics.position) > 5.0 {
-                    position = Vector2D::new(rand!(0.0, ARENA_SIZE), rand!(0.0, ARENA_SIZE));
+                    position = Vector2D::new(rand!(0.0, arena_size), rand!(0.0, arena_size));
                     is_position_valid = false;
                     break;
                 }
@@ -73,6 +118,51 @@ impl GameState {
         position
     }
 
+    /// The objectives pointed out to the player `id`: the leader, the nearest radiant orbs,
+    /// its clanmates and their calls for help.
+    pub fn get_markers(&self, id: u32, leader: Option<u32>) -> Vec<Marker> {
+        let mut markers = vec![];
+
+        if let Some(leader) = leader && leader != id && let Some(entity) = self.entities.get(&leader) {
+            markers.push(Marker { kind: MarkerKind::Leader, id: leader, position: entity.borrow().physics.position, color: LEADER_ARROW_COLOR });
+        }
+
+        if let Some(entity) = self.entities.get(&id) {
+            let position = entity.borrow().physics.position;
+
+            let mut objectives: Vec<_> = self.entities.values()
+                .map(|orb| orb.borrow())
+                .filter(|orb| orb.display.entity_type == EntityType::Orb && orb.display.orb_identity.id == OrbIdentityIds::Radiant)
+                .filter(|orb| orb.stats.alive == AliveState::Alive && orb.physics.position.distance(position) <= OBJECTIVE_MARKER_RANGE)
+                .map(|orb| (orb.id, orb.physics.position))
+                .collect();
+
+            objectives.sort_by(|(_, a), (_, b)| a.distance(position).total_cmp(&b.distance(position)));
+            markers.extend(objectives.into_iter().take(MAX_OBJECTIVE_MARKERS).map(|(id, position)| {
+                Marker { kind: MarkerKind::Objective, id, position, color: ORB_RADIANT_FILL }
+            }));
+        }
+
+        let Some(clan) = self.clan_state.get_membership(id).filter(|clan| clan.members.contains(&id)) else {
+            return markers;
+        };
+
+        for &member in clan.members.iter().filter(|&&member| member != id) {
+            let Some(entity) = self.entities.get(&member) else { continue; };
+            let entity = entity.borrow();
+
+            if entity.stats.alive == AliveState::Alive {
+                markers.push(Marker { kind: MarkerKind::Clanmate, id: member, position: entity.physics.position, color: ALLY_FILL });
+            }
+        }
+
+        for call in self.clan_state.distress_calls.iter().filter(|call| call.clan == clan.id && call.caller != id) {
+            markers.push(Marker { kind: MarkerKind::Distress, id: call.caller, position: call.position, color: Color::RED });
+        }
+
+        markers
+    }
+
     pub fn insert_entity(&mut self, entity: Entity) {
         self.shg.insert(entity.id, entity.physics.position, entity.display.radius);
         self.entities.insert(entity.id, RefCell::new(entity));
@@ -136,10 +226,80 @@ impl GameState {
         }
     }
 
+    /// Ends the run of player `id`, recording it in the high scores and the player's profile.
+    pub fn finish_run(&mut self, id: u32) {
+        let Some(entity) = self.entities.get(&id) else { return; };
+        let entity = entity.borrow();
+        if entity.display.entity_type != EntityType::Player {
+            return;
+        }
+
+        let definitions = get_definitions();
+        let achievements = entity.display.quests.get_completed().iter()
+            .filter(|&completed| definitions.quests.iter().any(|quest| quest.achievement && quest.id == *completed))
+            .cloned()
+            .collect();
+
+        let run = Run {
+            name: entity.display.name.clone(),
+            body: entity.display.body_identity.id,
+            turret: entity.display.turret_identity.id,
+            score: entity.display.score,
+            kills: entity.display.kills,
+            duration: (entity.time.ticks - entity.time.spawn_tick) / FPS,
+            achievements
+        };
+
+        self.finished_runs.push((id, entity.connection.profile, run));
+    }
+
+    /// Removes a player and everything it owns from the game entirely,
+    /// ending its run if it was still alive.
+    pub fn remove_player(&mut self, id: u32) {
+        let Some(entity) = self.entities.get(&id) else { return; };
+        if entity.borrow().stats.alive == AliveState::Alive {
+            self.finish_run(id);
+        }
+
+        self.delete_entity(id);
+        self.shg.delete(id);
+        self.entities.remove(&id);
+    }
+
+    /// The living player which the resume token `token` belongs to, whether or not it is still connected.
+    pub fn find_session(&self, token: u64) -> Option<u32> {
+        self.entities.values()
+            .map(|entity| entity.borrow())
+            .find(|entity| entity.stats.alive == AliveState::Alive && entity.connection.resume_token == Some(token))
+            .map(|entity| entity.id)
+    }
+
+    /// Removes every disconnected player who has not come back within the grace period.
+    fn remove_abandoned_players(&mut self) {
+        let abandoned: Vec<u32> = self.entities.values()
+            .map(|entity| entity.borrow())
+            .filter(|entity| entity.connection.disconnected_at.is_some_and(|tick| self.ticks - tick >= RESUME_GRACE_PERIOD))
+            .map(|entity| entity.id)
+            .collect();
+
+        for id in abandoned {
+            self.remove_player(id);
+        }
+    }
+
+    /// Reapplies the current definitions to every tank.
+    pub fn refresh_identities(&mut self) {
+        for entity in self.entities.values() {
+            let mut entity = entity.borrow_mut();
+            if entity.display.entity_type == EntityType::Player {
+                entity.refresh_identities();
+            }
+        }
+    }
+
     fn spawn_random_shape(&mut self) {
         let position = self.get_random_position();
-        let center_size = ARENA_SIZE * 0.15;
-        let (center_min, center_max) = ((ARENA_SIZE - center_size) / 2.0, (ARENA_SIZE + center_size) / 2.0);
+        let (center_min, center_max) = self.world.get_center_region();
         
         let is_center = position.x >= center_min && position.x <= center_max && position.y >= center_min && position.y <= center_max;
         let identity = if is_center {
@@ -174,7 +334,19 @@ impl GameState {
                 damage_per_tick: identity.body_damage,
                 reload: 0.0,
                 speed: 0.0,
-                lifetime: -1
+                lifetime: -1,
+                detonation: None,
+                effects: vec![],
+                afflicted: HashMap::new(),
+                status_effects: StatusEffects::default(),
+                ability: AbilityState::default(),
+                energy: 0.0,
+                max_energy: 0.0,
+                last_energy_tick: 0,
+                inventory: Inventory::default(),
+                inventory_changed: false,
+                damage_taken: HashMap::new(),
+                ledger: DamageLedger::default()
             },
             display: DisplayComponent {
                 entity_type: EntityType::Orb,
@@ -189,19 +361,111 @@ impl GameState {
         self.insert_entity(entity);
     }
 
+    /// Resizes the arena to fit the players in it, telling every player whenever the world changes.
+    fn update_world(&mut self) {
+        let players = self.entities.values()
+            .filter(|entity| {
+                let entity = entity.borrow();
+                entity.display.entity_type == EntityType::Player && entity.stats.alive != AliveState::Spectating
+            })
+            .count();
+
+        let arena_size = get_arena_size(players);
+        if arena_size == self.world.arena_size {
+            return;
+        }
+
+        self.world.arena_size = arena_size;
+        for entity in self.entities.values() {
+            let mut entity = entity.borrow_mut();
+            if entity.display.entity_type == EntityType::Player {
+                entity.connection.outgoing_packets.push(packets::form_world_info_packet(&self.world));
+            }
+        }
+    }
+
+    /// Spawns or removes orbs until the arena holds as many as it should.
+    /// Orbs left outside the arena when it shrinks are removed first, then those furthest from the center.
+    fn balance_orbs(&mut self) {
+        let arena_size = self.world.arena_size;
+        let center = Vector2D::new(arena_size / 2.0, arena_size / 2.0);
+
+        let mut orbs: Vec<(u32, Vector2D)> = self.entities.iter()
+            .map(|(&id, entity)| (id, entity.borrow()))
+            .filter(|(_, entity)| entity.display.entity_type == EntityType::Orb)
+            .map(|(id, entity)| (id, entity.physics.position))
+            .collect();
+
+        let is_inside = |position: Vector2D| (0.0..=arena_size).contains(&position.x) && (0.0..=arena_size).contains(&position.y);
+        for &(id, _) in orbs.iter().filter(|&&(_, position)| !is_inside(position)) {
+            self.delete_entity(id);
+        }
+
+        orbs.retain(|&(_, position)| is_inside(position));
+
+        let desired_orb_count = get_orb_count(self.desired_orb_count, arena_size);
+        if orbs.len() < desired_orb_count {
+            for _ in orbs.len()..desired_orb_count {
+                self.spawn_random_shape();
+            }
+        } else if orbs.len() > desired_orb_count {
+            orbs.sort_by(|a, b| b.1.distance_squared(center).total_cmp(&a.1.distance_squared(center)));
+            for &(id, _) in orbs.iter().take(orbs.len() - desired_orb_count) {
+                self.delete_entity(id);
+            }
+        }
+    }
+
+    /// Sends every player the events which happened to it this tick, along with
+    /// the shots fired around it and the hits it landed, then clears every event queue.
+    fn send_gameplay_events(&mut self) {
+        for entity in self.entities.values() {
+            let mut entity = entity.borrow_mut();
+            if entity.display.entity_type != EntityType::Player {
+                continue;
+            }
+
+            let mut events = entity.display.events.clone();
+            for id in entity.display.surroundings.iter() {
+                let Some(surrounding) = self.entities.get(id) else { continue; };
+                let Ok(surrounding) = surrounding.try_borrow() else { continue; };
+
+                events.extend(surrounding.display.events.iter().filter(|event| match event {
+                    GameplayEvent::TurretFired { .. } | GameplayEvent::ObjectiveCaptured { .. } => true,
+                    GameplayEvent::Hit { attacker, .. } => *attacker == Some(entity.id),
+                    _ => false
+                }));
+            }
+
+            if !events.is_empty() {
+                entity.connection.outgoing_packets.push(packets::form_events_packet(&events));
+            }
+        }
+
+        for entity in self.entities.values() {
+            entity.borrow_mut().display.events.clear();
+        }
+    }
+
     pub fn tick(&mut self) {
         let mspt = std::time::Instant::now();
-        let mut current_orb_count = 0;
 
-        self.clan_state.tick(&self.entities);
+        self.ticks += 1;
+        self.timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+            .unwrap_or_default();
+
+        self.remove_abandoned_players();
+        self.clan_state.tick(&self.entities, self.world.mode);
+        self.trade_state.tick(&self.entities);
+        self.update_world();
+        self.balance_orbs();
 
         let ids: Vec<_> = self.entities.keys().copied().collect();
 
         for id in ids {
             Entity::tick(self, id);
-            if let Some(entity) = self.get_entity(id) && entity.display.entity_type == EntityType::Orb {
-                current_orb_count += 1;
-            }
         }
 
         let ids: Vec<_> = self.entities.keys().copied().collect();
@@ -217,7 +481,7 @@ impl GameState {
                 let is_colliding = detect_collision(&this, &other);
 
                 if resolve_collision && is_colliding {
-                    this.collide(&self.entities, &mut other);
+                    this.collide(&self.entities, &mut other, self.friendly_fire);
                 } else if this.display.entity_type.is_drone() && other.display.entity_type.is_drone() && is_colliding {
                     let angle = (this.physics.position - other.physics.position).angle();
 
@@ -233,11 +497,83 @@ impl GameState {
             }
         }
 
-        let displacement = self.desired_orb_count - current_orb_count;
-        for _ in 0..displacement {
-            self.spawn_random_shape();
-        }
+        self.send_gameplay_events();
 
         self.mspt = mspt.elapsed().as_millis_f32();
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::utils::consts::ARENA_SIZE;
+    use crate::server::CELL_SIZE;
+    use super::*;
+
+    fn orb_positions(state: &GameState) -> Vec<(u32, Vector2D)> {
+        state.entities.iter()
+            .map(|(&id, entity)| (id, entity.borrow().physics.position))
+            .collect()
+    }
+
+    #[test]
+    fn shrinking_arenas_remove_the_orbs_left_outside() {
+        let mut state = GameState { desired_orb_count: 10, shg: SpatialHashGrid::new(256, CELL_SIZE), ..GameState::default() };
+        state.world.arena_size = ARENA_SIZE;
+
+        state.balance_orbs();
+        assert_eq!(state.entities.len(), 10);
+
+        let arena_size = ARENA_SIZE / 2.0;
+        let kept = *state.entities.keys().next().unwrap();
+        state.entities[&kept].borrow_mut().physics.position = Vector2D::new(arena_size / 2.0, arena_size / 2.0);
+
+        state.world.arena_size = arena_size;
+        state.balance_orbs();
+
+        let orbs = orb_positions(&state);
+        assert_eq!(orbs.len(), get_orb_count(10, arena_size));
+        assert!(orbs.iter().any(|&(id, _)| id == kept));
+        assert!(orbs.iter().all(|&(_, position)| position.x <= arena_size && position.y <= arena_size));
+    }
+
+    #[test]
+    fn only_the_nearest_radiant_orbs_are_marked() {
+        let mut state = GameState::default();
+        state.entities.insert(1, RefCell::new(Entity::from_id(1)));
+
+        for (id, x, orb) in [(2, 100.0, OrbIdentityIds::Radiant), (3, 200.0, OrbIdentityIds::Basic), (4, 300.0, OrbIdentityIds::Radiant),
+            (5, 400.0, OrbIdentityIds::Radiant), (6, 500.0, OrbIdentityIds::Radiant), (7, OBJECTIVE_MARKER_RANGE * 2.0, OrbIdentityIds::Radiant)]
+        {
+            let mut entity = Entity::from_id(id);
+            entity.display.entity_type = EntityType::Orb;
+            entity.stats.alive = AliveState::Alive;
+            entity.display.orb_identity = orb.try_into().unwrap();
+            entity.physics.position = Vector2D::new(x, 0.0);
+            state.entities.insert(id, RefCell::new(entity));
+        }
+
+        let objectives: Vec<_> = state.get_markers(1, None).into_iter()
+            .filter(|marker| marker.kind == MarkerKind::Objective)
+            .map(|marker| marker.id)
+            .collect();
+
+        assert_eq!(objectives, vec![2, 4, 5]);
+    }
+
+    #[test]
+    fn sessions_are_found_whether_or_not_they_are_connected() {
+        let mut state = GameState::default();
+        for (id, alive, disconnected_at) in [(1, AliveState::Alive, None), (2, AliveState::Alive, Some(0)), (3, AliveState::Dead, None)] {
+            let mut entity = Entity::from_id(id);
+            entity.stats.alive = alive;
+            entity.connection.resume_token = Some(id as u64 * 10);
+            entity.connection.disconnected_at = disconnected_at;
+            state.entities.insert(id, RefCell::new(entity));
+        }
+
+        assert_eq!(state.find_session(10), Some(1));
+        assert_eq!(state.find_session(20), Some(2));
+        assert_eq!(state.find_session(30), None);
+        assert_eq!(state.find_session(40), None);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{io::{ErrorKind, Write}, path::{Path, PathBuf}, sync::mpsc::{self, Sender}, thread::JoinHandle};
+
+use crate::{seconds_to_ticks, server::FPS};
+
+use super::highscores::get_unix_time;
+
+/// How often a changed store is written to disk.
+const SAVE_INTERVAL: u64 = seconds_to_ticks!(30);
+
+/// A value which can be kept in a JSON file.
+pub trait Document: Default {
+    fn parse(json: &str) -> Result<Self, String>;
+    fn to_json(&self) -> Result<String, String>;
+}
+
+/// `path` with `suffix` appended to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Writes `contents` to a temporary file beside `path` before renaming it over `path`,
+/// so a crash midway through never leaves a truncated file behind.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let temporary = with_suffix(path, ".tmp");
+
+    let mut file = std::fs::File::create(&temporary)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+
+    std::fs::rename(&temporary, path)
+}
+
+/// Keeps a document in a file which survives restarts.
+/// The document is written on a background thread, so ticks never wait on the disk.
+pub struct JsonStore<T: Document> {
+    /// What the document holds, as named in logs.
+    name: &'static str,
+    path: PathBuf,
+    document: T,
+    dirty: bool,
+    ticks: u64,
+    writer: Option<(Sender<String>, JoinHandle<()>)>
+}
+
+impl<T: Document> JsonStore<T> {
+    /// Opens the file named by the environment variable `var`, or `default` if it is unset.
+    pub fn from_env(name: &'static str, var: &str, default: &str) -> JsonStore<T> {
+        let path = std::env::var_os(var)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(default));
+
+        JsonStore::open(name, path)
+    }
+
+    /// Loads the document kept at `path`. A file which fails to parse is moved aside rather than overwritten,
+    /// and the server refuses to start if the file can be neither read nor moved.
+    pub fn open(name: &'static str, path: PathBuf) -> JsonStore<T> {
+        let document = match std::fs::read_to_string(&path) {
+            Ok(json) => T::parse(&json).unwrap_or_else(|error| {
+                let aside = with_suffix(&path, &format!(".corrupt-{}", get_unix_time()));
+                if let Err(rename_error) = std::fs::rename(&path, &aside) {
+                    panic!("Failed to parse {} ({}) or move them aside: {}", name, error, rename_error);
+                }
+
+                println!("Failed to parse {}, moved them to {} and started afresh: {}", name, aside.display(), error);
+                T::default()
+            }),
+            Err(error) if error.kind() == ErrorKind::NotFound => T::default(),
+            Err(error) => panic!("Failed to read {} from {}: {}", name, path.display(), error)
+        };
+
+        let (sender, receiver) = mpsc::channel::<String>();
+        let writer_path = path.clone();
+        let thread = std::thread::spawn(move || {
+            while let Ok(json) = receiver.recv() {
+                // Only the latest snapshot matters if several are waiting.
+                let json = receiver.try_iter().last().unwrap_or(json);
+                if let Err(error) = write_atomically(&writer_path, &json) {
+                    println!("Failed to save {} to {}: {}", name, writer_path.display(), error);
+                }
+            }
+        });
+
+        JsonStore { name, path, document, dirty: false, ticks: 0, writer: Some((sender, thread)) }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.document
+    }
+
+    /// Changes the document, which is saved the next time the store is due to be written
+    /// if `change` returns whether it changed anything.
+    pub fn update(&mut self, change: impl FnOnce(&mut T) -> bool) {
+        if change(&mut self.document) {
+            self.dirty = true;
+        }
+    }
+
+    /// Writes the document to disk every so often, if it has changed.
+    pub fn tick(&mut self) {
+        self.ticks += 1;
+        if self.ticks.is_multiple_of(SAVE_INTERVAL) && self.dirty {
+            self.save();
+        }
+    }
+
+    /// Hands a snapshot of the document to the background thread to be written.
+    pub fn save(&mut self) {
+        let Some((sender, _)) = &self.writer else { return; };
+
+        match self.document.to_json() {
+            Ok(json) => {
+                let _ = sender.send(json);
+                self.dirty = false;
+            },
+            Err(error) => println!("Failed to save {} to {}: {}", self.name, self.path.display(), error)
+        }
+    }
+
+    /// Saves the document if it has changed, waiting for it to be written.
+    pub fn shutdown(&mut self) {
+        if self.dirty {
+            self.save();
+        }
+
+        if let Some((sender, thread)) = self.writer.take() {
+            drop(sender);
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default, Debug, PartialEq)]
+    struct Lines(Vec<String>);
+
+    impl Document for Lines {
+        fn parse(json: &str) -> Result<Lines, String> {
+            json.strip_prefix('[')
+                .and_then(|json| json.strip_suffix(']'))
+                .map(|json| Lines(json.split(',').filter(|line| !line.is_empty()).map(str::to_string).collect()))
+                .ok_or_else(|| "not a list".to_string())
+        }
+
+        fn to_json(&self) -> Result<String, String> {
+            Ok(format!("[{}]", self.0.join(",")))
+        }
+    }
+
+    fn temporary_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{}-{}.json", name, rand::random::<u32>()))
+    }
+
+    #[test]
+    fn documents_are_saved_on_shutdown() {
+        let path = temporary_path("store");
+        let mut store = JsonStore::<Lines>::open("lines", path.clone());
+        store.update(|lines| { lines.0.push("first".to_string()); true });
+        store.update(|_| false);
+        assert!(store.dirty);
+
+        store.shutdown();
+        assert!(!store.dirty);
+
+        assert!(!with_suffix(&path, ".tmp").exists());
+        assert_eq!(JsonStore::<Lines>::open("lines", path.clone()).get(), &Lines(vec!["first".to_string()]));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn corrupt_files_are_moved_aside() {
+        let path = temporary_path("corrupt-store");
+        std::fs::write(&path, "{ not a list").unwrap();
+
+        let mut store = JsonStore::<Lines>::open("lines", path.clone());
+        assert!(!path.exists());
+        assert_eq!(store.get(), &Lines::default());
+        store.shutdown();
+
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let aside = std::fs::read_dir(std::env::temp_dir()).unwrap()
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.file_name().to_string_lossy().starts_with(&format!("{}.corrupt-", file_name)))
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(aside.path()).unwrap(), "{ not a list");
+        std::fs::remove_file(aside.path()).unwrap();
+    }
+}
@@ -0,0 +1,302 @@
+use shared::{game::{entity::Notification, gear::INVENTORY_CAPACITY}, utils::color::Color};
+
+use super::{entity::base::AliveState, state::EntityDataStructure};
+
+/// A trade between two clanmates.
+pub struct Trade {
+    pub parties: [u32; 2],
+    /// The inventory indices of the items each party has offered.
+    pub offers: [Vec<usize>; 2],
+    pub confirmed: [bool; 2]
+}
+
+impl Trade {
+    /// The position of `id` in the trade's parties.
+    pub fn get_side(&self, id: u32) -> usize {
+        if self.parties[0] == id { 0 } else { 1 }
+    }
+}
+
+/// State that maintains trade requests and open trades.
+#[derive(Default)]
+pub struct TradeState {
+    /// Requests which have not been returned yet, as (from, to).
+    pub requests: Vec<(u32, u32)>,
+    pub trades: Vec<Trade>
+}
+
+impl TradeState {
+    pub fn get_trade(&self, id: u32) -> Option<&Trade> {
+        self.trades.iter().find(|trade| trade.parties.contains(&id))
+    }
+
+    fn get_trade_mut(&mut self, id: u32) -> Option<&mut Trade> {
+        self.trades.iter_mut().find(|trade| trade.parties.contains(&id))
+    }
+
+    /// Requests a trade from `from` to `to`, opening the trade if `to` had already requested one.
+    /// Returns whether the trade was opened.
+    pub fn request(&mut self, from: u32, to: u32) -> bool {
+        if self.get_trade(from).is_some() || self.get_trade(to).is_some() {
+            return false;
+        }
+
+        if self.requests.contains(&(to, from)) {
+            self.requests.retain(|&(a, b)| ![a, b].contains(&from) && ![a, b].contains(&to));
+            self.trades.push(Trade { parties: [from, to], offers: [vec![], vec![]], confirmed: [false, false] });
+
+            return true;
+        }
+
+        self.requests.retain(|&(a, _)| a != from);
+        self.requests.push((from, to));
+        false
+    }
+
+    /// Replaces the items `id` has offered, which unconfirms the trade.
+    pub fn offer(&mut self, id: u32, indices: Vec<usize>) -> Option<u32> {
+        let trade = self.get_trade_mut(id)?;
+        let side = trade.get_side(id);
+
+        trade.offers[side] = indices;
+        trade.confirmed = [false, false];
+
+        Some(trade.parties[1 - side])
+    }
+
+    /// Confirms the trade `id` is in, returning it once both parties have confirmed.
+    pub fn confirm(&mut self, id: u32) -> Option<Trade> {
+        let trade = self.get_trade_mut(id)?;
+        let side = trade.get_side(id);
+        trade.confirmed[side] = true;
+
+        if trade.confirmed == [true, true] {
+            let idx = self.trades.iter().position(|trade| trade.parties.contains(&id))?;
+            Some(self.trades.remove(idx))
+        } else {
+            None
+        }
+    }
+
+    /// Cancels the trade and requests `id` is part of, returning everyone else who was affected.
+    pub fn cancel(&mut self, id: u32) -> Vec<u32> {
+        let mut affected = vec![];
+
+        self.requests.retain(|&(from, to)| {
+            if from == id || to == id {
+                affected.push(if from == id { to } else { from });
+                false
+            } else {
+                true
+            }
+        });
+
+        self.trades.retain(|trade| {
+            if trade.parties.contains(&id) {
+                affected.push(trade.parties[1 - trade.get_side(id)]);
+                false
+            } else {
+                true
+            }
+        });
+
+        affected
+    }
+
+    /// Swaps the offered items between both parties of a confirmed trade.
+    /// Returns whether the items were swapped.
+    pub fn execute(trade: &Trade, entities: &EntityDataStructure) -> bool {
+        let (Some(a), Some(b)) = (entities.get(&trade.parties[0]), entities.get(&trade.parties[1])) else { return false; };
+        let (mut a, mut b) = (a.borrow_mut(), b.borrow_mut());
+
+        let (a_len, b_len) = (a.stats.inventory.items.len(), b.stats.inventory.items.len());
+        let (a_offer, b_offer) = (trade.offers[0].len(), trade.offers[1].len());
+        if a_len - a_offer.min(a_len) + b_offer > INVENTORY_CAPACITY || b_len - b_offer.min(b_len) + a_offer > INVENTORY_CAPACITY {
+            return false;
+        }
+
+        let (mut a_inventory, mut b_inventory) = (a.stats.inventory.clone(), b.stats.inventory.clone());
+        let (Some(a_items), Some(b_items)) = (a_inventory.take(&trade.offers[0]), b_inventory.take(&trade.offers[1])) else { return false; };
+
+        a_inventory.items.extend(b_items);
+        b_inventory.items.extend(a_items);
+
+        a.stats.inventory = a_inventory;
+        b.stats.inventory = b_inventory;
+        true
+    }
+
+    /// Cancels every trade and request involving a player who has left, died, or is no longer a clanmate.
+    pub fn tick(&mut self, entities: &EntityDataStructure) {
+        let is_valid = |a: u32, b: u32| {
+            let (Some(a), Some(b)) = (entities.get(&a), entities.get(&b)) else { return false; };
+            let (a, b) = (a.borrow(), b.borrow());
+
+            a.stats.alive == AliveState::Alive
+                && b.stats.alive == AliveState::Alive
+                && a.display.clan_id.is_some()
+                && a.display.clan_id == b.display.clan_id
+        };
+
+        let (mut unrequested, mut cancelled) = (vec![], vec![]);
+        self.requests.retain(|&(from, to)| {
+            let valid = is_valid(from, to);
+            if !valid {
+                unrequested.extend([from, to]);
+            }
+
+            valid
+        });
+
+        self.trades.retain(|trade| {
+            let valid = is_valid(trade.parties[0], trade.parties[1]);
+            if !valid {
+                cancelled.extend(trade.parties);
+            }
+
+            valid
+        });
+
+        for id in unrequested {
+            if let Some(entity) = entities.get(&id) {
+                entity.borrow_mut().stats.inventory_changed = true;
+            }
+        }
+
+        for id in cancelled {
+            let Some(entity) = entities.get(&id) else { continue; };
+            let mut entity = entity.borrow_mut();
+
+            entity.stats.inventory_changed = true;
+            entity.display.notifications.push(Notification {
+                message: "The trade was cancelled.".to_string(),
+                color: Color::RED,
+                lifetime: 150,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use shared::game::gear::{Gear, GearKind, GearRarity};
+    use crate::game::entity::base::Entity;
+    use super::*;
+
+    fn gear(kind: GearKind) -> Gear {
+        Gear { kind, rarity: GearRarity::Common }
+    }
+
+    /// Two living clanmates, 1 carrying a barrel and 2 carrying plating.
+    fn clanmates() -> EntityDataStructure {
+        [(1, GearKind::Barrel), (2, GearKind::Plating)].into_iter()
+            .map(|(id, kind)| {
+                let mut entity = Entity::from_id(id);
+                entity.stats.alive = AliveState::Alive;
+                entity.display.clan_id = Some(0);
+                entity.stats.inventory.items.push(gear(kind));
+
+                (id, RefCell::new(entity))
+            })
+            .collect()
+    }
+
+    fn opened() -> TradeState {
+        let mut trades = TradeState::default();
+        assert!(!trades.request(1, 2));
+        assert!(trades.request(2, 1));
+
+        trades
+    }
+
+    #[test]
+    fn requests_open_trades_which_execute_once_confirmed() {
+        let entities = clanmates();
+        let mut trades = opened();
+        assert!(trades.requests.is_empty());
+        assert!(!trades.request(1, 2));
+
+        assert_eq!(trades.offer(1, vec![0]), Some(2));
+        assert_eq!(trades.offer(2, vec![0]), Some(1));
+        assert!(trades.confirm(1).is_none());
+
+        let trade = trades.confirm(2).unwrap();
+        assert!(trades.get_trade(1).is_none());
+        assert!(TradeState::execute(&trade, &entities));
+
+        assert_eq!(entities[&1].borrow().stats.inventory.items, vec![gear(GearKind::Plating)]);
+        assert_eq!(entities[&2].borrow().stats.inventory.items, vec![gear(GearKind::Barrel)]);
+    }
+
+    #[test]
+    fn changing_an_offer_unconfirms_the_trade() {
+        let mut trades = opened();
+        trades.offer(1, vec![0]);
+        assert!(trades.confirm(2).is_none());
+
+        trades.offer(1, vec![]);
+        assert_eq!(trades.get_trade(2).unwrap().confirmed, [false, false]);
+        assert!(trades.confirm(1).is_none());
+
+        let trade = trades.confirm(2).unwrap();
+        assert!(trade.offers[0].is_empty());
+    }
+
+    #[test]
+    fn cancelling_notifies_the_other_party() {
+        let mut trades = TradeState::default();
+        trades.request(1, 2);
+        assert_eq!(trades.cancel(2), vec![1]);
+        assert!(trades.requests.is_empty());
+
+        let mut trades = opened();
+        assert_eq!(trades.cancel(1), vec![2]);
+        assert!(trades.get_trade(2).is_none());
+    }
+
+    #[test]
+    fn invalid_offers_are_not_executed() {
+        let entities = clanmates();
+        let mut trades = opened();
+
+        trades.offer(1, vec![0, 0]);
+        trades.confirm(1);
+        let trade = trades.confirm(2).unwrap();
+
+        assert!(!TradeState::execute(&trade, &entities));
+        assert_eq!(entities[&1].borrow().stats.inventory.items, vec![gear(GearKind::Barrel)]);
+    }
+
+    #[test]
+    fn leaving_mid_trade_cancels_it() {
+        let mut entities = clanmates();
+        let mut trades = opened();
+        trades.offer(1, vec![0]);
+
+        trades.tick(&entities);
+        assert!(trades.get_trade(1).is_some());
+
+        entities.remove(&2);
+        trades.tick(&entities);
+        assert!(trades.get_trade(1).is_none());
+
+        let entity = entities[&1].borrow();
+        assert!(entity.stats.inventory_changed);
+        assert_eq!(entity.display.notifications.len(), 1);
+    }
+
+    #[test]
+    fn leaving_the_clan_drops_requests() {
+        let entities = clanmates();
+        let mut trades = TradeState::default();
+        trades.request(1, 2);
+
+        entities[&2].borrow_mut().display.clan_id = Some(1);
+        trades.tick(&entities);
+        assert!(trades.requests.is_empty());
+        assert!(entities[&1].borrow().stats.inventory_changed);
+    }
+}
+
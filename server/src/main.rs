@@ -3,7 +3,8 @@
 #![feature(generic_arg_infer)]
 
 use game::{physics::shg::SpatialHashGrid, state::GameState};
-use server::{Server, CELL_SIZE};
+use server::{get_game_mode, Server, CELL_SIZE};
+use shared::game::world::WorldInfo;
 
 mod server;
 mod game;
@@ -15,6 +16,8 @@ async fn main() {
         GameState {
             shg: SpatialHashGrid::new(256, CELL_SIZE),
             desired_orb_count: 300,
+            friendly_fire: false,
+            world: WorldInfo { mode: get_game_mode(), ..WorldInfo::default() },
             ..GameState::default()
         }
     ]).await;
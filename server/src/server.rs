@@ -1,7 +1,8 @@
 use std::{net::SocketAddr, sync::Arc};
 use axum::Router;
 use tokio::sync::{Mutex as AsyncMutex, MutexGuard};
-use crate::{connection::wss::WebSocketServer, game::state::{GameServer, GameState}};
+use shared::{game::world::GameMode, utils::consts::TICK_RATE};
+use crate::{connection::{http, wss::WebSocketServer}, game::state::{GameServer, GameState}};
 
 pub type WrappedServer = Arc<AsyncMutex<Server>>;
 
@@ -13,15 +14,47 @@ macro_rules! seconds_to_ticks {
 }
 
 pub const PORT: u16 = 8080;
-pub const FPS: u64 = 25;
+pub const FPS: u64 = TICK_RATE;
 pub const MSPT: u64 = 1000 / FPS;
 pub const CELL_SIZE: u32 = 7;
+/// The environment variable naming the mode the arena is played in, such as `ClanWars`.
+/// If unset, the arena is free for all.
+pub const GAME_MODE_VAR: &str = "GAME_MODE";
 
 pub const SPAWN_INVINCIBILITY_TIME: u64 = 30 * FPS;
-pub const LEADER_ARROW_VIEW: f32 = 1920.0 * 1.5;
 
 pub const MESSAGE_EXPIRY: u64 = seconds_to_ticks!(7);
 pub const SWITCH_TIME_THRESHOLD: u64 = seconds_to_ticks!(1) / 5;
+/// The maximum number of inputs buffered per client before the oldest are dropped.
+pub const MAX_BUFFERED_INPUTS: usize = 5;
+/// The opacity at or below which a tank can no longer be seen or targeted.
+pub const MIN_VISIBLE_OPACITY: f32 = 0.05;
+/// How far (in radians) an automatic turret may be off target and still fire.
+pub const AUTO_TURRET_AIM_TOLERANCE: f32 = 0.2;
+/// How close minions get to their targets before backing off.
+pub const MINION_DISTANCE: f32 = 350.0;
+/// How fast a tank which cannot go invisible fades back in after its smoke clears, per tick.
+pub const SMOKE_FADE_RATE: f32 = 0.05;
+/// The smallest reward for assisting in killing an orb which is announced to the assister.
+pub const ASSIST_NOTIFICATION_MIN_SCORE: usize = 100;
+/// How long a player must wait between distress calls.
+pub const DISTRESS_COOLDOWN: u64 = seconds_to_ticks!(10);
+/// How long a distress call is pointed out to clanmates.
+pub const DISTRESS_CALL_DURATION: u64 = seconds_to_ticks!(10);
+/// How far away radiant orbs are pointed out to a player.
+pub const OBJECTIVE_MARKER_RANGE: f32 = 4000.0;
+/// The most radiant orbs pointed out to a player at once, nearest first.
+pub const MAX_OBJECTIVE_MARKERS: usize = 3;
+/// How long each round of a clan war lasts.
+pub const CLAN_WAR_DURATION: u64 = seconds_to_ticks!(10 * 60);
+/// How fast a spectator's camera accelerates while panning freely.
+pub const SPECTATOR_SPEED: f32 = 5.0;
+/// How long an entity waits before afflicting the same victim with its effects again.
+pub const EFFECT_COOLDOWN: u64 = seconds_to_ticks!(1);
+/// How long a disconnected player's tank is kept for it to reclaim.
+pub const RESUME_GRACE_PERIOD: u64 = seconds_to_ticks!(30);
+/// How long a client may go without sending anything before it is disconnected.
+pub const PING_TIMEOUT: u64 = seconds_to_ticks!(10);
 
 pub struct Server {
     pub game_server: GameServer,
@@ -30,6 +63,15 @@ pub struct Server {
 
 pub type ServerGuard<'a> = MutexGuard<'a, Server>;
 
+/// The mode named by the environment variable `GAME_MODE`, or free for all if it is unset.
+/// The server refuses to start if the mode is not recognised.
+pub fn get_game_mode() -> GameMode {
+    let Some(name) = std::env::var_os(GAME_MODE_VAR) else { return GameMode::default(); };
+    let name = name.to_string_lossy();
+
+    GameMode::from_name(&name).unwrap_or_else(|| panic!("Unknown game mode in {}: {}", GAME_MODE_VAR, name))
+}
+
 impl Server {
     /// Initializes the server.
     pub async fn init(game_states: Vec<GameState>) {
@@ -44,6 +86,7 @@ impl Server {
         tokio::task::spawn(async move {
             let router = Router::new()
                 .route("/ws", axum::routing::get(WebSocketServer::handle_incoming_connection))
+                .route("/highscores", axum::routing::get(http::serve_high_scores))
                 .with_state(wrapped_server_clone);
 
             let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", PORT))
@@ -59,9 +102,38 @@ impl Server {
         });
 
         let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(MSPT));
+        let shutdown = Server::shutdown_signal();
+        tokio::pin!(shutdown);
+
         loop {
-            interval.tick().await;
-            wrapped_server_clone_2.lock().await.tick().await;
+            tokio::select! {
+                _ = interval.tick() => wrapped_server_clone_2.lock().await.tick().await,
+                _ = &mut shutdown => break
+            }
+        }
+
+        println!("Shutting down...");
+        wrapped_server_clone_2.lock().await.game_server.shutdown();
+    }
+
+    /// Resolves once the process is asked to stop, either by Ctrl+C or by SIGTERM.
+    async fn shutdown_signal() {
+        let ctrl_c = async { let _ = tokio::signal::ctrl_c().await; };
+
+        #[cfg(unix)]
+        let terminate = async {
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(mut signal) => { signal.recv().await; },
+                Err(_) => std::future::pending().await
+            }
+        };
+
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = ctrl_c => (),
+            _ = terminate => ()
         }
     }
 
@@ -1 +1 @@
-nodemon --watch server --exec "./server_helper.sh"
\ No newline at end of file
+nodemon --watch server --ignore "server/*.json" --exec "./server_helper.sh"
\ No newline at end of file
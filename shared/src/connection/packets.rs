@@ -1,13 +1,18 @@
 #[derive(Debug, Clone, PartialEq, num_enum::TryFromPrimitive)]
 #[repr(u8)]
 pub enum ServerboundPackets {
-    Spawn    = 0x0,
-    Input    = 0x1,
-    Stats    = 0x2,
-    Upgrade  = 0x3,
-    Ping     = 0x4,
-    Chat     = 0x5,
-    Clan     = 0x6
+    Spawn      = 0x0,
+    Input      = 0x1,
+    Stats      = 0x2,
+    Upgrade    = 0x3,
+    Ping       = 0x4,
+    Chat       = 0x5,
+    Clan       = 0x6,
+    Inventory  = 0x7,
+    Spectate   = 0x8,
+    Resume     = 0x9,
+    Profile    = 0xA,
+    HighScores = 0xB
 }
 
 #[derive(Debug, Clone, num_enum::TryFromPrimitive)]
@@ -16,7 +21,14 @@ pub enum ClientboundPackets {
     Update         = 0x0,
     Notifications  = 0x1,
     Pong           = 0x2,
-    ServerInfo     = 0x3
+    ServerInfo     = 0x3,
+    Inventory      = 0x4,
+    Events         = 0x5,
+    Death          = 0x6,
+    WorldInfo      = 0x7,
+    Session        = 0x8,
+    Profile        = 0x9,
+    HighScores     = 0xA
 }
 
 #[derive(Debug, Clone, PartialEq, num_enum::TryFromPrimitive)]
@@ -26,7 +38,44 @@ pub enum ClanPacketOpcode {
     Join      = 0x1,
     Leave     = 0x2,
     Kick      = 0x3,
-    Distress  = 0x4
+    Distress  = 0x4,
+    Accept    = 0x5,
+    Reject    = 0x6,
+    Transfer  = 0x7
+}
+
+#[derive(Debug, Clone, PartialEq, num_enum::TryFromPrimitive)]
+#[repr(u64)]
+pub enum InventoryPacketOpcode {
+    Equip         = 0x0,
+    Unequip       = 0x1,
+    Discard       = 0x2,
+    TradeRequest  = 0x3,
+    TradeOffer    = 0x4,
+    TradeConfirm  = 0x5,
+    TradeCancel   = 0x6
+}
+
+#[derive(Debug, Clone, PartialEq, num_enum::TryFromPrimitive)]
+#[repr(u64)]
+pub enum SpectatePacketOpcode {
+    Start     = 0x0,
+    Leader    = 0x1,
+    Next      = 0x2,
+    Previous  = 0x3,
+    Free      = 0x4,
+    Pan       = 0x5,
+    Stop      = 0x6
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum GameplayEventType {
+    TurretFired       = 0x0,
+    Hit               = 0x1,
+    LevelUp           = 0x2,
+    UpgradeAvailable  = 0x3,
+    ObjectiveCaptured = 0x4
 }
 
 #[derive(Debug, Clone, Copy, strum_macros::EnumIter)]
@@ -38,7 +87,8 @@ pub enum Inputs {
     Right    = 0b100000,
     LevelUp  = 0b1000000,
     Repel    = 0b10000000,
-    Switch   = 0b100000000
+    Switch   = 0b100000000,
+    Ability  = 0b1000000000
 }
 
 #[derive(Debug, strum_macros::EnumIter, Clone, num_enum::TryFromPrimitive)]
@@ -61,5 +111,11 @@ pub enum CensusProperties {
     Ticks,
     Invincibility,
     Messages,
-    Clan
+    Clan,
+    InputSequence,
+    TurretAngles,
+    StatusEffects,
+    Ability,
+    Energy,
+    Movement
 }
\ No newline at end of file
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use crate::utils::consts::TICK_RATE;
+
+/// The actions a tank can perform with the ability input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, num_enum::TryFromPrimitive, strum_macros::EnumIter)]
+#[repr(u8)]
+pub enum AbilityKind {
+    /// Launches the tank in the direction it is moving, or facing if still, with `magnitude` as the impulse.
+    Dash,
+    /// Blocks `magnitude`, a fraction of all damage taken, while active.
+    Shield,
+    /// Brings every drone and minion the tank owns back to it.
+    DroneRecall,
+    /// Hides the tank in a cloud of smoke while active.
+    Smoke
+}
+
+impl AbilityKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbilityKind::Dash => "Dash",
+            AbilityKind::Shield => "Shield",
+            AbilityKind::DroneRecall => "Recall",
+            AbilityKind::Smoke => "Smoke"
+        }
+    }
+}
+
+/// An ability granted by a body or turret.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ability {
+    pub kind: AbilityKind,
+    /// The number of seconds it takes to restore a single charge.
+    pub cooldown: f32,
+    /// The number of times the ability can be used in a row.
+    #[serde(default = "default_charges")]
+    pub charges: u8,
+    /// How long the ability lasts once used, in seconds.
+    #[serde(default)]
+    pub duration: f32,
+    /// How strong the ability is. Its meaning depends on the kind.
+    #[serde(default)]
+    pub magnitude: f32,
+    /// The energy spent per use.
+    #[serde(default)]
+    pub energy_cost: f32
+}
+
+fn default_charges() -> u8 {
+    1
+}
+
+impl Ability {
+    pub fn get_cooldown_ticks(&self) -> u64 {
+        ((self.cooldown * TICK_RATE as f32).round() as u64).max(1)
+    }
+
+    pub fn get_duration_ticks(&self) -> u64 {
+        (self.duration * TICK_RATE as f32).round() as u64
+    }
+}
+
+/// The charges and cooldown of a tank's ability.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AbilityState {
+    /// The ability the state belongs to, or `None` if the tank has no ability.
+    pub kind: Option<AbilityKind>,
+    pub charges: u8,
+    /// The number of ticks until the next charge is restored.
+    pub recharge_ticks: u64,
+    /// The number of ticks the ability remains active for.
+    pub active_ticks: u64
+}
+
+impl AbilityState {
+    pub fn new(ability: &Ability) -> AbilityState {
+        AbilityState {
+            kind: Some(ability.kind),
+            charges: ability.charges,
+            recharge_ticks: 0,
+            active_ticks: 0
+        }
+    }
+
+    /// Counts down the active ability and restores charges one at a time.
+    pub fn tick(&mut self, ability: &Ability) {
+        self.active_ticks = self.active_ticks.saturating_sub(1);
+
+        if self.charges >= ability.charges {
+            self.recharge_ticks = 0;
+            return;
+        }
+
+        self.recharge_ticks = self.recharge_ticks.saturating_sub(1);
+        if self.recharge_ticks == 0 {
+            self.charges += 1;
+
+            if self.charges < ability.charges {
+                self.recharge_ticks = ability.get_cooldown_ticks();
+            }
+        }
+    }
+
+    /// Spends a charge, returning whether there was one to spend.
+    pub fn try_use(&mut self, ability: &Ability) -> bool {
+        if self.charges == 0 {
+            return false;
+        }
+
+        self.charges -= 1;
+        if self.recharge_ticks == 0 {
+            self.recharge_ticks = ability.get_cooldown_ticks();
+        }
+
+        self.active_ticks = ability.get_duration_ticks();
+        true
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_ticks > 0
+    }
+
+    /// The fraction of the cooldown left before the next charge is restored.
+    pub fn get_cooldown(&self, ability: &Ability) -> f32 {
+        self.recharge_ticks as f32 / ability.get_cooldown_ticks() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dash(charges: u8) -> Ability {
+        Ability { kind: AbilityKind::Dash, cooldown: 1.0, charges, duration: 0.0, magnitude: 30.0, energy_cost: 0.0 }
+    }
+
+    #[test]
+    fn charges_are_spent_and_restored_one_at_a_time() {
+        let ability = dash(2);
+        let mut state = AbilityState::new(&ability);
+
+        assert!(state.try_use(&ability));
+        assert!(state.try_use(&ability));
+        assert!(!state.try_use(&ability));
+
+        for _ in 0..TICK_RATE {
+            state.tick(&ability);
+        }
+        assert_eq!(state.charges, 1);
+        assert!((state.get_cooldown(&ability) - 1.0).abs() < f32::EPSILON);
+
+        for _ in 0..TICK_RATE {
+            state.tick(&ability);
+        }
+        assert_eq!(state.charges, 2);
+        assert_eq!(state.get_cooldown(&ability), 0.0);
+    }
+
+    #[test]
+    fn abilities_stay_active_for_their_duration() {
+        let ability = Ability { kind: AbilityKind::Shield, cooldown: 10.0, charges: 1, duration: 2.0, magnitude: 0.5, energy_cost: 0.0 };
+        let mut state = AbilityState::new(&ability);
+
+        assert!(state.try_use(&ability));
+        assert!(state.is_active());
+
+        for _ in 0..2 * TICK_RATE {
+            state.tick(&ability);
+        }
+        assert!(!state.is_active());
+        assert_eq!(state.charges, 0);
+    }
+}
\ No newline at end of file
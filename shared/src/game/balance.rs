@@ -0,0 +1,390 @@
+use std::{collections::VecDeque, fmt::Write};
+use strum::EnumCount;
+
+use crate::utils::{consts::{FRICTION, MAX_LEVEL, TICK_RATE}, vec2::Vector2D};
+use super::{body::{BodyIdentity, BodyIdentityIds}, entity::{EntityType, UpgradeStats, BASE_TANK_RADIUS}, movement::{accelerate, get_tank_speed, integrate}, turret::{ProjectileIdentity, TurretIdentity, TurretIdentityIds, TurretStructure}};
+
+/// The number of ticks a turret is fired for when measuring its rate of fire.
+const FIRE_RATE_SAMPLE_TICKS: usize = TICK_RATE as usize * 60;
+/// The number of ticks a tank accelerates for when measuring its top speed.
+const TOP_SPEED_SAMPLE_TICKS: usize = 250;
+
+/// The number of ticks between shots of a turret with a reload multiplier of 1.
+pub fn get_reload(reload_investment: usize) -> f32 {
+    15.0 * 0.914_f32.powf(reload_investment as f32)
+}
+
+pub fn get_max_health(body_max_health: f32, level: usize, max_health_investment: usize) -> f32 {
+    body_max_health
+        + (2.0 * (level.max(1) - 1) as f32)
+        + (20.0 * max_health_investment as f32)
+}
+
+pub fn get_regen_per_tick(max_health: f32, health_regen_investment: usize) -> f32 {
+    (max_health * 4.0 * health_regen_investment as f32 + max_health) / 25000.0
+}
+
+pub fn get_body_damage(body_damage: f32, body_damage_investment: usize) -> f32 {
+    (body_damage_investment as f32 * 6.0 + 20.0) * body_damage
+}
+
+/// Scales body damage by how close a tank is to its top speed.
+pub fn get_ramming_multiplier(ramming_factor: f32, velocity: f32, acceleration: f32) -> f32 {
+    if ramming_factor == 0.0 || acceleration <= 0.0 {
+        return 1.0;
+    }
+
+    let top_speed = acceleration / FRICTION;
+    1.0 + ramming_factor * (velocity / top_speed).min(1.0)
+}
+
+pub fn get_fov(turret_fov: f32, level: usize) -> f32 {
+    (0.55 * turret_fov) / 1.01_f32.powf((level.max(1) as f32 - 1.0) / 2.0)
+}
+
+pub fn get_tank_radius(level: usize) -> f32 {
+    BASE_TANK_RADIUS * 1.007_f32.powf((level.max(1) - 1) as f32)
+}
+
+/// The speed constantly added to a projectile every tick.
+pub fn get_projectile_base_speed(projectile: &ProjectileIdentity, projectile_speed_investment: usize) -> f32 {
+    (20.0 + 1.5 * projectile_speed_investment as f32) * projectile.speed
+}
+
+/// Splits a projectile's speed into `(additional, initial)` velocities.
+/// `scatter` is a random number in `[0, 1]`.
+pub fn get_projectile_speeds(projectile: &ProjectileIdentity, base_speed: f32, scatter: f32) -> Result<(f32, f32), String> {
+    match projectile.projectile_type {
+        EntityType::Bullet | EntityType::Missile => Ok((base_speed, base_speed + 20.0 - scatter * projectile.scatter_rate)),
+        EntityType::Drone | EntityType::Minion => Ok((base_speed, base_speed / 2.0)),
+        EntityType::Trap | EntityType::Mine => Ok((0.0, base_speed)),
+        projectile_type => Err(format!("{:?} is not a projectile type", projectile_type))
+    }
+}
+
+pub fn get_projectile_penetration(projectile: &ProjectileIdentity, projectile_penetration_investment: usize) -> f32 {
+    (1.5 * projectile_penetration_investment as f32 + 2.0) * projectile.health
+}
+
+pub fn get_projectile_damage(projectile: &ProjectileIdentity, projectile_damage_investment: usize) -> f32 {
+    (7.0 + projectile_damage_investment as f32 * 3.0) * projectile.damage
+}
+
+pub fn get_projectile_push_factor(projectile: &ProjectileIdentity, projectile_damage_investment: usize) -> f32 {
+    ((7.0 / 3.0) + projectile_damage_investment as f32) * projectile.damage * projectile.absorption_factor
+}
+
+/// The lifetime of a projectile in ticks, or -1 if it lives forever.
+pub fn get_projectile_lifetime(projectile: &ProjectileIdentity) -> Result<isize, String> {
+    let ticks_per_lifetime = match projectile.projectile_type {
+        EntityType::Bullet | EntityType::Missile => 72.0,
+        EntityType::Drone | EntityType::Minion => 88.0,
+        EntityType::Trap | EntityType::Mine => 75.0,
+        projectile_type => return Err(format!("{:?} is not a projectile type", projectile_type))
+    };
+
+    if projectile.lifetime == -1.0 {
+        Ok(-1)
+    } else {
+        Ok((projectile.lifetime * ticks_per_lifetime) as isize)
+    }
+}
+
+/// A tank to compute the balance of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TankBuild {
+    pub turret: TurretIdentityIds,
+    pub body: BodyIdentityIds,
+    pub level: usize,
+    /// Investments in the order of `UpgradeStats`.
+    pub stat_investments: [usize; UpgradeStats::COUNT]
+}
+
+impl Default for TankBuild {
+    fn default() -> TankBuild {
+        TankBuild {
+            turret: TurretIdentityIds::Base,
+            body: BodyIdentityIds::Base,
+            level: MAX_LEVEL,
+            stat_investments: [0; UpgradeStats::COUNT]
+        }
+    }
+}
+
+/// The derived numbers of a single turret.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurretBalance {
+    pub projectile_type: EntityType,
+    /// The sustained rate of fire, once the turret has as many projectiles out as it may.
+    pub shots_per_second: f32,
+    /// How many projectiles the turret may have out at once, or `None` if there is no limit.
+    pub max_projectiles: Option<usize>,
+    pub damage: f32,
+    pub penetration: f32,
+    /// The speed of a projectile on its first tick, in units per second.
+    pub speed: f32,
+    /// How far a projectile travels before expiring, or `None` if it lives forever.
+    pub range: Option<f32>
+}
+
+/// The derived numbers of a tank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TankBalance {
+    pub build: TankBuild,
+    pub turrets: Vec<TurretBalance>,
+    /// Damage dealt per second at the sustained rate of fire, if every projectile connects for one tick.
+    pub dps: f32,
+    /// The highest penetration of any turret.
+    pub penetration: f32,
+    /// The fastest projectile speed of any turret, in units per second.
+    pub projectile_speed: f32,
+    /// The longest range of any turret, or `None` if a projectile lives forever.
+    pub range: Option<f32>,
+    /// The net velocity the turrets push the tank back with each second.
+    pub recoil_per_second: f32,
+    /// The top speed of the tank, in units per second.
+    pub movement_speed: f32,
+    pub fov: f32,
+    pub max_health: f32,
+    /// Body damage at top speed.
+    pub body_damage: f32
+}
+
+impl TankBalance {
+    pub fn compute(build: &TankBuild) -> Result<TankBalance, String> {
+        let structure = TurretStructure::try_from(build.turret)
+            .map_err(|_| format!("unknown turret identity {:?}", build.turret))?;
+        let body = BodyIdentity::try_from(build.body)
+            .map_err(|_| format!("unknown body identity {:?}", build.body))?;
+
+        let investment = |stat: UpgradeStats| build.stat_investments[stat as usize];
+        let level = build.level.clamp(1, MAX_LEVEL);
+        let reload = get_reload(investment(UpgradeStats::Reload));
+
+        let mut turrets = Vec::with_capacity(structure.turrets.len());
+        let mut recoil = Vector2D::ZERO;
+
+        for turret in structure.turrets.iter() {
+            let projectile = &turret.projectile_identity;
+            let lifetime = get_projectile_lifetime(projectile)?;
+            let shots_per_second = get_shots_per_second(turret, reload, lifetime);
+
+            let base_speed = get_projectile_base_speed(projectile, investment(UpgradeStats::ProjectileSpeed));
+            let (additional_speed, initial_speed) = get_projectile_speeds(projectile, base_speed, 0.5)?;
+            let (speed, range) = get_projectile_travel(additional_speed, initial_speed, lifetime);
+
+            recoil += Vector2D::from_polar(turret.recoil * shots_per_second, turret.angle);
+
+            turrets.push(TurretBalance {
+                projectile_type: projectile.projectile_type,
+                shots_per_second,
+                max_projectiles: usize::try_from(turret.max_projectiles).ok(),
+                damage: get_projectile_damage(projectile, investment(UpgradeStats::ProjectileDamage)),
+                penetration: get_projectile_penetration(projectile, investment(UpgradeStats::ProjectilePenetration)),
+                speed,
+                range
+            });
+        }
+
+        let range = if turrets.iter().any(|turret| turret.range.is_none()) {
+            None
+        } else {
+            Some(turrets.iter().filter_map(|turret| turret.range).fold(0.0, f32::max))
+        };
+
+        let acceleration = get_tank_speed(body.speed, investment(UpgradeStats::MovementSpeed), level);
+
+        Ok(TankBalance {
+            dps: turrets.iter().map(|turret| turret.damage * turret.shots_per_second).sum(),
+            penetration: turrets.iter().map(|turret| turret.penetration).fold(0.0, f32::max),
+            projectile_speed: turrets.iter().map(|turret| turret.speed).fold(0.0, f32::max),
+            range,
+            recoil_per_second: recoil.magnitude(),
+            movement_speed: get_top_speed(acceleration),
+            fov: get_fov(structure.fov, level),
+            max_health: get_max_health(body.max_health, level, investment(UpgradeStats::MaxHealth)),
+            body_damage: get_body_damage(body.body_damage, investment(UpgradeStats::BodyDamage))
+                * get_ramming_multiplier(body.ramming_factor, acceleration / FRICTION, acceleration),
+            build: TankBuild { level, ..build.clone() },
+            turrets
+        })
+    }
+
+    /// Formats tanks side by side, one column per tank.
+    pub fn to_table(tanks: &[TankBalance]) -> String {
+        let format_range = |range: Option<f32>| range.map(|range| format!("{:.0}", range)).unwrap_or("-".to_string());
+
+        let mut rows: Vec<(&str, Vec<String>)> = vec![
+            ("tank", tanks.iter().map(|tank| {
+                if tank.build.body == BodyIdentityIds::Base {
+                    tank.build.turret.to_string()
+                } else {
+                    format!("{} + {}", tank.build.turret, tank.build.body)
+                }
+            }).collect()),
+            ("level", tanks.iter().map(|tank| tank.build.level.to_string()).collect()),
+            ("build", tanks.iter().map(|tank| tank.build.stat_investments.map(|stat| stat.to_string()).join("/")).collect()),
+            ("dps", tanks.iter().map(|tank| format!("{:.1}", tank.dps)).collect()),
+            ("penetration", tanks.iter().map(|tank| format!("{:.1}", tank.penetration)).collect()),
+            ("projectile speed", tanks.iter().map(|tank| format!("{:.0}", tank.projectile_speed)).collect()),
+            ("range", tanks.iter().map(|tank| format_range(tank.range)).collect()),
+            ("recoil/s", tanks.iter().map(|tank| format!("{:.1}", tank.recoil_per_second)).collect()),
+            ("movement speed", tanks.iter().map(|tank| format!("{:.0}", tank.movement_speed)).collect()),
+            ("fov", tanks.iter().map(|tank| format!("{:.3}", tank.fov)).collect()),
+            ("max health", tanks.iter().map(|tank| format!("{:.0}", tank.max_health)).collect()),
+            ("body damage", tanks.iter().map(|tank| format!("{:.1}", tank.body_damage)).collect())
+        ];
+
+        if tanks.len() == 1 {
+            for (i, turret) in tanks[0].turrets.iter().enumerate() {
+                rows.push(("", vec![]));
+                rows.push(("turret", vec![format!("#{} ({:?})", i, turret.projectile_type)]));
+                rows.push(("  shots/s", vec![format!("{:.2}", turret.shots_per_second)]));
+                rows.push(("  max out", vec![turret.max_projectiles.map(|max| max.to_string()).unwrap_or("-".to_string())]));
+                rows.push(("  damage", vec![format!("{:.1}", turret.damage)]));
+                rows.push(("  penetration", vec![format!("{:.1}", turret.penetration)]));
+                rows.push(("  speed", vec![format!("{:.0}", turret.speed)]));
+                rows.push(("  range", vec![format_range(turret.range)]));
+            }
+        }
+
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or_default();
+        let column_widths: Vec<usize> = (0..tanks.len())
+            .map(|i| rows.iter().filter_map(|(_, cells)| cells.get(i)).map(String::len).max().unwrap_or_default())
+            .collect();
+
+        let mut table = String::new();
+        for (label, cells) in rows.iter() {
+            let mut line = format!("{:<width$}", label, width = label_width);
+            for (cell, width) in cells.iter().zip(column_widths.iter()) {
+                let _ = write!(line, "  {:>width$}", cell, width = width);
+            }
+
+            table += line.trim_end();
+            table.push('\n');
+        }
+
+        table
+    }
+}
+
+/// Fires a turret through the server's reload cycle and counts the shots.
+/// A turret with a projectile limit holds its fire while it is full, until a projectile expires after `lifetime` ticks.
+fn get_shots_per_second(turret: &TurretIdentity, reload: f32, lifetime: isize) -> f32 {
+    let mut turret = turret.clone();
+    turret.reload_time = 0.0;
+    turret.cycle_position = 0.0;
+    turret.projectiles_spawned = 0;
+
+    let mut expiries = VecDeque::new();
+    let mut shots = 0;
+
+    for tick in 0..FIRE_RATE_SAMPLE_TICKS as isize {
+        while expiries.front() == Some(&tick) {
+            expiries.pop_front();
+            turret.projectiles_spawned -= 1;
+        }
+
+        if !turret.can_fire(reload, true) {
+            continue;
+        }
+
+        if turret.max_projectiles != -1 && turret.projectiles_spawned >= turret.max_projectiles {
+            continue;
+        }
+
+        shots += 1;
+        turret.projectiles_spawned += 1;
+        if lifetime != -1 {
+            expiries.push_back(tick + lifetime);
+        }
+    }
+
+    shots as f32 * TICK_RATE as f32 / FIRE_RATE_SAMPLE_TICKS as f32
+}
+
+/// Moves a projectile the way the server does, returning its first tick speed
+/// (in units per second) and how far it travels in its lifetime.
+fn get_projectile_travel(additional_speed: f32, initial_speed: f32, lifetime: isize) -> (f32, Option<f32>) {
+    let additional_velocity = Vector2D::new(additional_speed, 0.0);
+    let mut velocity = Vector2D::new(initial_speed, 0.0);
+    let mut position = Vector2D::ZERO;
+
+    integrate(&mut position, &mut velocity, additional_velocity, None);
+    let speed = position.x * TICK_RATE as f32;
+
+    if lifetime == -1 {
+        return (speed, None);
+    }
+
+    for _ in 1..lifetime {
+        integrate(&mut position, &mut velocity, additional_velocity, None);
+    }
+
+    (speed, Some(position.x))
+}
+
+/// Accelerates a tank from rest until friction balances it out, in units per second.
+fn get_top_speed(acceleration: f32) -> f32 {
+    let mut position = Vector2D::ZERO;
+    let mut velocity = Vector2D::ZERO;
+
+    for _ in 0..TOP_SPEED_SAMPLE_TICKS {
+        integrate(&mut position, &mut velocity, Vector2D::ZERO, None);
+        accelerate(&mut velocity, Vector2D::new(1.0, 0.0), acceleration);
+    }
+
+    // The velocity after integration, which is what actually moves the tank.
+    velocity.x * (1.0 - FRICTION) * TICK_RATE as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::entity::MAX_STAT_INVESTMENT;
+    use super::*;
+
+    #[test]
+    fn base_tank_fires_once_per_reload_cycle() {
+        let balance = TankBalance::compute(&TankBuild { turret: TurretIdentityIds::Mono, ..Default::default() }).unwrap();
+        let reload_ticks = get_reload(0).ceil();
+
+        assert_eq!(balance.turrets.len(), 1);
+        assert!((balance.turrets[0].shots_per_second - TICK_RATE as f32 / reload_ticks).abs() < 0.1);
+    }
+
+    #[test]
+    fn investments_improve_their_stats() {
+        let base = TankBalance::compute(&TankBuild { turret: TurretIdentityIds::Mono, ..Default::default() }).unwrap();
+        let maxed = TankBalance::compute(&TankBuild {
+            turret: TurretIdentityIds::Mono,
+            stat_investments: [MAX_STAT_INVESTMENT; UpgradeStats::COUNT],
+            ..Default::default()
+        }).unwrap();
+
+        assert!(maxed.dps > base.dps);
+        assert!(maxed.penetration > base.penetration);
+        assert!(maxed.projectile_speed > base.projectile_speed);
+        assert!(maxed.movement_speed > base.movement_speed);
+        assert!(maxed.max_health > base.max_health);
+    }
+
+    #[test]
+    fn projectile_limits_cap_the_rate_of_fire() {
+        let spawner = TankBalance::compute(&TankBuild { turret: TurretIdentityIds::Spawner, ..Default::default() }).unwrap();
+        assert_eq!(spawner.turrets[0].max_projectiles, Some(4));
+        assert_eq!(spawner.turrets[0].shots_per_second, 4.0 / 60.0);
+
+        let minelayer = TankBalance::compute(&TankBuild { turret: TurretIdentityIds::Minelayer, ..Default::default() }).unwrap();
+        let lifetime = get_projectile_lifetime(&TurretStructure::try_from(TurretIdentityIds::Minelayer).unwrap().turrets[0].projectile_identity).unwrap();
+        assert!(minelayer.turrets[0].shots_per_second <= 8.0 * TICK_RATE as f32 / lifetime as f32 + 0.1);
+        assert!((minelayer.dps - minelayer.turrets[0].damage * minelayer.turrets[0].shots_per_second).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rejects_non_projectiles() {
+        let projectile = ProjectileIdentity { projectile_type: EntityType::Orb, ..Default::default() };
+        assert!(get_projectile_speeds(&projectile, 20.0, 0.5).is_err());
+        assert!(get_projectile_lifetime(&projectile).is_err());
+    }
+}
+
@@ -1,56 +1,55 @@
 use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+use super::{ability::Ability, definitions::get_definitions, energy::EnergyRules, turret::TurretIdentity};
 
-macro_rules! into_structure {
-    (
-        $( #[$enum_meta:meta] )*
-        $enum_vis:vis enum $enum_name:ident {
-            $(
-                $( #[$variant_meta:meta] )*
-                $variant:ident = $variant_index:expr,
-            )*
-        }
-    ) => {
-        $( #[$enum_meta] )*
-        $enum_vis enum $enum_name {
-            $(
-                $( #[$variant_meta] )*
-                $variant = $variant_index,
-            )*
-        }
-
-        impl std::convert::TryFrom<BodyIdentityIds> for BodyIdentity {
-            type Error = ();
-
-            fn try_from(value: BodyIdentityIds) -> ::std::result::Result<BodyIdentity, Self::Error> {
-                match value {
-                    $(
-                        BodyIdentityIds::$variant => Ok(::paste::paste! { [< get_body_ $variant:snake _identity >] () }),
-                    )*
-                }
-            }
-        }
-    };
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BodyRenderingHints {
     SmasherGuard {
         /// The thickness of the guard.
         thickness: f32,
         /// The number of sides on the guard.
         sides: usize
+    },
+    /// Triangular spikes around the body.
+    Spikes {
+        /// The number of spikes.
+        spikes: usize,
+        /// How far the spikes reach, relative to the radius.
+        length: f32
+    },
+    /// Small studs around the rim of the body.
+    Studs {
+        /// The number of studs.
+        studs: usize,
+        /// The radius of each stud, relative to the radius.
+        size: f32
+    },
+    /// A plate covering the front of the body.
+    RamPlate {
+        /// How far the plate reaches, relative to the radius.
+        thickness: f32,
+        /// The angle the plate covers, in degrees.
+        arc: f32
+    },
+    /// A ring of armor around the body.
+    Armor {
+        /// How far the armor reaches, relative to the radius.
+        thickness: f32
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct BodyIdentity {
     /// The ID of the body identity.
     pub id: BodyIdentityIds, 
     /// Hints as to how to render the body.
+    #[serde(default)]
     pub render_hints: Vec<BodyRenderingHints>,
     /// The level requirement for the body.
     pub level_requirement: usize,
     /// The bodies the current body can upgrade to.
+    #[serde(default)]
     pub upgrades: Vec<BodyIdentityIds>,
     /// The inherent speed of the tank.
     pub speed: f32,
@@ -60,18 +59,41 @@ pub struct BodyIdentity {
     pub body_damage: f32,
     /// The absorption factor of the tank.
     pub absorption_factor: f32,
+    /// The rate at which the opacity decreases per tick while still, or -1 if the body cannot go invisible.
+    #[serde(default = "default_invisibility_rate")]
+    pub invisibility_rate: f32,
+    /// How much body damage grows with speed. At top speed, body damage is multiplied by `1 + ramming_factor`.
+    #[serde(default)]
+    pub ramming_factor: f32,
+    /// The ability granted by the body, unless the turret grants one.
+    #[serde(default)]
+    pub ability: Option<Ability>,
+    /// How the body stores and regenerates energy.
+    #[serde(default)]
+    pub energy: EnergyRules,
+    /// The automatic turrets mounted on the body.
+    #[serde(default)]
+    pub turrets: Vec<TurretIdentity>,
     /// A description of the body.
-    pub description: &'static str
+    pub description: String
 }
 
-into_structure! {
-    #[derive(Debug, Default, Clone, Copy, PartialEq, num_enum::TryFromPrimitive)]
-    #[repr(usize)]
-    pub enum BodyIdentityIds {
-        #[default]
-        Base       = 0,
-        Smasher    = 1,
-    }   
+fn default_invisibility_rate() -> f32 {
+    -1.0
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, num_enum::TryFromPrimitive, strum_macros::EnumIter, Serialize, Deserialize)]
+#[repr(usize)]
+pub enum BodyIdentityIds {
+    #[default]
+    Base       = 0,
+    Smasher    = 1,
+    Spike      = 2,
+    Landmine   = 3,
+    BatteringRam = 4,
+    Tank       = 5,
+    Juggernaut = 6,
+    AutoSmasher = 7,
 }
 
 impl Display for BodyIdentityIds {
@@ -93,33 +115,10 @@ impl Display for BodyIdentityIds {
     }
 }
 
-pub fn get_body_base_identity() -> BodyIdentity {
-    BodyIdentity {
-        id: BodyIdentityIds::Base,
-        render_hints: vec![],
-        level_requirement: 0,
-        upgrades: vec![BodyIdentityIds::Smasher],
-        speed: 1.0,
-        max_health: 50.0,
-        body_damage: 1.0,
-        absorption_factor: 1.0,
-        description: "Null and void."
-    }
-}
+impl TryFrom<BodyIdentityIds> for BodyIdentity {
+    type Error = ();
 
-pub fn get_body_smasher_identity() -> BodyIdentity {
-    BodyIdentity {
-        id: BodyIdentityIds::Smasher,
-        render_hints: vec![BodyRenderingHints::SmasherGuard {
-            thickness: 1.15,
-            sides: 6
-        }],
-        level_requirement: 0,
-        upgrades: vec![],
-        speed: 1.0,
-        max_health: 55.0,
-        body_damage: 1.2,
-        absorption_factor: 0.95,
-        description: "Takes less knockback and slightly increased health/body damage."
+    fn try_from(value: BodyIdentityIds) -> Result<BodyIdentity, Self::Error> {
+        get_definitions().bodies.get(&value).cloned().ok_or(())
     }
-}
+}
\ No newline at end of file
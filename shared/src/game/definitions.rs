@@ -0,0 +1,393 @@
+use std::{collections::HashMap, fmt::{Debug, Display}, hash::Hash, path::Path, sync::{Arc, LazyLock, RwLock}};
+use serde::{de::DeserializeOwned, Deserialize};
+use strum::IntoEnumIterator;
+
+use super::{body::{BodyIdentity, BodyIdentityIds}, orb::{OrbIdentity, OrbIdentityIds}, quests::QuestDefinition, turret::{TurretIdentityIds, TurretStructure}};
+
+/// The turret definitions compiled into the binary.
+pub const EMBEDDED_TURRETS: &str = include_str!("../../data/turrets.json");
+/// The body definitions compiled into the binary.
+pub const EMBEDDED_BODIES: &str = include_str!("../../data/bodies.json");
+/// The orb definitions compiled into the binary.
+pub const EMBEDDED_ORBS: &str = include_str!("../../data/orbs.json");
+/// The quest definitions compiled into the binary.
+pub const EMBEDDED_QUESTS: &str = include_str!("../../data/quests.json");
+
+/// The definitions currently in use.
+/// Starts out as the embedded definitions and may be swapped at runtime.
+static DEFINITIONS: LazyLock<RwLock<Arc<Definitions>>> = LazyLock::new(|| {
+    RwLock::new(Arc::new(Definitions::embedded()))
+});
+
+/// Gets the definitions currently in use.
+pub fn get_definitions() -> Arc<Definitions> {
+    DEFINITIONS.read().unwrap().clone()
+}
+
+/// Replaces the definitions currently in use.
+/// Only identities converted after this call are affected.
+pub fn set_definitions(definitions: Definitions) {
+    *DEFINITIONS.write().unwrap() = Arc::new(definitions);
+}
+
+/// A file containing definitions.
+#[derive(Debug, Clone, Copy, PartialEq, strum_macros::EnumIter)]
+pub enum DefinitionFile {
+    Turrets,
+    Bodies,
+    Orbs,
+    Quests
+}
+
+impl DefinitionFile {
+    /// The name of the file, relative to the definitions directory.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            DefinitionFile::Turrets => "turrets.json",
+            DefinitionFile::Bodies => "bodies.json",
+            DefinitionFile::Orbs => "orbs.json",
+            DefinitionFile::Quests => "quests.json"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefinitionError {
+    /// The file could not be read.
+    Read(DefinitionFile, String),
+    /// The file is not valid JSON or does not match the schema.
+    Parse(DefinitionFile, String),
+    /// An identity is defined more than once.
+    Duplicate(DefinitionFile, String),
+    /// An identity is not defined.
+    Missing(DefinitionFile, String)
+}
+
+impl Display for DefinitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefinitionError::Read(file, error) | DefinitionError::Parse(file, error) => write!(f, "{}: {}", file.file_name(), error),
+            DefinitionError::Duplicate(file, id) => write!(f, "{}: {} is defined more than once", file.file_name(), id),
+            DefinitionError::Missing(file, id) => write!(f, "{}: {} is not defined", file.file_name(), id)
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+#[derive(Deserialize)]
+struct TurretFile {
+    turrets: Vec<TurretStructure>
+}
+
+#[derive(Deserialize)]
+struct BodyFile {
+    bodies: Vec<BodyIdentity>
+}
+
+#[derive(Deserialize)]
+struct OrbFile {
+    orbs: Vec<OrbIdentity>
+}
+
+#[derive(Deserialize)]
+struct QuestFile {
+    quests: Vec<QuestDefinition>
+}
+
+/// Every turret, body and orb identity, keyed by ID, along with every quest.
+#[derive(Debug, Clone, Default)]
+pub struct Definitions {
+    pub turrets: HashMap<TurretIdentityIds, TurretStructure>,
+    pub bodies: HashMap<BodyIdentityIds, BodyIdentity>,
+    pub orbs: HashMap<OrbIdentityIds, OrbIdentity>,
+    /// Every quest, in the order they are defined.
+    pub quests: Vec<QuestDefinition>
+}
+
+impl Definitions {
+    /// Parses definitions from the contents of the turret, body, orb and quest files.
+    pub fn parse(turrets: &str, bodies: &str, orbs: &str, quests: &str) -> Result<Definitions, DefinitionError> {
+        let turrets: TurretFile = parse_file(DefinitionFile::Turrets, turrets)?;
+        let bodies: BodyFile = parse_file(DefinitionFile::Bodies, bodies)?;
+        let orbs: OrbFile = parse_file(DefinitionFile::Orbs, orbs)?;
+        let quests: QuestFile = parse_file(DefinitionFile::Quests, quests)?;
+
+        if let Some(body) = bodies.bodies.iter().find(|body| body.turrets.iter().any(|turret| turret.auto.is_none())) {
+            return Err(DefinitionError::Parse(DefinitionFile::Bodies, format!("{:?} mounts a turret which is not automatic", body.id)));
+        }
+
+        for (i, quest) in quests.quests.iter().enumerate() {
+            if quests.quests[..i].iter().any(|other| other.id == quest.id) {
+                return Err(DefinitionError::Duplicate(DefinitionFile::Quests, quest.id.clone()));
+            }
+        }
+
+        Ok(Definitions {
+            turrets: index_by_id(DefinitionFile::Turrets, turrets.turrets, |turret| turret.id)?,
+            bodies: index_by_id(DefinitionFile::Bodies, bodies.bodies, |body| body.id)?,
+            orbs: index_by_id(DefinitionFile::Orbs, orbs.orbs, |orb| orb.id)?,
+            quests: quests.quests
+        })
+    }
+
+    /// Reads and parses the definition files in `directory`.
+    pub fn load_dir(directory: &Path) -> Result<Definitions, DefinitionError> {
+        let read = |file: DefinitionFile| std::fs::read_to_string(directory.join(file.file_name()))
+            .map_err(|error| DefinitionError::Read(file, error.to_string()));
+
+        Definitions::parse(
+            &read(DefinitionFile::Turrets)?,
+            &read(DefinitionFile::Bodies)?,
+            &read(DefinitionFile::Orbs)?,
+            &read(DefinitionFile::Quests)?
+        )
+    }
+
+    /// The definitions compiled into the binary.
+    pub fn embedded() -> Definitions {
+        Definitions::parse(EMBEDDED_TURRETS, EMBEDDED_BODIES, EMBEDDED_ORBS, EMBEDDED_QUESTS)
+            .unwrap_or_else(|error| panic!("Embedded definitions are invalid: {}", error))
+    }
+}
+
+fn parse_file<T: DeserializeOwned>(file: DefinitionFile, contents: &str) -> Result<T, DefinitionError> {
+    serde_json::from_str(contents).map_err(|error| DefinitionError::Parse(file, error.to_string()))
+}
+
+/// Indexes identities by their ID, ensuring every ID is defined exactly once.
+fn index_by_id<K, V>(file: DefinitionFile, identities: Vec<V>, id: impl Fn(&V) -> K) -> Result<HashMap<K, V>, DefinitionError>
+where
+    K: IntoEnumIterator + Hash + Eq + Debug
+{
+    let mut map = HashMap::with_capacity(identities.len());
+    for identity in identities {
+        let key = id(&identity);
+        if map.contains_key(&key) {
+            return Err(DefinitionError::Duplicate(file, format!("{:?}", key)));
+        }
+
+        map.insert(key, identity);
+    }
+
+    if let Some(missing) = K::iter().find(|key| !map.contains_key(key)) {
+        return Err(DefinitionError::Missing(file, format!("{:?}", missing)));
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{body::BodyRenderingHints, entity::EntityType, turret::TurretRenderingHints};
+    use super::*;
+
+    fn with_turrets(turrets: &str) -> Result<Definitions, DefinitionError> {
+        Definitions::parse(turrets, EMBEDDED_BODIES, EMBEDDED_ORBS, EMBEDDED_QUESTS)
+    }
+
+    /// Builds a turret file from the embedded one, with `id` replaced by `turret`.
+    fn replace_turret(id: TurretIdentityIds, turret: serde_json::Value) -> String {
+        let mut file: serde_json::Value = serde_json::from_str(EMBEDDED_TURRETS).unwrap();
+        let turrets = file["turrets"].as_array_mut().unwrap();
+        let index = turrets.iter().position(|t| t["id"] == format!("{:?}", id)).unwrap();
+        turrets[index] = turret;
+
+        file.to_string()
+    }
+
+    #[test]
+    fn embedded_definitions_are_complete() {
+        let definitions = Definitions::embedded();
+
+        assert_eq!(definitions.turrets.len(), TurretIdentityIds::iter().count());
+        assert_eq!(definitions.bodies.len(), BodyIdentityIds::iter().count());
+        assert_eq!(definitions.orbs.len(), OrbIdentityIds::iter().count());
+        assert!(!definitions.quests.is_empty());
+
+        for (id, turret) in definitions.turrets.iter() {
+            assert_eq!(*id, turret.id);
+        }
+    }
+
+    #[test]
+    fn embedded_definitions_match_known_values() {
+        let definitions = Definitions::embedded();
+
+        let mono = &definitions.turrets[&TurretIdentityIds::Mono];
+        assert_eq!(mono.turrets.len(), 1);
+        assert_eq!(mono.turrets[0].length, 57.0);
+        assert_eq!(mono.turrets[0].projectile_identity.projectile_type, EntityType::Bullet);
+        assert!(mono.upgrades.contains(&TurretIdentityIds::Sniper));
+
+        let spawner = &definitions.turrets[&TurretIdentityIds::Spawner];
+        assert_eq!(spawner.turrets[0].rendering_hints, vec![TurretRenderingHints::Trapezoidal(0.0)]);
+        assert_eq!(spawner.turrets[0].max_projectiles, 4);
+        assert_eq!(spawner.level_requirement, 15);
+
+        let smasher = &definitions.bodies[&BodyIdentityIds::Smasher];
+        assert_eq!(smasher.render_hints, vec![BodyRenderingHints::SmasherGuard { thickness: 1.15, sides: 6 }]);
+
+        assert_eq!(definitions.orbs[&OrbIdentityIds::Radiant].exp_yield, 2000);
+    }
+
+    #[test]
+    fn conversions_use_definitions() {
+        let turret: TurretStructure = TurretIdentityIds::Sniper.try_into().unwrap();
+        assert_eq!(turret.id, TurretIdentityIds::Sniper);
+
+        let body: BodyIdentity = BodyIdentityIds::Base.try_into().unwrap();
+        assert_eq!(body.upgrades, vec![BodyIdentityIds::Smasher, BodyIdentityIds::Tank]);
+
+        let orb: OrbIdentity = OrbIdentityIds::Basic.try_into().unwrap();
+        assert_eq!(orb.id, OrbIdentityIds::Basic);
+    }
+
+    #[test]
+    fn optional_fields_use_defaults() {
+        let turrets = replace_turret(TurretIdentityIds::Mono, serde_json::json!({
+            "id": "Mono",
+            "turrets": [{
+                "angle": 0.0,
+                "width": 24.0,
+                "length": 57.0,
+                "reload": 1.0,
+                "recoil": 1.0,
+                "mouse_controllable": true,
+                "max_projectiles": -1,
+                "projectile_identity": {
+                    "projectile_type": "Bullet",
+                    "size_factor": 1.0,
+                    "health": 1.0,
+                    "damage": 1.0,
+                    "speed": 1.0,
+                    "scatter_rate": 1.0,
+                    "lifetime": 1.0,
+                    "absorption_factor": 1.0
+                }
+            }],
+            "level_requirement": 0,
+            "description": "Shoots bullets.",
+            "fov": 1.0,
+            "invisibility_rate": -1.0
+        }));
+
+        let definitions = with_turrets(&turrets).unwrap();
+        let mono = &definitions.turrets[&TurretIdentityIds::Mono];
+
+        assert!(mono.upgrades.is_empty());
+        assert!(mono.upgrade_message.is_empty());
+        assert_eq!(mono.turrets[0].x_offset, 0.0);
+        assert_eq!(mono.turrets[0].delay, 0.0);
+        assert!(!mono.turrets[0].force_shoot);
+        assert!(mono.turrets[0].rendering_hints.is_empty());
+        assert_eq!(mono.turrets[0].projectiles_spawned, 0);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let definitions = Definitions::embedded();
+
+        let mut turrets: Vec<_> = definitions.turrets.values().cloned().collect();
+        turrets.sort_by_key(|turret| turret.id as usize);
+        let file = serde_json::json!({ "turrets": turrets }).to_string();
+
+        let reparsed = with_turrets(&file).unwrap();
+        for (id, turret) in definitions.turrets.iter() {
+            assert_eq!(&reparsed.turrets[id], turret);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(matches!(
+            with_turrets("{ \"turrets\": [ }"),
+            Err(DefinitionError::Parse(DefinitionFile::Turrets, _))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let mut mono = serde_json::to_value(&Definitions::embedded().turrets[&TurretIdentityIds::Mono]).unwrap();
+        mono["reload_speed"] = serde_json::json!(2.0);
+
+        assert!(matches!(
+            with_turrets(&replace_turret(TurretIdentityIds::Mono, mono)),
+            Err(DefinitionError::Parse(DefinitionFile::Turrets, _))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_ids() {
+        let mut mono = serde_json::to_value(&Definitions::embedded().turrets[&TurretIdentityIds::Mono]).unwrap();
+        mono["upgrades"] = serde_json::json!(["Mega"]);
+
+        assert!(matches!(
+            with_turrets(&replace_turret(TurretIdentityIds::Mono, mono)),
+            Err(DefinitionError::Parse(DefinitionFile::Turrets, _))
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_ids() {
+        let sniper = serde_json::to_value(&Definitions::embedded().turrets[&TurretIdentityIds::Sniper]).unwrap();
+
+        assert_eq!(
+            with_turrets(&replace_turret(TurretIdentityIds::Mono, sniper)).unwrap_err(),
+            DefinitionError::Duplicate(DefinitionFile::Turrets, "Sniper".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_missing_ids() {
+        let mut file: serde_json::Value = serde_json::from_str(EMBEDDED_ORBS).unwrap();
+        file["orbs"].as_array_mut().unwrap().retain(|orb| orb["id"] != "Heavy");
+
+        assert_eq!(
+            Definitions::parse(EMBEDDED_TURRETS, EMBEDDED_BODIES, &file.to_string(), EMBEDDED_QUESTS).unwrap_err(),
+            DefinitionError::Missing(DefinitionFile::Orbs, "Heavy".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_quests() {
+        let mut file: serde_json::Value = serde_json::from_str(EMBEDDED_QUESTS).unwrap();
+        let quests = file["quests"].as_array_mut().unwrap();
+        quests.push(quests[0].clone());
+
+        assert_eq!(
+            Definitions::parse(EMBEDDED_TURRETS, EMBEDDED_BODIES, EMBEDDED_ORBS, &file.to_string()).unwrap_err(),
+            DefinitionError::Duplicate(DefinitionFile::Quests, file["quests"][0]["id"].as_str().unwrap().to_string())
+        );
+    }
+
+    #[test]
+    fn loads_directories() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+        let definitions = Definitions::load_dir(&data).unwrap();
+        assert_eq!(definitions.turrets.len(), TurretIdentityIds::iter().count());
+
+        assert!(matches!(
+            Definitions::load_dir(&data.join("missing")).unwrap_err(),
+            DefinitionError::Read(DefinitionFile::Turrets, _)
+        ));
+    }
+
+    #[test]
+    fn body_turrets_are_mounted_and_automatic() {
+        let body = BodyIdentity::try_from(BodyIdentityIds::AutoSmasher).unwrap();
+        let structure = TurretStructure::try_from(TurretIdentityIds::Mono).unwrap().with_body(&body);
+        assert_eq!(structure.turrets.len(), 2);
+        assert!(structure.turrets[1].auto.is_some());
+
+        let mut file: serde_json::Value = serde_json::from_str(EMBEDDED_BODIES).unwrap();
+        let bodies = file["bodies"].as_array_mut().unwrap();
+        let index = bodies.iter().position(|body| body["id"] == "AutoSmasher").unwrap();
+        bodies[index]["turrets"][0].as_object_mut().unwrap().remove("auto");
+
+        assert!(matches!(
+            Definitions::parse(EMBEDDED_TURRETS, &file.to_string(), EMBEDDED_ORBS, EMBEDDED_QUESTS).unwrap_err(),
+            DefinitionError::Parse(DefinitionFile::Bodies, _)
+        ));
+    }
+}
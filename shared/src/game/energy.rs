@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use crate::utils::consts::TICK_RATE;
+
+/// How a body stores and regenerates energy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnergyRules {
+    /// The most energy the tank can hold.
+    pub max_energy: f32,
+    /// The energy regenerated per second.
+    pub regen: f32,
+    /// The number of seconds after spending energy before it starts regenerating.
+    pub regen_delay: f32,
+    /// Multiplier for regeneration while the tank is still.
+    pub still_multiplier: f32
+}
+
+impl Default for EnergyRules {
+    fn default() -> EnergyRules {
+        EnergyRules {
+            max_energy: 100.0,
+            regen: 5.0,
+            regen_delay: 1.5,
+            still_multiplier: 1.5
+        }
+    }
+}
+
+impl EnergyRules {
+    pub fn get_regen_per_tick(&self, ticks_since_spent: u64, is_still: bool) -> f32 {
+        if (ticks_since_spent as f32) < self.regen_delay * TICK_RATE as f32 {
+            return 0.0;
+        }
+
+        let regen = self.regen / TICK_RATE as f32;
+        if is_still { regen * self.still_multiplier } else { regen }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn energy_regenerates_after_the_delay() {
+        let rules = EnergyRules::default();
+        let delay = (rules.regen_delay * TICK_RATE as f32).ceil() as u64;
+
+        assert_eq!(rules.get_regen_per_tick(delay - 1, false), 0.0);
+        assert!((rules.get_regen_per_tick(delay, false) * TICK_RATE as f32 - rules.regen).abs() < 1e-4);
+        assert!(rules.get_regen_per_tick(delay, true) > rules.get_regen_per_tick(delay, false));
+    }
+}
\ No newline at end of file
@@ -1,8 +1,9 @@
 use std::fmt::Debug;
 
 use derive_new::new as New;
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
-use crate::{connection::packets::Inputs, utils::{color::Color, consts::MAX_LEVEL, interpolatable::Interpolatable, vec2::Vector2D}};
+use crate::{connection::packets::{GameplayEventType, Inputs}, utils::{color::Color, consts::MAX_LEVEL, interpolatable::Interpolatable, vec2::Vector2D}};
 
 use super::{body::BodyIdentityIds, turret::TurretIdentityIds};
 
@@ -42,7 +43,7 @@ impl Debug for InputFlags {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, num_enum::TryFromPrimitive)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, num_enum::TryFromPrimitive, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum EntityType {
     #[default]
@@ -50,7 +51,10 @@ pub enum EntityType {
     Bullet,
     Drone,
     Trap,
-    Orb
+    Orb,
+    Missile,
+    Minion,
+    Mine
     // Planet,
     // Star,
     // Comet
@@ -58,11 +62,11 @@ pub enum EntityType {
 
 impl EntityType {
     pub fn is_projectile(&self) -> bool {
-        matches!(self, EntityType::Bullet | EntityType::Drone | EntityType::Trap)
+        matches!(self, EntityType::Bullet | EntityType::Drone | EntityType::Trap | EntityType::Missile | EntityType::Minion | EntityType::Mine)
     }
 
     pub fn is_drone(&self) -> bool {
-        matches!(self, EntityType::Drone)
+        matches!(self, EntityType::Drone | EntityType::Minion)
     }
 }
 
@@ -167,12 +171,88 @@ pub struct Notification {
     pub position: Interpolatable<Vector2D>
 }
 
+/// Something which happened during a tick that clients may react to,
+/// such as by animating a turret or showing a hit marker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameplayEvent {
+    /// The turret at index `turret` of entity `id` fired a projectile.
+    TurretFired { id: u32, turret: usize },
+    /// `victim` took `damage`, credited to `attacker` if it is known.
+    Hit { attacker: Option<u32>, victim: u32, damage: f32 },
+    LevelUp(usize),
+    /// A new body or turret upgrade became available.
+    UpgradeAvailable,
+    /// Entity `id` destroyed a radiant orb, the objective at the center of the arena.
+    ObjectiveCaptured { id: u32 }
+}
+
+impl GameplayEvent {
+    pub fn get_type(&self) -> GameplayEventType {
+        match self {
+            GameplayEvent::TurretFired { .. } => GameplayEventType::TurretFired,
+            GameplayEvent::Hit { .. } => GameplayEventType::Hit,
+            GameplayEvent::LevelUp(_) => GameplayEventType::LevelUp,
+            GameplayEvent::UpgradeAvailable => GameplayEventType::UpgradeAvailable,
+            GameplayEvent::ObjectiveCaptured { .. } => GameplayEventType::ObjectiveCaptured
+        }
+    }
+}
+
+/// The kinds of objectives pointed out on the minimap and at the edges of the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum MarkerKind {
+    /// The player at the top of the leaderboard.
+    Leader,
+    Clanmate,
+    /// A clanmate's call for help.
+    Distress,
+    /// A radiant orb near the player.
+    Objective
+}
+
+/// An objective pointed out to a single player.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker {
+    pub kind: MarkerKind,
+    /// The entity the marker belongs to, which stays the same while it moves.
+    pub id: u32,
+    pub position: Vector2D,
+    pub color: Color
+}
+
+/// What a spectator's camera follows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum SpectateMode {
+    /// The player at the top of the leaderboard.
+    #[default]
+    Leader,
+    /// A player picked by cycling through everyone alive.
+    Player,
+    /// Panned freely with the movement keys.
+    Free
+}
+
 #[derive(Default, Clone)]
 pub struct TankUpgrades {
     pub body: Vec<BodyIdentityIds>,
     pub turret: Vec<TurretIdentityIds>
 }
 
+/// A clan's place on the clan leaderboard.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClanStanding {
+    pub name: String,
+    pub members: usize,
+    /// The combined score of every member.
+    pub score: usize,
+    /// The number of players from other clans killed by members.
+    pub kills: usize,
+    /// The kills made during the current clan war.
+    pub war_kills: usize
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct ClanInformation {
     pub id: u32,
@@ -0,0 +1,299 @@
+use strum::EnumCount;
+use crate::utils::color::Color;
+
+/// The number of unequipped items a player can carry.
+pub const INVENTORY_CAPACITY: usize = 8;
+
+/// The kinds of gear, each of which boosts a single stat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, num_enum::TryFromPrimitive, strum_macros::EnumCount, strum_macros::EnumIter)]
+#[repr(u8)]
+pub enum GearKind {
+    /// Boosts maximum health.
+    Plating,
+    /// Boosts body damage.
+    Spikes,
+    /// Boosts health regeneration.
+    Medkit,
+    /// Boosts movement speed.
+    Thrusters,
+    /// Boosts maximum energy.
+    Capacitor,
+    /// Boosts projectile damage.
+    Barrel,
+    /// Boosts projectile speed.
+    Accelerator,
+    /// Shortens the time between shots.
+    Autoloader
+}
+
+/// Where a piece of gear is equipped. Only one piece can be equipped per slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, num_enum::TryFromPrimitive, strum_macros::EnumCount, strum_macros::EnumIter)]
+#[repr(u8)]
+pub enum GearSlot {
+    Hull,
+    Engine,
+    Weapon
+}
+
+impl GearKind {
+    pub fn slot(&self) -> GearSlot {
+        match self {
+            GearKind::Plating | GearKind::Spikes | GearKind::Medkit => GearSlot::Hull,
+            GearKind::Thrusters | GearKind::Capacitor => GearSlot::Engine,
+            GearKind::Barrel | GearKind::Accelerator | GearKind::Autoloader => GearSlot::Weapon
+        }
+    }
+
+    /// The fraction by which common gear of this kind boosts its stat.
+    pub fn base_bonus(&self) -> f32 {
+        match self {
+            GearKind::Plating => 0.1,
+            GearKind::Spikes => 0.15,
+            GearKind::Medkit => 0.25,
+            GearKind::Thrusters => 0.06,
+            GearKind::Capacitor => 0.2,
+            GearKind::Barrel => 0.1,
+            GearKind::Accelerator => 0.1,
+            GearKind::Autoloader => 0.08
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GearKind::Plating => "Plating",
+            GearKind::Spikes => "Spikes",
+            GearKind::Medkit => "Medkit",
+            GearKind::Thrusters => "Thrusters",
+            GearKind::Capacitor => "Capacitor",
+            GearKind::Barrel => "Barrel",
+            GearKind::Accelerator => "Accelerator",
+            GearKind::Autoloader => "Autoloader"
+        }
+    }
+
+    /// The stat the gear boosts, as shown to the player.
+    pub fn stat_name(&self) -> &'static str {
+        match self {
+            GearKind::Plating => "Max Health",
+            GearKind::Spikes => "Body Damage",
+            GearKind::Medkit => "Health Regen",
+            GearKind::Thrusters => "Movement Speed",
+            GearKind::Capacitor => "Max Energy",
+            GearKind::Barrel => "Projectile Damage",
+            GearKind::Accelerator => "Projectile Speed",
+            GearKind::Autoloader => "Reload"
+        }
+    }
+}
+
+impl GearSlot {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GearSlot::Hull => "Hull",
+            GearSlot::Engine => "Engine",
+            GearSlot::Weapon => "Weapon"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, num_enum::TryFromPrimitive, strum_macros::EnumCount, strum_macros::EnumIter)]
+#[repr(u8)]
+pub enum GearRarity {
+    Common,
+    Rare,
+    Epic,
+    Legendary
+}
+
+impl GearRarity {
+    /// The multiplier applied to the base bonus of the gear.
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            GearRarity::Common => 1.0,
+            GearRarity::Rare => 1.5,
+            GearRarity::Epic => 2.25,
+            GearRarity::Legendary => 3.5
+        }
+    }
+
+    /// Picks a rarity given a roll in `[0, 1)`.
+    /// Common gear drops 60% of the time, rare 28%, epic 10% and legendary 2%.
+    pub fn from_roll(roll: f32) -> GearRarity {
+        match roll {
+            r if r < 0.6 => GearRarity::Common,
+            r if r < 0.88 => GearRarity::Rare,
+            r if r < 0.98 => GearRarity::Epic,
+            _ => GearRarity::Legendary
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GearRarity::Common => "Common",
+            GearRarity::Rare => "Rare",
+            GearRarity::Epic => "Epic",
+            GearRarity::Legendary => "Legendary"
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            GearRarity::Common => Color::MATERIAL_SILVER,
+            GearRarity::Rare => Color::MATERIAL_CYAN,
+            GearRarity::Epic => Color::MATERIAL_PURPLE,
+            GearRarity::Legendary => Color::MATERIAL_ORANGE
+        }
+    }
+}
+
+/// A single item of gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gear {
+    pub kind: GearKind,
+    pub rarity: GearRarity
+}
+
+impl Gear {
+    /// The fraction by which the gear boosts its stat.
+    pub fn bonus(&self) -> f32 {
+        self.kind.base_bonus() * self.rarity.multiplier()
+    }
+}
+
+/// The gear a player carries and has equipped.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Inventory {
+    pub items: Vec<Gear>,
+    pub equipped: [Option<Gear>; GearSlot::COUNT]
+}
+
+impl Inventory {
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= INVENTORY_CAPACITY
+    }
+
+    /// Adds an item to the inventory, returning whether there was room for it.
+    pub fn add(&mut self, gear: Gear) -> bool {
+        if self.is_full() {
+            return false;
+        }
+
+        self.items.push(gear);
+        true
+    }
+
+    /// Equips the item at `index`, swapping out whatever was equipped in its slot.
+    pub fn equip(&mut self, index: usize) -> bool {
+        if index >= self.items.len() {
+            return false;
+        }
+
+        let gear = self.items.remove(index);
+        if let Some(old) = self.equipped[gear.kind.slot() as usize].replace(gear) {
+            self.items.insert(index, old);
+        }
+
+        true
+    }
+
+    /// Moves the item in `slot` back into the inventory, if there is room for it.
+    pub fn unequip(&mut self, slot: GearSlot) -> bool {
+        if self.is_full() {
+            return false;
+        }
+
+        match self.equipped[slot as usize].take() {
+            Some(gear) => {
+                self.items.push(gear);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Removes every item at `indices`, or nothing if any index is invalid or repeated.
+    pub fn take(&mut self, indices: &[usize]) -> Option<Vec<Gear>> {
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        if sorted.len() != indices.len() || sorted.last().is_some_and(|&index| index >= self.items.len()) {
+            return None;
+        }
+
+        Some(sorted.into_iter().rev().map(|index| self.items.remove(index)).collect())
+    }
+
+    pub fn discard(&mut self, index: usize) -> Option<Gear> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+
+    /// The multiplier applied to the stat boosted by `kind`.
+    pub fn get_multiplier(&self, kind: GearKind) -> f32 {
+        match self.equipped[kind.slot() as usize] {
+            Some(gear) if gear.kind == kind => 1.0 + gear.bonus(),
+            _ => 1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equipping_swaps_items_in_the_same_slot() {
+        let mut inventory = Inventory::default();
+        inventory.add(Gear { kind: GearKind::Plating, rarity: GearRarity::Common });
+        inventory.add(Gear { kind: GearKind::Spikes, rarity: GearRarity::Epic });
+
+        assert!(inventory.equip(0));
+        assert_eq!(inventory.items.len(), 1);
+        assert!((inventory.get_multiplier(GearKind::Plating) - 1.1).abs() < 1e-4);
+
+        assert!(inventory.equip(0));
+        assert_eq!(inventory.items, vec![Gear { kind: GearKind::Plating, rarity: GearRarity::Common }]);
+        assert_eq!(inventory.get_multiplier(GearKind::Plating), 1.0);
+        assert!(inventory.get_multiplier(GearKind::Spikes) > 1.0);
+
+        assert!(inventory.unequip(GearSlot::Hull));
+        assert!(!inventory.unequip(GearSlot::Hull));
+        assert_eq!(inventory.items.len(), 2);
+    }
+
+    #[test]
+    fn full_inventories_reject_items() {
+        let mut inventory = Inventory::default();
+        for _ in 0..INVENTORY_CAPACITY {
+            assert!(inventory.add(Gear { kind: GearKind::Barrel, rarity: GearRarity::Rare }));
+        }
+
+        assert!(!inventory.add(Gear { kind: GearKind::Barrel, rarity: GearRarity::Rare }));
+        assert!(inventory.discard(INVENTORY_CAPACITY).is_none());
+        assert!(inventory.discard(0).is_some());
+    }
+
+    #[test]
+    fn taking_items_rejects_invalid_indices() {
+        let mut inventory = Inventory::default();
+        inventory.add(Gear { kind: GearKind::Medkit, rarity: GearRarity::Common });
+        inventory.add(Gear { kind: GearKind::Capacitor, rarity: GearRarity::Legendary });
+        inventory.add(Gear { kind: GearKind::Thrusters, rarity: GearRarity::Rare });
+
+        assert!(inventory.take(&[0, 0]).is_none());
+        assert!(inventory.take(&[1, 3]).is_none());
+        assert_eq!(inventory.items.len(), 3);
+
+        let taken = inventory.take(&[2, 0]).unwrap();
+        assert_eq!(taken.len(), 2);
+        assert_eq!(inventory.items, vec![Gear { kind: GearKind::Capacitor, rarity: GearRarity::Legendary }]);
+    }
+
+    #[test]
+    fn rarity_rolls_cover_every_rarity() {
+        assert_eq!(GearRarity::from_roll(0.0), GearRarity::Common);
+        assert_eq!(GearRarity::from_roll(0.7), GearRarity::Rare);
+        assert_eq!(GearRarity::from_roll(0.9), GearRarity::Epic);
+        assert_eq!(GearRarity::from_roll(0.99), GearRarity::Legendary);
+    }
+}
\ No newline at end of file
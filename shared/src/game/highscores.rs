@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use super::profile::Run;
+
+/// The number of runs in every high-score table.
+pub const HIGH_SCORE_COUNT: usize = 10;
+const DAY: u64 = 24 * 60 * 60;
+
+/// How far back a high-score table looks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, num_enum::TryFromPrimitive, strum_macros::EnumIter)]
+#[repr(u8)]
+pub enum HighScorePeriod {
+    #[default]
+    Daily,
+    Weekly,
+    AllTime
+}
+
+impl HighScorePeriod {
+    /// How long the period lasts in seconds, or `None` if it never ends.
+    pub fn duration(&self) -> Option<u64> {
+        match self {
+            HighScorePeriod::Daily => Some(DAY),
+            HighScorePeriod::Weekly => Some(7 * DAY),
+            HighScorePeriod::AllTime => None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HighScorePeriod::Daily => "Daily",
+            HighScorePeriod::Weekly => "Weekly",
+            HighScorePeriod::AllTime => "All-Time"
+        }
+    }
+}
+
+/// A run, along with when it ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRun {
+    #[serde(flatten)]
+    pub run: Run,
+    /// When the run ended, in seconds since the UNIX epoch.
+    pub finished_at: u64
+}
+
+/// The best runs of every period, as served over HTTP.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct HighScoreTables<'a> {
+    pub daily: Vec<&'a RecordedRun>,
+    pub weekly: Vec<&'a RecordedRun>,
+    pub all_time: Vec<&'a RecordedRun>
+}
+
+impl HighScoreTables<'_> {
+    pub fn get(&self, period: HighScorePeriod) -> &[&RecordedRun] {
+        match period {
+            HighScorePeriod::Daily => &self.daily,
+            HighScorePeriod::Weekly => &self.weekly,
+            HighScorePeriod::AllTime => &self.all_time
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Every run which may still appear in a high-score table.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RunHistory {
+    runs: Vec<RecordedRun>
+}
+
+impl RunHistory {
+    pub fn parse(json: &str) -> serde_json::Result<RunHistory> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Records a run which ended at `now`, forgetting every run which can no longer make a table.
+    /// Returns whether the history changed.
+    pub fn record(&mut self, run: Run, now: u64) -> bool {
+        let len = self.runs.len();
+        let idx = self.runs.partition_point(|recorded| recorded.run.score >= run.score);
+        self.runs.insert(idx, RecordedRun { run, finished_at: now });
+
+        // Runs are kept in the all-time top, or while they are recent and outranked by fewer than
+        // a table's worth of runs which will last at least as long as they do.
+        let mut outlasting: Vec<u64> = Vec::with_capacity(self.runs.len());
+        let mut new_run_kept = true;
+
+        let mut position = 0;
+        self.runs.retain(|recorded| {
+            let is_new = position == idx;
+            position += 1;
+
+            let at = outlasting.partition_point(|&finished_at| finished_at < recorded.finished_at);
+            let kept = outlasting.len() < HIGH_SCORE_COUNT
+                || (now - recorded.finished_at.min(now) < 7 * DAY && outlasting.len() - at < HIGH_SCORE_COUNT);
+
+            if kept {
+                outlasting.insert(at, recorded.finished_at);
+            } else if is_new {
+                new_run_kept = false;
+            }
+
+            kept
+        });
+
+        new_run_kept || self.runs.len() != len
+    }
+
+    /// The best runs which ended within `period` of `now`, from highest to lowest score.
+    pub fn get_top(&self, period: HighScorePeriod, now: u64) -> Vec<&RecordedRun> {
+        self.runs.iter()
+            .filter(|recorded| period.duration().is_none_or(|duration| now - recorded.finished_at.min(now) < duration))
+            .take(HIGH_SCORE_COUNT)
+            .collect()
+    }
+
+    pub fn get_tables(&self, now: u64) -> HighScoreTables<'_> {
+        let mut tables = HighScoreTables::default();
+        for period in HighScorePeriod::iter() {
+            let top = self.get_top(period, now);
+            match period {
+                HighScorePeriod::Daily => tables.daily = top,
+                HighScorePeriod::Weekly => tables.weekly = top,
+                HighScorePeriod::AllTime => tables.all_time = top
+            }
+        }
+
+        tables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{body::BodyIdentityIds, turret::TurretIdentityIds};
+    use super::*;
+
+    fn run(score: usize) -> Run {
+        Run {
+            name: format!("Player {}", score),
+            body: BodyIdentityIds::Base,
+            turret: TurretIdentityIds::Mono,
+            score,
+            kills: 0,
+            duration: 60,
+            achievements: vec![]
+        }
+    }
+
+    #[test]
+    fn tables_only_include_recent_runs() {
+        let mut history = RunHistory::default();
+        history.record(run(9000), 0);
+        history.record(run(5000), 6 * DAY);
+        history.record(run(1000), 7 * DAY);
+
+        let now = 7 * DAY + 1;
+        let scores = |period| history.get_top(period, now).iter().map(|recorded| recorded.run.score).collect::<Vec<_>>();
+
+        assert_eq!(scores(HighScorePeriod::Daily), vec![1000]);
+        assert_eq!(scores(HighScorePeriod::Weekly), vec![5000, 1000]);
+        assert_eq!(scores(HighScorePeriod::AllTime), vec![9000, 5000, 1000]);
+    }
+
+    #[test]
+    fn old_runs_outside_the_top_are_forgotten() {
+        let mut history = RunHistory::default();
+        for score in 1..=HIGH_SCORE_COUNT + 5 {
+            history.record(run(score * 100), 0);
+        }
+
+        assert_eq!(history.runs.len(), HIGH_SCORE_COUNT);
+        assert!(!history.record(run(10), 0));
+
+        assert!(history.record(run(50), 7 * DAY));
+        assert_eq!(history.runs.len(), HIGH_SCORE_COUNT + 1);
+        assert_eq!(history.get_top(HighScorePeriod::AllTime, 7 * DAY)[0].run.score, (HIGH_SCORE_COUNT + 5) * 100);
+
+        let parsed = RunHistory::parse(&history.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, history);
+    }
+
+    #[test]
+    fn outranked_runs_are_kept_until_they_could_make_a_table() {
+        let mut history = RunHistory::default();
+        for _ in 0..HIGH_SCORE_COUNT {
+            history.record(run(2000), 0);
+        }
+
+        assert!(history.record(run(1000), DAY));
+        assert!(history.record(run(500), DAY));
+        assert_eq!(history.runs.len(), HIGH_SCORE_COUNT + 2);
+
+        let now = 7 * DAY + 1;
+        let weekly = history.get_top(HighScorePeriod::Weekly, now).iter().map(|recorded| recorded.run.score).collect::<Vec<_>>();
+        assert_eq!(weekly, vec![1000, 500]);
+        assert_eq!(history.get_top(HighScorePeriod::AllTime, now).len(), HIGH_SCORE_COUNT);
+    }
+}
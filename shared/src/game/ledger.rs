@@ -0,0 +1,118 @@
+use crate::utils::consts::TICK_RATE;
+
+/// How long damage counts towards a kill, in ticks. Damage is worth less the older it is.
+pub const DAMAGE_LEDGER_WINDOW: u64 = 15 * TICK_RATE;
+
+/// Damage dealt by a single attacker during a single tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamageEntry {
+    pub attacker: u32,
+    pub amount: f32,
+    pub tick: u64
+}
+
+impl DamageEntry {
+    /// The amount of damage after decaying until `tick`.
+    pub fn get_weight(&self, tick: u64) -> f32 {
+        let age = tick.saturating_sub(self.tick);
+        if age >= DAMAGE_LEDGER_WINDOW {
+            return 0.0;
+        }
+
+        self.amount * (1.0 - age as f32 / DAMAGE_LEDGER_WINDOW as f32)
+    }
+}
+
+/// The damage an entity has recently taken, used to split the reward for killing it.
+#[derive(Debug, Default, Clone)]
+pub struct DamageLedger {
+    entries: Vec<DamageEntry>
+}
+
+impl DamageLedger {
+    /// Records damage from `attacker`, forgetting any damage too old to count.
+    pub fn record(&mut self, attacker: u32, amount: f32, tick: u64) {
+        self.prune(tick);
+
+        match self.entries.iter_mut().find(|entry| entry.attacker == attacker && entry.tick == tick) {
+            Some(entry) => entry.amount += amount,
+            None => self.entries.push(DamageEntry { attacker, amount, tick })
+        }
+    }
+
+    pub fn prune(&mut self, tick: u64) {
+        self.entries.retain(|entry| entry.get_weight(tick) > 0.0);
+    }
+
+    pub fn get_entries(&self) -> &[DamageEntry] {
+        &self.entries
+    }
+
+    /// The share of the credit owed to every eligible attacker, from largest to smallest.
+    /// The shares sum to 1, unless no eligible attacker has dealt any damage.
+    pub fn get_shares(&self, tick: u64, is_eligible: impl Fn(u32) -> bool) -> Vec<(u32, f32)> {
+        let mut shares: Vec<(u32, f32)> = vec![];
+
+        for entry in self.entries.iter() {
+            let weight = entry.get_weight(tick);
+            if weight <= 0.0 {
+                continue;
+            }
+
+            match shares.iter_mut().find(|(attacker, _)| *attacker == entry.attacker) {
+                Some((_, total)) => *total += weight,
+                None if is_eligible(entry.attacker) => shares.push((entry.attacker, weight)),
+                None => ()
+            }
+        }
+
+        let total: f32 = shares.iter().map(|(_, weight)| weight).sum();
+        for (_, weight) in shares.iter_mut() {
+            *weight /= total;
+        }
+
+        shares.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_damage_outweighs_old_damage() {
+        let mut ledger = DamageLedger::default();
+        ledger.record(1, 50.0, 0);
+        ledger.record(2, 50.0, DAMAGE_LEDGER_WINDOW / 2);
+
+        let shares = ledger.get_shares(DAMAGE_LEDGER_WINDOW / 2, |_| true);
+        assert_eq!(shares[0].0, 2);
+        assert!((shares[0].1 - 2.0 / 3.0).abs() < 1e-2);
+        assert!((shares.iter().map(|(_, share)| share).sum::<f32>() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn old_damage_is_forgotten() {
+        let mut ledger = DamageLedger::default();
+        ledger.record(1, 100.0, 0);
+        ledger.record(1, 5.0, 10);
+        assert_eq!(ledger.get_entries().len(), 2);
+
+        ledger.record(2, 5.0, DAMAGE_LEDGER_WINDOW);
+        assert_eq!(ledger.get_entries().len(), 2);
+        assert_eq!(ledger.get_shares(DAMAGE_LEDGER_WINDOW + 10, |_| true), vec![(2, 1.0)]);
+    }
+
+    #[test]
+    fn ineligible_attackers_get_nothing() {
+        let mut ledger = DamageLedger::default();
+        ledger.record(1, 30.0, 0);
+        ledger.record(2, 10.0, 0);
+        ledger.record(2, 10.0, 0);
+
+        assert_eq!(ledger.get_entries().len(), 2);
+        assert_eq!(ledger.get_shares(0, |attacker| attacker == 2), vec![(2, 1.0)]);
+        assert!(ledger.get_shares(0, |_| false).is_empty());
+    }
+}
\ No newline at end of file
@@ -2,4 +2,17 @@ pub mod entity;
 pub mod turret;
 pub mod body;
 pub mod theme;
-pub mod orb;
\ No newline at end of file
+pub mod orb;
+pub mod movement;
+pub mod definitions;
+pub mod upgrade_tree;
+pub mod balance;
+pub mod status;
+pub mod ability;
+pub mod energy;
+pub mod gear;
+pub mod quests;
+pub mod ledger;
+pub mod world;
+pub mod profile;
+pub mod highscores;
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::{connection::packets::Inputs, utils::{consts::FRICTION, vec2::Vector2D}};
+
+use super::entity::InputFlags;
+
+/// The kinematic state of a tank.
+/// Shared by the server and the client's prediction so both integrate identically.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MovementState {
+    pub position: Vector2D,
+    pub velocity: Vector2D,
+    /// Velocity which is added on top of the tank's own, unaffected by friction.
+    pub additional_velocity: Vector2D
+}
+
+impl MovementState {
+    pub fn new(position: Vector2D, velocity: Vector2D, additional_velocity: Vector2D) -> MovementState {
+        MovementState { position, velocity, additional_velocity }
+    }
+
+    /// Simulates one tick of tank movement, in the same order the server does:
+    /// friction and integration first, then any impulse from an ability, then acceleration from the inputs.
+    pub fn tick_tank(&mut self, inputs: InputFlags, speed: f32, impulse: Vector2D, arena_size: f32) {
+        integrate(&mut self.position, &mut self.velocity, self.additional_velocity, Some(arena_size));
+        self.velocity += impulse;
+        accelerate(&mut self.velocity, get_movement_direction(inputs), speed);
+    }
+}
+
+/// Gets the direction a tank wants to move in given its inputs.
+/// The vector is not normalized.
+pub fn get_movement_direction(inputs: InputFlags) -> Vector2D {
+    let mut movement = Vector2D::ZERO;
+
+    if inputs.is_set(Inputs::Up) {
+        movement.y -= 1.0;
+    }
+
+    if inputs.is_set(Inputs::Down) {
+        movement.y += 1.0;
+    }
+
+    if inputs.is_set(Inputs::Left) {
+        movement.x -= 1.0;
+    }
+
+    if inputs.is_set(Inputs::Right) {
+        movement.x += 1.0;
+    }
+
+    movement
+}
+
+/// Applies friction to the velocity and moves the position by it.
+/// The position is clamped to `[0, arena_size]` if an arena size is given.
+pub fn integrate(position: &mut Vector2D, velocity: &mut Vector2D, additional_velocity: Vector2D, arena_size: Option<f32>) {
+    *velocity *= 1.0 - FRICTION;
+    *position += *velocity + additional_velocity;
+
+    if let Some(arena_size) = arena_size {
+        position.constrain(0.0, arena_size);
+    }
+}
+
+/// Accelerates the velocity in the direction of movement.
+pub fn accelerate(velocity: &mut Vector2D, mut movement: Vector2D, speed: f32) {
+    movement.set_magnitude(speed);
+    *velocity += movement;
+}
+
+/// Computes the speed of a tank given its body speed, movement speed investment and level.
+pub fn get_tank_speed(body_speed: f32, movement_speed_investment: usize, level: usize) -> f32 {
+    body_speed * 2.55 * 1.07_f32.powf(movement_speed_investment as f32)
+        / 1.015_f32.powf((level.max(1) - 1) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integration_applies_friction_and_clamps() {
+        let (mut position, mut velocity) = (Vector2D::new(10.0, 10.0), Vector2D::new(-20.0, 4.0));
+        integrate(&mut position, &mut velocity, Vector2D::new(0.0, 1.0), Some(100.0));
+
+        assert_eq!(velocity, Vector2D::new(-20.0, 4.0) * (1.0 - FRICTION));
+        assert_eq!(position.x, 0.0);
+        assert!((position.y - (10.0 + 4.0 * (1.0 - FRICTION) + 1.0)).abs() < 1e-4);
+
+        let mut unbounded = Vector2D::new(10.0, 10.0);
+        integrate(&mut unbounded, &mut Vector2D::new(-20.0, 0.0), Vector2D::ZERO, None);
+        assert!(unbounded.x < 0.0);
+    }
+
+    #[test]
+    fn acceleration_is_normalized() {
+        let mut velocity = Vector2D::ZERO;
+        accelerate(&mut velocity, get_movement_direction(InputFlags::new(Inputs::Up as u32 | Inputs::Right as u32)), 2.0);
+        assert!((velocity.magnitude() - 2.0).abs() < 1e-4);
+        assert!(velocity.x > 0.0 && velocity.y < 0.0);
+
+        accelerate(&mut velocity, Vector2D::ZERO, 2.0);
+        assert!((velocity.magnitude() - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn additional_velocity_carries_the_tank() {
+        let mut state = MovementState::new(Vector2D::new(500.0, 500.0), Vector2D::ZERO, Vector2D::new(3.0, 0.0));
+        for _ in 0..4 {
+            state.tick_tank(InputFlags::default(), 1.0, Vector2D::ZERO, 1000.0);
+        }
+
+        assert_eq!(state.position, Vector2D::new(512.0, 500.0));
+        assert_eq!(state.velocity, Vector2D::ZERO);
+
+        state.tick_tank(InputFlags::new(Inputs::Left as u32), 1.0, Vector2D::ZERO, 1000.0);
+        state.tick_tank(InputFlags::default(), 1.0, Vector2D::ZERO, 1000.0);
+        assert!((state.position.x - (515.0 + 3.0 - (1.0 - FRICTION))).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+use super::{body::BodyIdentityIds, turret::TurretIdentityIds};
+
+/// A run which has ended, either because the player died or because it left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub name: String,
+    pub body: BodyIdentityIds,
+    pub turret: TurretIdentityIds,
+    pub score: usize,
+    pub kills: usize,
+    /// How long the run lasted, in seconds.
+    pub duration: u64,
+    /// The achievements unlocked during the run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub achievements: Vec<String>
+}
+
+/// The best score a player has reached with a single tank.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TankRecord {
+    pub body: BodyIdentityIds,
+    pub turret: TurretIdentityIds,
+    pub score: usize
+}
+
+/// The lifetime statistics of an anonymous player.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// The best score reached with every tank played, from highest to lowest.
+    pub best_scores: Vec<TankRecord>,
+    pub kills: usize,
+    /// The total time spent playing, in seconds.
+    pub playtime: u64,
+    pub runs: usize,
+    pub achievements: Vec<String>
+}
+
+impl Profile {
+    /// Adds a finished run to the player's statistics.
+    pub fn record(&mut self, run: &Run) {
+        self.kills += run.kills;
+        self.playtime += run.duration;
+        self.runs += 1;
+
+        for achievement in run.achievements.iter() {
+            if !self.achievements.contains(achievement) {
+                self.achievements.push(achievement.clone());
+            }
+        }
+
+        match self.best_scores.iter_mut().find(|record| record.body == run.body && record.turret == run.turret) {
+            Some(record) => record.score = record.score.max(run.score),
+            None => self.best_scores.push(TankRecord { body: run.body, turret: run.turret, score: run.score })
+        }
+
+        self.best_scores.sort_by_key(|record| std::cmp::Reverse(record.score));
+    }
+
+    pub fn get_best_score(&self, body: BodyIdentityIds, turret: TurretIdentityIds) -> usize {
+        self.best_scores.iter()
+            .find(|record| record.body == body && record.turret == turret)
+            .map(|record| record.score)
+            .unwrap_or_default()
+    }
+}
+
+/// Every profile, keyed by the token its player identifies with.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProfileBook {
+    profiles: HashMap<u64, Profile>
+}
+
+impl ProfileBook {
+    pub fn parse(json: &str) -> serde_json::Result<ProfileBook> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn get(&self, token: u64) -> Option<&Profile> {
+        self.profiles.get(&token)
+    }
+
+    pub fn get_mut(&mut self, token: u64) -> Option<&mut Profile> {
+        self.profiles.get_mut(&token)
+    }
+
+    /// Creates an empty profile under `token`, returning whether the token was free.
+    pub fn create(&mut self, token: u64) -> bool {
+        if token == 0 || self.profiles.contains_key(&token) {
+            return false;
+        }
+
+        self.profiles.insert(token, Profile::default());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(turret: TurretIdentityIds, score: usize, achievements: &[&str]) -> Run {
+        Run {
+            name: "Player".to_string(),
+            body: BodyIdentityIds::Base,
+            turret,
+            score,
+            kills: 2,
+            duration: 60,
+            achievements: achievements.iter().map(|achievement| achievement.to_string()).collect()
+        }
+    }
+
+    #[test]
+    fn runs_keep_the_best_score_per_tank() {
+        let mut profile = Profile::default();
+        profile.record(&run(TurretIdentityIds::Mono, 5000, &["first_blood"]));
+        profile.record(&run(TurretIdentityIds::Mono, 3000, &["first_blood"]));
+        profile.record(&run(TurretIdentityIds::Sniper, 8000, &[]));
+
+        assert_eq!(profile.get_best_score(BodyIdentityIds::Base, TurretIdentityIds::Mono), 5000);
+        assert_eq!(profile.best_scores[0].turret, TurretIdentityIds::Sniper);
+        assert_eq!((profile.kills, profile.playtime, profile.runs), (6, 180, 3));
+        assert_eq!(profile.achievements, vec!["first_blood".to_string()]);
+    }
+
+    #[test]
+    fn books_survive_a_round_trip() {
+        let mut book = ProfileBook::default();
+        assert!(book.create(42));
+        assert!(!book.create(42));
+        assert!(!book.create(0));
+
+        book.get_mut(42).unwrap().record(&run(TurretIdentityIds::Mono, 1000, &[]));
+
+        let parsed = ProfileBook::parse(&book.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, book);
+        assert!(ProfileBook::parse("{\"7\": {}}").unwrap().get(7).is_some());
+    }
+}
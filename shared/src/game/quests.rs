@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+use super::{body::BodyIdentityIds, orb::OrbIdentityIds, turret::TurretIdentityIds};
+
+/// Something a player did which may progress their quests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuestEvent {
+    /// Killed a player while using the given tank.
+    PlayerKilled { body: BodyIdentityIds, turret: TurretIdentityIds },
+    OrbDestroyed(OrbIdentityIds),
+    LevelReached(usize)
+}
+
+/// What has to be done to complete a quest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum QuestObjective {
+    /// Kill `count` players, optionally while using a certain body or turret.
+    KillPlayers {
+        count: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        body: Option<BodyIdentityIds>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        turret: Option<TurretIdentityIds>
+    },
+    /// Destroy `count` orbs, optionally of a certain identity.
+    DestroyOrbs {
+        count: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        orb: Option<OrbIdentityIds>
+    },
+    ReachLevel {
+        level: usize
+    }
+}
+
+impl QuestObjective {
+    /// The progress needed to complete the objective.
+    pub fn get_target(&self) -> u32 {
+        match self {
+            QuestObjective::KillPlayers { count, .. } | QuestObjective::DestroyOrbs { count, .. } => *count,
+            QuestObjective::ReachLevel { level } => *level as u32
+        }
+    }
+
+    /// The progress after `event`, or `None` if the event does not count towards the objective.
+    pub fn advance(&self, progress: u32, event: QuestEvent) -> Option<u32> {
+        match (self, event) {
+            (QuestObjective::KillPlayers { body: wanted_body, turret: wanted_turret, .. }, QuestEvent::PlayerKilled { body, turret })
+                if wanted_body.is_none_or(|wanted| wanted == body) && wanted_turret.is_none_or(|wanted| wanted == turret) => Some(progress + 1),
+            (QuestObjective::DestroyOrbs { orb: wanted, .. }, QuestEvent::OrbDestroyed(orb))
+                if wanted.is_none_or(|wanted| wanted == orb) => Some(progress + 1),
+            (QuestObjective::ReachLevel { .. }, QuestEvent::LevelReached(level)) => Some(progress.max(level as u32)),
+            _ => None
+        }
+    }
+}
+
+/// A quest or achievement, as defined in the quest file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestDefinition {
+    /// The unique ID of the quest, which progress is tracked under.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub objective: QuestObjective,
+    /// The score given upon completion.
+    #[serde(default)]
+    pub score_reward: usize,
+    /// Whether the quest is announced as an achievement.
+    #[serde(default)]
+    pub achievement: bool
+}
+
+/// A player's progress towards every quest during a session.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QuestProgress {
+    progress: HashMap<String, u32>,
+    completed: Vec<String>
+}
+
+impl QuestProgress {
+    /// Advances every incomplete quest the event counts towards, returning the quests it completed.
+    pub fn handle_event<'a>(&mut self, quests: &'a [QuestDefinition], event: QuestEvent) -> Vec<&'a QuestDefinition> {
+        let mut completed = vec![];
+
+        for quest in quests.iter() {
+            if self.is_completed(&quest.id) {
+                continue;
+            }
+
+            let progress = self.get_progress(&quest.id);
+            let Some(progress) = quest.objective.advance(progress, event) else { continue; };
+
+            if progress >= quest.objective.get_target() {
+                self.progress.remove(&quest.id);
+                self.completed.push(quest.id.clone());
+                completed.push(quest);
+            } else {
+                self.progress.insert(quest.id.clone(), progress);
+            }
+        }
+
+        completed
+    }
+
+    pub fn get_progress(&self, id: &str) -> u32 {
+        self.progress.get(id).copied().unwrap_or_default()
+    }
+
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.iter().any(|completed| completed == id)
+    }
+
+    /// The IDs of every completed quest, in the order they were completed.
+    pub fn get_completed(&self) -> &[String] {
+        &self.completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest(id: &str, objective: QuestObjective) -> QuestDefinition {
+        QuestDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            objective,
+            score_reward: 0,
+            achievement: false
+        }
+    }
+
+    #[test]
+    fn kills_only_count_with_the_right_tank() {
+        let quests = [quest("snipe", QuestObjective::KillPlayers { count: 2, body: None, turret: Some(TurretIdentityIds::Sniper) })];
+        let mut progress = QuestProgress::default();
+
+        let as_sniper = QuestEvent::PlayerKilled { body: BodyIdentityIds::Base, turret: TurretIdentityIds::Sniper };
+        let as_mono = QuestEvent::PlayerKilled { body: BodyIdentityIds::Base, turret: TurretIdentityIds::Mono };
+
+        assert!(progress.handle_event(&quests, as_mono).is_empty());
+        assert_eq!(progress.get_progress("snipe"), 0);
+
+        assert!(progress.handle_event(&quests, as_sniper).is_empty());
+        assert_eq!(progress.handle_event(&quests, as_sniper), vec![&quests[0]]);
+        assert!(progress.is_completed("snipe"));
+
+        assert!(progress.handle_event(&quests, as_sniper).is_empty());
+    }
+
+    #[test]
+    fn levels_complete_once_reached() {
+        let quests = [
+            quest("orb", QuestObjective::DestroyOrbs { count: 1, orb: Some(OrbIdentityIds::Radiant) }),
+            quest("level", QuestObjective::ReachLevel { level: 15 })
+        ];
+        let mut progress = QuestProgress::default();
+
+        assert!(progress.handle_event(&quests, QuestEvent::OrbDestroyed(OrbIdentityIds::Basic)).is_empty());
+        assert!(progress.handle_event(&quests, QuestEvent::LevelReached(14)).is_empty());
+        assert_eq!(progress.get_progress("level"), 14);
+
+        assert_eq!(progress.handle_event(&quests, QuestEvent::LevelReached(20)), vec![&quests[1]]);
+        assert_eq!(progress.handle_event(&quests, QuestEvent::OrbDestroyed(OrbIdentityIds::Radiant)), vec![&quests[0]]);
+        assert_eq!(progress.get_completed(), ["level".to_string(), "orb".to_string()]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use crate::utils::{color::Color, consts::TICK_RATE};
+
+/// The kinds of timed effects an entity can be afflicted with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, num_enum::TryFromPrimitive, strum_macros::EnumCount, strum_macros::EnumIter)]
+#[repr(u8)]
+pub enum StatusEffectKind {
+    /// Reduces movement speed by `magnitude`, a fraction of the entity's speed.
+    Slow,
+    /// Deals `magnitude` damage per second, per stack.
+    Poison,
+    /// Prevents the entity from moving or shooting.
+    Stun,
+    /// Increases the time between shots by `magnitude`, a fraction of the entity's reload.
+    ReloadPenalty,
+    /// Increases damage taken by `magnitude`, a fraction of the damage, per stack.
+    ArmorBreak
+}
+
+/// How an effect behaves when it is applied to an entity which already has it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackingRule {
+    /// The duration is refreshed and the strongest magnitude is kept.
+    Strongest,
+    /// A stack is added, up to the limit, and the duration is refreshed.
+    Stack(u8)
+}
+
+impl StatusEffectKind {
+    pub fn stacking(&self) -> StackingRule {
+        match self {
+            StatusEffectKind::Slow => StackingRule::Strongest,
+            StatusEffectKind::Poison => StackingRule::Stack(5),
+            StatusEffectKind::Stun => StackingRule::Strongest,
+            StatusEffectKind::ReloadPenalty => StackingRule::Strongest,
+            StatusEffectKind::ArmorBreak => StackingRule::Stack(3)
+        }
+    }
+
+    /// The color an afflicted entity is tinted with.
+    pub fn color(&self) -> Color {
+        match self {
+            StatusEffectKind::Slow => Color::MATERIAL_CYAN,
+            StatusEffectKind::Poison => Color::MATERIAL_GREEN,
+            StatusEffectKind::Stun => Color::MATERIAL_YELLOW,
+            StatusEffectKind::ReloadPenalty => Color::MATERIAL_PURPLE,
+            StatusEffectKind::ArmorBreak => Color::MATERIAL_ORANGE
+        }
+    }
+
+    /// The glyph drawn on the effect's icon.
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            StatusEffectKind::Slow => "S",
+            StatusEffectKind::Poison => "P",
+            StatusEffectKind::Stun => "!",
+            StatusEffectKind::ReloadPenalty => "R",
+            StatusEffectKind::ArmorBreak => "A"
+        }
+    }
+}
+
+/// An effect inflicted by a projectile or an ability.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    /// How long the effect lasts, in seconds.
+    pub duration: f32,
+    /// How strong the effect is. Its meaning depends on the kind.
+    #[serde(default)]
+    pub magnitude: f32
+}
+
+/// An effect an entity is currently afflicted with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActiveStatusEffect {
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub stacks: u8,
+    pub ticks_remaining: u64,
+    pub duration: u64,
+    /// The entity credited if the effect kills.
+    pub source: Option<u32>
+}
+
+impl ActiveStatusEffect {
+    /// The fraction of the effect's duration which remains.
+    pub fn remaining(&self) -> f32 {
+        if self.duration == 0 {
+            0.0
+        } else {
+            self.ticks_remaining as f32 / self.duration as f32
+        }
+    }
+}
+
+/// Every effect an entity is afflicted with, at most one per kind.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StatusEffects {
+    effects: Vec<ActiveStatusEffect>
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: &StatusEffect, source: Option<u32>) {
+        let duration = (effect.duration * TICK_RATE as f32).round() as u64;
+        if duration == 0 {
+            return;
+        }
+
+        let Some(active) = self.effects.iter_mut().find(|active| active.kind == effect.kind) else {
+            self.effects.push(ActiveStatusEffect {
+                kind: effect.kind,
+                magnitude: effect.magnitude,
+                stacks: 1,
+                ticks_remaining: duration,
+                duration,
+                source
+            });
+
+            return;
+        };
+
+        match effect.kind.stacking() {
+            StackingRule::Strongest => {
+                if effect.magnitude >= active.magnitude {
+                    active.magnitude = effect.magnitude;
+                    active.source = source.or(active.source);
+                }
+            },
+            StackingRule::Stack(limit) => {
+                active.stacks = (active.stacks + 1).min(limit);
+                active.magnitude = active.magnitude.max(effect.magnitude);
+                active.source = source.or(active.source);
+            }
+        }
+
+        if duration >= active.ticks_remaining {
+            active.ticks_remaining = duration;
+            active.duration = duration;
+        }
+    }
+
+    /// Counts down every effect, removing those which have expired.
+    pub fn tick(&mut self) {
+        for effect in self.effects.iter_mut() {
+            effect.ticks_remaining = effect.ticks_remaining.saturating_sub(1);
+        }
+
+        self.effects.retain(|effect| effect.ticks_remaining > 0);
+    }
+
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    pub fn get(&self, kind: StatusEffectKind) -> Option<&ActiveStatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveStatusEffect> {
+        self.effects.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.get(StatusEffectKind::Stun).is_some()
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+
+        self.get(StatusEffectKind::Slow).map_or(1.0, |effect| (1.0 - effect.magnitude).clamp(0.0, 1.0))
+    }
+
+    pub fn reload_multiplier(&self) -> f32 {
+        self.get(StatusEffectKind::ReloadPenalty).map_or(1.0, |effect| 1.0 + effect.magnitude.max(0.0))
+    }
+
+    pub fn damage_taken_multiplier(&self) -> f32 {
+        self.get(StatusEffectKind::ArmorBreak).map_or(1.0, |effect| 1.0 + effect.magnitude.max(0.0) * effect.stacks as f32)
+    }
+
+    /// The poison damage dealt this tick, along with whoever inflicted it.
+    pub fn poison_per_tick(&self) -> (f32, Option<u32>) {
+        self.get(StatusEffectKind::Poison).map_or((0.0, None), |effect| {
+            (effect.magnitude * effect.stacks as f32 / TICK_RATE as f32, effect.source)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(kind: StatusEffectKind, duration: f32, magnitude: f32) -> StatusEffect {
+        StatusEffect { kind, duration, magnitude }
+    }
+
+    #[test]
+    fn strongest_effects_keep_the_highest_magnitude() {
+        let mut effects = StatusEffects::default();
+        effects.apply(&effect(StatusEffectKind::Slow, 2.0, 0.5), Some(1));
+        effects.apply(&effect(StatusEffectKind::Slow, 1.0, 0.25), Some(2));
+
+        let slow = effects.get(StatusEffectKind::Slow).unwrap();
+        assert_eq!(effects.len(), 1);
+        assert_eq!(slow.stacks, 1);
+        assert_eq!(slow.source, Some(1));
+        assert_eq!(slow.ticks_remaining, 2 * TICK_RATE);
+        assert!((effects.speed_multiplier() - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn stacking_effects_are_capped() {
+        let mut effects = StatusEffects::default();
+        for _ in 0..10 {
+            effects.apply(&effect(StatusEffectKind::Poison, 1.0, 10.0), Some(1));
+        }
+
+        let (damage, source) = effects.poison_per_tick();
+        assert_eq!(effects.get(StatusEffectKind::Poison).unwrap().stacks, 5);
+        assert_eq!(source, Some(1));
+        assert!((damage - 50.0 / TICK_RATE as f32).abs() < 1e-4);
+    }
+
+    #[test]
+    fn effects_expire() {
+        let mut effects = StatusEffects::default();
+        effects.apply(&effect(StatusEffectKind::Stun, 1.0, 0.0), None);
+        assert_eq!(effects.speed_multiplier(), 0.0);
+
+        for _ in 0..TICK_RATE {
+            effects.tick();
+        }
+
+        assert!(effects.is_empty());
+        assert_eq!(effects.speed_multiplier(), 1.0);
+    }
+}
\ No newline at end of file
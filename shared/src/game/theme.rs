@@ -9,6 +9,7 @@ pub const INBOUNDS_FILL: Color = Color::from_numeric(0x323644); // Slightly ligh
 
 pub const PLAYER_FILL: Color = Color::from_numeric(0x00B1DE);
 pub const ENEMY_FILL: Color = Color::from_numeric(0xF14E54);
+pub const ALLY_FILL: Color = Color::from_numeric(0x00E16E);
 pub const TURRET_FILL: Color = Color::from_numeric(0x878787);
 
 // fills of shapes scattered across the arena
@@ -21,6 +22,8 @@ pub const ORB_CELESTIAL_FILL: Color = Color::MATERIAL_GREEN; // Vibrant yellow-g
 
 pub const SCORE_BAR_FOREGROUND: Color = Color::from_numeric(0x1FCC6F); // Deep emerald green  
 pub const LEVEL_BAR_FOREGROUND: Color = Color::from_numeric(0xE0B13D); // Warm gold  
+pub const ABILITY_BAR_FOREGROUND: Color = Color::from_numeric(0x3D9BE0); // Bright azure  
+pub const ENERGY_BAR_FOREGROUND: Color = Color::from_numeric(0xF2E14C); // Starlight yellow  
 
 pub const SMASHER_GUARD_FILL: Color = Color::from_numeric(0x878787);
 
@@ -53,6 +56,7 @@ pub const STROKE_INTENSITY: f32 = 0.25;
 pub const PLAYER_STROKE: Color = Color::blend_colors(PLAYER_FILL, Color::BLACK, STROKE_INTENSITY);
 // pub const ENEMY_FILL: Color = Color::from_numeric(0xE88B8B);
 pub const ENEMY_STROKE: Color = Color::blend_colors(ENEMY_FILL, Color::BLACK, STROKE_INTENSITY);
+pub const ALLY_STROKE: Color = Color::blend_colors(ALLY_FILL, Color::BLACK, STROKE_INTENSITY);
 
 // pub const TURRET_FILL: Color = Color::from_numeric(0xB8B8B8);
 pub const TURRET_STROKE: Color = Color::blend_colors(TURRET_FILL, Color::BLACK, STROKE_INTENSITY);
@@ -1,46 +1,56 @@
 use std::fmt::Display;
-use super::entity::EntityType;
+use serde::{Deserialize, Serialize};
+use super::{ability::Ability, body::BodyIdentity, definitions::get_definitions, entity::EntityType, status::StatusEffect};
 
-macro_rules! into_structure {
-    (
-        $( #[$enum_meta:meta] )*
-        $enum_vis:vis enum $enum_name:ident {
-            $(
-                $( #[$variant_meta:meta] )*
-                $variant:ident = $variant_index:expr,
-            )*
-        }
-    ) => {
-        $( #[$enum_meta] )*
-        $enum_vis enum $enum_name {
-            $(
-                $( #[$variant_meta] )*
-                $variant = $variant_index,
-            )*
-        }
-
-        impl std::convert::TryFrom<TurretIdentityIds> for TurretStructure {
-            type Error = ();
-
-            fn try_from(value: TurretIdentityIds) -> ::std::result::Result<TurretStructure, Self::Error> {
-                match value {
-                    $(
-                        TurretIdentityIds::$variant => Ok(::paste::paste! { [< get_turret_ $variant:snake _identity >] () }),
-                    )*
-                }
-            }
-        }
-    };
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TurretRenderingHints {
     Trapezoidal(f32), // Trapezoidal(angle)
     Trapper,
     Ranger
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+/// Which entities an automatic turret will aim at.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoTurretTargets {
+    /// The nearest player or orb.
+    #[default]
+    Any,
+    /// The nearest player, falling back to the nearest orb.
+    PreferPlayers,
+    Players,
+    Orbs
+}
+
+/// Makes a turret mounted on the body which aims at enemies by itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AutoTurret {
+    /// The angle (in radians) the turret can swivel through, centered on its resting angle.
+    pub arc: f32,
+    /// The furthest a target can be from the turret.
+    pub range: f32,
+    /// How fast the turret turns, in radians per tick.
+    pub turn_rate: f32,
+    #[serde(default)]
+    pub targets: AutoTurretTargets
+}
+
+/// Makes a mine explode once an enemy tank comes close.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Detonation {
+    /// The number of seconds after being laid before the mine can go off.
+    pub arm_time: f32,
+    /// How close an enemy tank must be to set the mine off.
+    pub trigger_radius: f32,
+    /// How far the explosion reaches.
+    pub blast_radius: f32,
+    /// Multiplier for the damage of the mine dealt by the explosion, at its center.
+    pub damage: f32
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ProjectileIdentity {
     pub projectile_type: EntityType,
     /// Multiplier for the size of the projectile relative to the turret width.
@@ -53,45 +63,83 @@ pub struct ProjectileIdentity {
     /// The lifetime of the projectile.
     pub lifetime: f64,
     /// The absorption factor of the tank.
-    pub absorption_factor: f32
+    pub absorption_factor: f32,
+    /// The turrets carried by missiles and minions, relative to the projectile.
+    /// These should only fire bullets, drones or traps.
+    #[serde(default)]
+    pub turrets: Vec<TurretIdentity>,
+    /// How a mine explodes.
+    #[serde(default)]
+    pub detonation: Option<Detonation>,
+    /// The status effects inflicted on whatever the projectile damages.
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TurretIdentity {
     /// The angle of the turret from the horizontal.
     pub angle: f32,
     /// The x offset of the turret from the horizontal.
+    #[serde(default)]
     pub x_offset: f32,
     /// The y offset of the turret from the vertical (to influence bullet offsets).
+    #[serde(default)]
     pub y_offset: f32,
     /// The size of the y direction of the turret.
     pub width: f32,
     /// The size of the x direction of the turret.
     pub length: f32,
     /// The delay of the turret, relative to turrets with 0 delay.
+    #[serde(default)]
     pub delay: f32,
     pub reload: f32,
     /// The cached reload time of the turret.
+    #[serde(default)]
     pub reload_time: f32,
     /// The position the turret is in the shooting cycle.
+    #[serde(default)]
     pub cycle_position: f32,
     pub recoil: f32,
+    #[serde(default)]
     pub force_shoot: bool,
+    #[serde(default)]
     pub repel_to_shoot: bool,
     pub mouse_controllable: bool,
 
     /// Hints as to how to render the turret.
+    #[serde(default)]
     pub rendering_hints: Vec<TurretRenderingHints>,
 
+    /// Makes the turret aim by itself instead of following the mouse.
+    #[serde(default)]
+    pub auto: Option<AutoTurret>,
+    /// The current angle of an automatic turret, relative to the tank.
+    #[serde(default, skip_serializing)]
+    pub auto_angle: Option<f32>,
+    /// The entity an automatic turret is aiming at.
+    #[serde(default, skip_serializing)]
+    pub auto_target: Option<u32>,
+
     /// The maximum number of projectiles this turret can spawn.
     pub max_projectiles: isize,
     /// The current number of projectiles the turret has spawned.
+    #[serde(default, skip_serializing)]
     pub projectiles_spawned: isize,
+    /// The energy spent per shot by the tank firing the turret.
+    #[serde(default)]
+    pub energy_cost: f32,
     /// The identity of the projectiles the turret shoots.
     pub projectile_identity: ProjectileIdentity
 }
 
 impl TurretIdentity {
+    /// The angle the turret is facing, relative to the tank.
+    pub fn get_angle(&self) -> f32 {
+        self.auto_angle.unwrap_or(self.angle)
+    }
+
     pub fn can_fire(&mut self, reload: f32, shooting: bool) -> bool {
         if self.reload_time == 0.0 && self.cycle_position == 0.0 {
             self.reload_time = reload * self.reload;
@@ -122,77 +170,87 @@ impl TurretIdentity {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TurretStructure {
     pub id: TurretIdentityIds,
+    #[serde(default)]
     pub turrets: Vec<TurretIdentity>,
     pub level_requirement: usize,
+    #[serde(default)]
     pub upgrades: Vec<TurretIdentityIds>,
-    pub description: &'static str,
+    pub description: String,
     /// Any messages to the player when upgrading.
-    pub upgrade_message: &'static str,
+    #[serde(default)]
+    pub upgrade_message: String,
     pub fov: f32,
     /// The rate at which the opacity decreases per tick.
     pub invisibility_rate: f32,
-}
-
-into_structure! {
-    #[derive(Debug, Default, Clone, Copy, PartialEq, num_enum::TryFromPrimitive, strum_macros::EnumCount)]
-    #[repr(usize)]
-    pub enum TurretIdentityIds {
-        #[default]
-        Base          = 1,
-        Mono          = 2,
-        Spawner       = 3,
-        Warden        = 4,
-        Couplet       = 5,
-        Flurry        = 6,
-        Flank         = 7,
-        Sniper        = 8,
-        Pounder       = 9,
-        Thruster      = 10,
-        Triplet       = 11,
-        PentaShot     = 12,
-        SpreadShot    = 13,
-        HexaShot      = 14,
-        OctoShot      = 15,
-        Cyclone       = 16,
-        CoupletFlank  = 17,
-        TripleCouplet = 18,
-        Gunner        = 19,
-        GunnerTrap    = 20,
-        Equalizer     = 21,
-        Sprayer       = 22,
-        Halzion       = 23,
-        Booster       = 24,
-        Propeller     = 25,
-        Warrior       = 26,
-        Falcon        = 27,
-        Hunter        = 28,
-        Predator      = 29,
-        Assassin      = 30,
-        Stalker       = 31,
-        Ranger        = 32,
-        Eradicator    = 33,
-        Dreadnought   = 34,
-        Overseer      = 35,
-        Overlord      = 36,
-        Manager       = 37,
-        Swarmer       = 38,
-        Battleship    = 39,
-        Surfer        = 40,
-        Builder       = 41,
-        Autocrat      = 42,
-        Constructor   = 43,
-        Architect     = 44,
-        TriTrapper    = 45,
-        Fortress      = 46,
-        HexaTrapper   = 47,
-        OverTrapper   = 48,
-        FlankTrapper  = 49,
-        SniperTrapper = 50,
-        Bomber        = 51,
-    }
+    /// The ability granted by the turret, which takes priority over the body's.
+    #[serde(default)]
+    pub ability: Option<Ability>
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, num_enum::TryFromPrimitive, strum_macros::EnumCount, strum_macros::EnumIter, Serialize, Deserialize)]
+#[repr(usize)]
+pub enum TurretIdentityIds {
+    #[default]
+    Base          = 1,
+    Mono          = 2,
+    Spawner       = 3,
+    Warden        = 4,
+    Couplet       = 5,
+    Flurry        = 6,
+    Flank         = 7,
+    Sniper        = 8,
+    Pounder       = 9,
+    Thruster      = 10,
+    Triplet       = 11,
+    PentaShot     = 12,
+    SpreadShot    = 13,
+    HexaShot      = 14,
+    OctoShot      = 15,
+    Cyclone       = 16,
+    CoupletFlank  = 17,
+    TripleCouplet = 18,
+    Gunner        = 19,
+    GunnerTrap    = 20,
+    Equalizer     = 21,
+    Sprayer       = 22,
+    Halzion       = 23,
+    Booster       = 24,
+    Propeller     = 25,
+    Warrior       = 26,
+    Falcon        = 27,
+    Hunter        = 28,
+    Predator      = 29,
+    Assassin      = 30,
+    Stalker       = 31,
+    Ranger        = 32,
+    Eradicator    = 33,
+    Dreadnought   = 34,
+    Overseer      = 35,
+    Overlord      = 36,
+    Manager       = 37,
+    Swarmer       = 38,
+    Battleship    = 39,
+    Surfer        = 40,
+    Builder       = 41,
+    Autocrat      = 42,
+    Constructor   = 43,
+    Architect     = 44,
+    TriTrapper    = 45,
+    Fortress      = 46,
+    HexaTrapper   = 47,
+    OverTrapper   = 48,
+    FlankTrapper  = 49,
+    SniperTrapper = 50,
+    Bomber        = 51,
+    Sentry        = 52,
+    Watchtower    = 53,
+    Launcher      = 54,
+    Factory       = 55,
+    Minelayer     = 56,
 }
 
 impl Display for TurretIdentityIds {
@@ -214,5176 +272,18 @@ impl Display for TurretIdentityIds {
     }
 }
 
-pub fn get_turret_base_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Base,
-        turrets: vec![],
-        level_requirement: 0,
-        upgrades: vec![TurretIdentityIds::Mono],
-        description: "Null and void.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_mono_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Mono,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 0,
-        upgrades: vec![
-            TurretIdentityIds::Couplet, 
-            TurretIdentityIds::Flurry, 
-            TurretIdentityIds::Flank, 
-            TurretIdentityIds::Sniper, 
-            TurretIdentityIds::Pounder,
-            TurretIdentityIds::Spawner,
-            TurretIdentityIds::Warden
-        ],
-        description: "Shoots bullets.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_spawner_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Spawner,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 4,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::Overseer, TurretIdentityIds::Swarmer],
-        description: "Shoots AI/user controlled drones, slightly increased FOV.",
-        upgrade_message: "Fire to attract the drones to your mouse, Shift/Right Click to repel them.",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_warden_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Warden,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::Builder, TurretIdentityIds::TriTrapper, TurretIdentityIds::FlankTrapper],
-        description: "Shoots persistent traps, slightly increased FOV.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_couplet_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Couplet,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.9,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.9,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::Thruster, TurretIdentityIds::HexaShot, TurretIdentityIds::CoupletFlank],
-        description: "Fires two bullets successively.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_flurry_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Flurry,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 0.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 3.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::Gunner, TurretIdentityIds::Sprayer],
-        description: "High bullet spread and reload for less damage.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_flank_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Flank,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::HexaShot, TurretIdentityIds::CoupletFlank, TurretIdentityIds::Booster, TurretIdentityIds::FlankTrapper],
-        description: "Two diametrically opposed barrels.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_sniper_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Sniper,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 3.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.5,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::Hunter, TurretIdentityIds::Assassin],
-        description: "Shoots fast bullets with low reload, slightly increased FOV.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_pounder_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Pounder,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 30.0,
-                delay: 0.0,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 7.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.5,
-                    damage: 1.5,
-                    speed: 0.85,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 0.5
-                },
-            }
-        ],
-        level_requirement: 15,
-        upgrades: vec![TurretIdentityIds::Eradicator, TurretIdentityIds::Builder],
-        description: "Shoots strong bullets with low reload.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_thruster_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Thruster,
-        turrets: vec![
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 47.0,
-                width: 18.0,
-                delay: 1.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.35,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 47.0,
-                width: 18.0,
-                delay: 1.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.35,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.9,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.9,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Triplet, TurretIdentityIds::PentaShot, TurretIdentityIds::SpreadShot],
-        description: "Has two small turret offshoots per barrel.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_triplet_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Triplet,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.7,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.7,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.7,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Focused fire, high damage triple shooter.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
+impl TurretStructure {
+    /// Appends the automatic turrets mounted on `body`, which fire alongside the structure's own.
+    pub fn with_body(mut self, body: &BodyIdentity) -> TurretStructure {
+        self.turrets.extend(body.turrets.iter().cloned());
+        self
     }
 }
 
-pub fn get_turret_penta_shot_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::PentaShot,
-        turrets: vec![
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.55,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.55,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_8,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.33,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.55,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_8,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.33,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.55,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.55,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "A spammer tank with high spread and recoil.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_spread_shot_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::SpreadShot,
-        turrets: vec![
-            TurretIdentity {
-                angle: 1.3089969,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 39.0,
-                width: 17.0,
-                delay: 0.83333,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -1.3089969,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 39.0,
-                width: 17.0,
-                delay: 0.83333,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 43.0,
-                width: 17.0,
-                delay: 0.666666,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 43.0,
-                width: 17.0,
-                delay: 0.666666,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 46.0,
-                width: 17.0,
-                delay: 0.5,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 46.0,
-                width: 17.0,
-                delay: 0.5,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 50.0,
-                width: 17.0,
-                delay: 0.333333,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 50.0,
-                width: 17.0,
-                delay: 0.333333,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.26179938,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 54.0,
-                width: 17.0,
-                delay: 0.16666,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -0.26179938,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 54.0,
-                width: 17.0,
-                delay: 0.16666,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.6,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "A spammer tank with extreme spread, but low recoil.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_hexa_shot_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::HexaShot,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::OctoShot, TurretIdentityIds::Cyclone, TurretIdentityIds::HexaTrapper],
-        description: "Six evenly spaced out barrels.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_octo_shot_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::OctoShot,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 3.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 3.0 * std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 7.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.65,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Eight evenly spaced out barrels.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_cyclone_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Cyclone,
-        turrets: (0..12).map(|i| {
-            let angle = i as f32 * (2.0 * std::f32::consts::PI / 12.0);
-            TurretIdentity {
-                angle,
-                x_offset: 0.0, 
-                y_offset: 0.0, 
-                length: 45.0,  
-                width: 15.0,   
-                delay: if i % 2 == 0 { 0.0 } else { 0.5 },
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.45,
-                    damage: 0.5,
-                    speed: 1.1,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        }).collect(),
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Twelve evenly spaced out gunner barrels.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_couplet_flank_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::CoupletFlank,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::TripleCouplet, TurretIdentityIds::Battleship],
-        description: "Two diametrically opposed couplets.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_triple_couplet_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::TripleCouplet,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: -16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 16.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.75,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Three evenly spaced out couplets.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_gunner_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Gunner,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -19.2,
-                length: 39.0,
-                width: 15.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.45,
-                    damage: 0.5,
-                    speed: 1.1,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 19.2,
-                length: 39.0,
-                width: 15.0,
-                delay: 0.75,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.45,
-                    damage: 0.5,
-                    speed: 1.1,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -10.4,
-                length: 51.0,
-                width: 15.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.45,
-                    damage: 0.5,
-                    speed: 1.1,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 10.4,
-                length: 51.0,
-                width: 15.0,
-                delay: 0.25,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.45,
-                    damage: 0.5,
-                    speed: 1.1,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::GunnerTrap, TurretIdentityIds::Equalizer],
-        description: "Spams small, high damage pellets.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_gunner_trap_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::GunnerTrap,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -9.6,
-                length: 45.0,
-                width: 12.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 9.6,
-                length: 45.0,
-                width: 12.0,
-                delay: 0.33,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.5,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Shoots traps and higher damage bullets.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_equalizer_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Equalizer,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.1,
-                    scatter_rate: 0.3,
-                    lifetime: 0.8,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 60.0,
-                width: 24.0,
-                delay: 0.2,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.1,
-                    scatter_rate: 0.3,
-                    lifetime: 0.8,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 54.0,
-                width: 24.0,
-                delay: 0.4,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.1,
-                    scatter_rate: 0.3,
-                    lifetime: 0.8,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.6,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.1,
-                    scatter_rate: 0.3,
-                    lifetime: 0.8,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.8,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.1,
-                    scatter_rate: 0.3,
-                    lifetime: 0.8,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Has several overlayed barrels which shoot strong bullets.",
-        upgrade_message: "",
-        fov: 0.85,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_sprayer_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Sprayer,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.1,
-                    speed: 1.0,
-                    scatter_rate: 3.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 0.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 3.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Halzion],
-        description: "Extra cannon for more damage.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_halzion_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Halzion,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 75.0,
-                width: 24.0,
-                delay: 0.75,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.1,
-                    speed: 1.0,
-                    scatter_rate: 3.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 18.0,
-                delay: 0.5,
-                reload: 0.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.4,
-                    speed: 1.0,
-                    scatter_rate: 3.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 0.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.7,
-                    speed: 1.0,
-                    scatter_rate: 3.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Extra cannons for more damage.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_booster_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Booster,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 7.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Propeller, TurretIdentityIds::Warrior, TurretIdentityIds::Falcon, TurretIdentityIds::Surfer, TurretIdentityIds::Bomber],
-        description: "Propels itself with high recoil rear turrets.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_propeller_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Propeller,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 3.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.2,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 7.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.33,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.33,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Extra rear bullets to propel itself faster.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_warrior_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Warrior,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 7.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.8,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.8,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Side cannons to defend the tank better.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_falcon_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Falcon,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.0,
-                force_shoot: false,
-                repel_to_shoot: true,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.5,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 3.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.33,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.2,
-                    speed: 0.5,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_4,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.66,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.2,
-                    speed: 0.5,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 7.0 * std::f32::consts::FRAC_PI_8,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.99,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.2,
-                    speed: 0.5,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 9.0 * std::f32::consts::FRAC_PI_8,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.99,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.2,
-                    speed: 0.5,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 1.33,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.7,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 0.5,
-                    damage: 0.2,
-                    speed: 0.5,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Extra rear cannons for higher speed with a sniper barrel, slightly increased FOV.",
-        upgrade_message: "Right click or press shift to shoot sniper bullets.",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_hunter_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Hunter,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 2.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.3,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.75,
-                    speed: 1.4,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 33.0,
-                delay: 0.2,
-                reload: 2.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.3,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.75,
-                    speed: 1.4,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Predator, TurretIdentityIds::Equalizer, TurretIdentityIds::SniperTrapper],
-        description: "Slightly higher FOV, Shoots more bullets.",
-        upgrade_message: "",
-        fov: 0.85,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_predator_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Predator,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.3,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.75,
-                    speed: 1.4,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 33.0,
-                delay: 0.2,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.3,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.75,
-                    speed: 1.4,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 41.0,
-                delay: 0.4,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.3,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.75,
-                    speed: 1.4,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Higher FOV, shoots more bullets.",
-        upgrade_message: "",
-        fov: 0.75,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_assassin_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Assassin,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 72.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 3.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.5,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Stalker, TurretIdentityIds::Ranger],
-        description: "Higher FOV with lower reload.",
-        upgrade_message: "",
-        fov: 0.8,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_stalker_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Stalker,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 72.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 3.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.5,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Can go invisible when still.",
-        upgrade_message: "",
-        fov: 0.8,
-        invisibility_rate: 0.23
-    }
-}
-
-pub fn get_turret_ranger_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Ranger,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 72.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 2.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 3.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Ranger],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.5,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Higher FOV.",
-        upgrade_message: "",
-        fov: 0.7,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_eradicator_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Eradicator,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 41.0,
-                delay: 0.0,
-                reload: 4.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 15.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 3.0,
-                    speed: 0.7,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 0.1
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Dreadnought],
-        description: "Shoots stronger and bigger bullets with lower reload and higher recoil.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_dreadnought_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Dreadnought,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 57.0,
-                delay: 0.0,
-                reload: 4.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 17.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 3.0,
-                    speed: 0.7,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 0.05
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Shoots bigger bullets with higher recoil.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_overseer_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Overseer,
-        turrets: vec![
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 4,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 4,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Overlord, TurretIdentityIds::Manager, TurretIdentityIds::OverTrapper],
-        description: "Has an extra spawner for higher reload.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_overlord_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Overlord,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 2,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 2,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 2,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 3.0 * std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 2,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Has two extra spawners for higher reload.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_manager_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Manager,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: 8,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "One spawner with twice the reload, can go invisible.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: 0.23
-    }
-}
-
-pub fn get_turret_swarmer_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Swarmer,
-        turrets: vec![
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: -12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: -12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Battleship, TurretIdentityIds::Surfer, TurretIdentityIds::Fortress],
-        description: "Shoots weak, ephemeral drones at high reload.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_battleship_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Battleship,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: -12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: -12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: -12.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Shoots weak, ephemeral drones at high reload.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_surfer_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Surfer,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 7.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_2,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: false,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Side cannons which shoot swarm drones.",
-        upgrade_message: "",
-        fov: 1.0,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_builder_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Builder,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 30.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.5,
-                    damage: 1.5,
-                    speed: 2.5,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Autocrat, TurretIdentityIds::Constructor, TurretIdentityIds::Architect],
-        description: "Shoots stronger traps with lower reload.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_autocrat_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Autocrat,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 30.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.5,
-                    damage: 1.5,
-                    speed: 2.5,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 41.0,
-                delay: 0.0,
-                reload: 4.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 15.0,
-                force_shoot: false,
-                repel_to_shoot: true,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 3.0,
-                    speed: 0.7,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 0.1
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Has an eradicator turret behind it.",
-        upgrade_message: "Right click or press shift to shoot Eradicator bullets.",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_constructor_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Constructor,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 41.0,
-                delay: 0.0,
-                reload: 4.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 3.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 3.5,
-                    damage: 2.0,
-                    speed: 2.5,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Shoots stronger traps with lower reload.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_architect_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Architect,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 30.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.5,
-                    damage: 1.5,
-                    speed: 2.5,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 30.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.5,
-                    damage: 1.5,
-                    speed: 2.5,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 30.0,
-                delay: 0.0,
-                reload: 3.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.5,
-                    damage: 1.5,
-                    speed: 2.5,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Three builder turrets.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_tri_trapper_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::TriTrapper,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Fortress, TurretIdentityIds::HexaTrapper, TurretIdentityIds::Architect, TurretIdentityIds::OverTrapper],
-        description: "Has three trapper turrets.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_fortress_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Fortress,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 5.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 45.0,
-                width: 17.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(std::f32::consts::PI)],
-                max_projectiles: -1,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 0.7,
-                    health: 1.0,
-                    damage: 0.15,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Has three swarmer turrets.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_hexa_trapper_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::HexaTrapper,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: -2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Has six trapper turrets.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_over_trapper_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::OverTrapper,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 2.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 2,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_3,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 42.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 6.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: true,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapezoidal(0.0)],
-                max_projectiles: 2,
-                projectiles_spawned: 0,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Drone,
-                    size_factor: 1.0,
-                    health: 2.0,
-                    damage: 0.7,
-                    speed: 0.8,
-                    scatter_rate: 1.0,
-                    lifetime: -1.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Trapper with 2 spawners which spawn 2 drones each.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_flank_trapper_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::FlankTrapper,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                }
-            }
-        ],
-        level_requirement: 30,
-        upgrades: vec![TurretIdentityIds::Autocrat, TurretIdentityIds::GunnerTrap, TurretIdentityIds::SniperTrapper, TurretIdentityIds::Bomber],
-        description: "Flank with trapper guard behind it.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
-
-pub fn get_turret_sniper_trapper_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::SniperTrapper,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 66.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 3.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.5,
-                    scatter_rate: 0.3,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                }
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Sniper with trapper guard behind it.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
-    }
-}
+impl TryFrom<TurretIdentityIds> for TurretStructure {
+    type Error = ();
 
-pub fn get_turret_bomber_identity() -> TurretStructure {
-    TurretStructure {
-        id: TurretIdentityIds::Bomber,
-        turrets: vec![
-            TurretIdentity {
-                angle: 0.0,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 57.0,
-                width: 24.0,
-                delay: 0.0,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 0.1,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 1.0,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 1.0,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 8.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: 4.0 * std::f32::consts::FRAC_PI_6,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 48.0,
-                width: 24.0,
-                delay: 0.5,
-                reload: 1.0,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 2.5,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Bullet,
-                    size_factor: 1.0,
-                    health: 1.0,
-                    damage: 0.2,
-                    speed: 1.0,
-                    scatter_rate: 1.0,
-                    lifetime: 0.5,
-                    absorption_factor: 1.0
-                },
-            },
-            TurretIdentity {
-                angle: std::f32::consts::PI,
-                x_offset: 0.0,
-                y_offset: 0.0,
-                length: 36.0,
-                width: 24.0,
-                delay: 1.0,
-                reload: 1.5,
-                reload_time: 0.0,
-                cycle_position: 0.0,
-                recoil: 1.0,
-                force_shoot: false,
-                repel_to_shoot: false,
-                mouse_controllable: true,
-                rendering_hints: vec![TurretRenderingHints::Trapper],
-                max_projectiles: -1,
-                projectiles_spawned: -1,
-                projectile_identity: ProjectileIdentity {
-                    projectile_type: EntityType::Trap,
-                    size_factor: 0.8,
-                    health: 2.0,
-                    damage: 1.0,
-                    speed: 2.0,
-                    scatter_rate: 1.0,
-                    lifetime: 8.0,
-                    absorption_factor: 1.0
-                },
-            }
-        ],
-        level_requirement: 45,
-        upgrades: vec![],
-        description: "Booster with trapper guard.",
-        upgrade_message: "",
-        fov: 0.9,
-        invisibility_rate: -1.0
+    fn try_from(value: TurretIdentityIds) -> Result<TurretStructure, Self::Error> {
+        get_definitions().turrets.get(&value).cloned().ok_or(())
     }
 }
\ No newline at end of file
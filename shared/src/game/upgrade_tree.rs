@@ -0,0 +1,345 @@
+use std::{collections::{HashMap, HashSet, VecDeque}, fmt::Display};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use super::{body::{BodyIdentity, BodyIdentityIds}, turret::{TurretIdentityIds, TurretStructure}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpgradeTreeKind {
+    Turret,
+    Body
+}
+
+impl Display for UpgradeTreeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpgradeTreeKind::Turret => write!(f, "turret"),
+            UpgradeTreeKind::Body => write!(f, "body")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpgradeNode {
+    pub id: usize,
+    pub name: String,
+    pub level_requirement: usize,
+    pub upgrades: Vec<usize>
+}
+
+/// A problem found in an upgrade tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpgradeTreeIssue {
+    /// An identity could not be loaded.
+    MissingIdentity { id: String },
+    /// An upgrade points to an identity which could not be loaded.
+    MissingLink { from: String, to: String },
+    /// An identity cannot be reached from the root.
+    Unreachable { id: String },
+    /// A chain of upgrades leads back to where it started.
+    Cycle { path: Vec<String> },
+    /// An upgrade is available at a lower level than its parent.
+    LevelRequirement { parent: String, parent_level: usize, child: String, child_level: usize }
+}
+
+impl Display for UpgradeTreeIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpgradeTreeIssue::MissingIdentity { id } => write!(f, "{} has no definition", id),
+            UpgradeTreeIssue::MissingLink { from, to } => write!(f, "{} upgrades to {}, which has no definition", from, to),
+            UpgradeTreeIssue::Unreachable { id } => write!(f, "{} cannot be reached from the root", id),
+            UpgradeTreeIssue::Cycle { path } => write!(f, "upgrade cycle: {}", path.join(" -> ")),
+            UpgradeTreeIssue::LevelRequirement { parent, parent_level, child, child_level } => write!(
+                f, "{} (level {}) upgrades to {} which only requires level {}",
+                parent, parent_level, child, child_level
+            )
+        }
+    }
+}
+
+/// The upgrade graph of every turret or body identity.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpgradeTree {
+    pub kind: UpgradeTreeKind,
+    pub root: usize,
+    /// Every identity which could be loaded, sorted by ID.
+    pub nodes: Vec<UpgradeNode>,
+    /// The names of every known ID, including those which couldn't be loaded.
+    #[serde(skip)]
+    names: HashMap<usize, String>
+}
+
+impl UpgradeTree {
+    /// Builds a tree by loading every ID in `ids` through `load`,
+    /// which yields the level requirement and upgrades of an identity.
+    pub fn from_fn(
+        kind: UpgradeTreeKind,
+        root: usize,
+        ids: impl IntoIterator<Item = (usize, String)>,
+        load: impl Fn(usize) -> Option<(usize, Vec<usize>)>
+    ) -> UpgradeTree {
+        let names: HashMap<usize, String> = ids.into_iter().collect();
+
+        let mut nodes: Vec<UpgradeNode> = names.iter()
+            .filter_map(|(&id, name)| load(id).map(|(level_requirement, upgrades)| UpgradeNode {
+                id,
+                name: name.clone(),
+                level_requirement,
+                upgrades
+            }))
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+
+        UpgradeTree { kind, root, nodes, names }
+    }
+
+    /// Loads every turret through `TryFrom<TurretIdentityIds>`.
+    pub fn turrets() -> UpgradeTree {
+        UpgradeTree::from_fn(
+            UpgradeTreeKind::Turret,
+            TurretIdentityIds::Base as usize,
+            TurretIdentityIds::iter().map(|id| (id as usize, id.to_string())),
+            |id| {
+                let structure = TurretStructure::try_from(TurretIdentityIds::try_from(id).ok()?).ok()?;
+                Some((structure.level_requirement, structure.upgrades.iter().map(|&upgrade| upgrade as usize).collect()))
+            }
+        )
+    }
+
+    /// Loads every body through `TryFrom<BodyIdentityIds>`.
+    pub fn bodies() -> UpgradeTree {
+        UpgradeTree::from_fn(
+            UpgradeTreeKind::Body,
+            BodyIdentityIds::Base as usize,
+            BodyIdentityIds::iter().map(|id| (id as usize, id.to_string())),
+            |id| {
+                let identity = BodyIdentity::try_from(BodyIdentityIds::try_from(id).ok()?).ok()?;
+                Some((identity.level_requirement, identity.upgrades.iter().map(|&upgrade| upgrade as usize).collect()))
+            }
+        )
+    }
+
+    fn get_node(&self, id: usize) -> Option<&UpgradeNode> {
+        self.nodes.binary_search_by_key(&id, |node| node.id).ok().map(|index| &self.nodes[index])
+    }
+
+    fn get_name(&self, id: usize) -> String {
+        self.names.get(&id).cloned().unwrap_or_else(|| format!("#{}", id))
+    }
+
+    /// Finds every problem in the tree.
+    pub fn validate(&self) -> Vec<UpgradeTreeIssue> {
+        let mut issues = vec![];
+
+        let mut missing: Vec<usize> = self.names.keys().copied().filter(|&id| self.get_node(id).is_none()).collect();
+        missing.sort();
+        issues.extend(missing.into_iter().map(|id| UpgradeTreeIssue::MissingIdentity { id: self.get_name(id) }));
+
+        for node in self.nodes.iter() {
+            for &upgrade in node.upgrades.iter() {
+                match self.get_node(upgrade) {
+                    None => issues.push(UpgradeTreeIssue::MissingLink { from: node.name.clone(), to: self.get_name(upgrade) }),
+                    Some(child) if child.level_requirement < node.level_requirement => {
+                        issues.push(UpgradeTreeIssue::LevelRequirement {
+                            parent: node.name.clone(),
+                            parent_level: node.level_requirement,
+                            child: child.name.clone(),
+                            child_level: child.level_requirement
+                        });
+                    },
+                    _ => ()
+                }
+            }
+        }
+
+        let reachable = self.reachable();
+        issues.extend(self.nodes.iter()
+            .filter(|node| !reachable.contains(&node.id))
+            .map(|node| UpgradeTreeIssue::Unreachable { id: node.name.clone() }));
+
+        issues.extend(self.cycles().into_iter().map(|cycle| UpgradeTreeIssue::Cycle {
+            path: cycle.into_iter().map(|id| self.get_name(id)).collect()
+        }));
+
+        issues
+    }
+
+    fn reachable(&self) -> HashSet<usize> {
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        if self.get_node(self.root).is_some() {
+            reachable.insert(self.root);
+            queue.push_back(self.root);
+        }
+
+        while let Some(id) = queue.pop_front() {
+            for &upgrade in self.get_node(id).unwrap().upgrades.iter() {
+                if self.get_node(upgrade).is_some() && reachable.insert(upgrade) {
+                    queue.push_back(upgrade);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Finds every cycle, each given as the path back to its first ID.
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        fn visit(tree: &UpgradeTree, id: usize, stack: &mut Vec<usize>, done: &mut HashSet<usize>, cycles: &mut Vec<Vec<usize>>) {
+            if let Some(position) = stack.iter().position(|&other| other == id) {
+                let mut cycle = stack[position..].to_vec();
+
+                // Rotate so the same cycle found from another entry point compares equal.
+                let start = cycle.iter().enumerate().min_by_key(|&(_, &id)| id).unwrap().0;
+                cycle.rotate_left(start);
+                cycle.push(cycle[0]);
+
+                if !cycles.contains(&cycle) {
+                    cycles.push(cycle);
+                }
+
+                return;
+            }
+
+            if done.contains(&id) {
+                return;
+            }
+
+            let Some(node) = tree.get_node(id) else { return; };
+
+            stack.push(id);
+            for &upgrade in node.upgrades.iter() {
+                visit(tree, upgrade, stack, done, cycles);
+            }
+            stack.pop();
+
+            done.insert(id);
+        }
+
+        let (mut stack, mut done, mut cycles) = (vec![], HashSet::new(), vec![]);
+        for node in self.nodes.iter() {
+            visit(self, node.id, &mut stack, &mut done, &mut cycles);
+        }
+
+        cycles
+    }
+
+    /// Exports the tree in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("digraph {} {{\n    rankdir=LR;\n    node [shape=box];\n", self.kind);
+
+        for node in self.nodes.iter() {
+            dot += &format!("    \"{}\" [label=\"{}\\nLevel {}\"];\n", node.name, node.name, node.level_requirement);
+        }
+
+        for node in self.nodes.iter() {
+            for &upgrade in node.upgrades.iter() {
+                dot += &format!("    \"{}\" -> \"{}\";\n", node.name, self.get_name(upgrade));
+            }
+        }
+
+        dot += "}\n";
+        dot
+    }
+
+    /// Exports the tree as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a tree from `(id, level, upgrades)` triples, rooted at 0.
+    fn tree(nodes: &[(usize, usize, &[usize])], unloadable: &[usize]) -> UpgradeTree {
+        let definitions: HashMap<usize, (usize, Vec<usize>)> = nodes.iter()
+            .map(|&(id, level, upgrades)| (id, (level, upgrades.to_vec())))
+            .collect();
+
+        let ids = nodes.iter().map(|node| node.0).chain(unloadable.iter().copied())
+            .map(|id| (id, format!("T{}", id)))
+            .collect::<Vec<_>>();
+
+        UpgradeTree::from_fn(UpgradeTreeKind::Turret, 0, ids, |id| definitions.get(&id).cloned())
+    }
+
+    #[test]
+    fn embedded_trees_load_every_identity() {
+        assert_eq!(UpgradeTree::turrets().nodes.len(), TurretIdentityIds::iter().count());
+        assert_eq!(UpgradeTree::bodies().nodes.len(), BodyIdentityIds::iter().count());
+    }
+
+    #[test]
+    fn valid_tree_has_no_issues() {
+        let tree = tree(&[(0, 0, &[1, 2]), (1, 15, &[3]), (2, 15, &[3]), (3, 30, &[])], &[]);
+        assert_eq!(tree.validate(), vec![]);
+    }
+
+    #[test]
+    fn reports_missing_identities_and_links() {
+        let tree = tree(&[(0, 0, &[1])], &[1]);
+        assert_eq!(tree.validate(), vec![
+            UpgradeTreeIssue::MissingIdentity { id: "T1".to_string() },
+            UpgradeTreeIssue::MissingLink { from: "T0".to_string(), to: "T1".to_string() }
+        ]);
+    }
+
+    #[test]
+    fn reports_unreachable_identities() {
+        let tree = tree(&[(0, 0, &[1]), (1, 15, &[]), (2, 15, &[3]), (3, 30, &[])], &[]);
+        assert_eq!(tree.validate(), vec![
+            UpgradeTreeIssue::Unreachable { id: "T2".to_string() },
+            UpgradeTreeIssue::Unreachable { id: "T3".to_string() }
+        ]);
+    }
+
+    #[test]
+    fn reports_each_cycle_once() {
+        let tree = tree(&[(0, 0, &[1]), (1, 15, &[2]), (2, 15, &[1, 2])], &[]);
+        let cycles: Vec<_> = tree.validate().into_iter()
+            .filter(|issue| matches!(issue, UpgradeTreeIssue::Cycle { .. }))
+            .collect();
+
+        assert_eq!(cycles, vec![
+            UpgradeTreeIssue::Cycle { path: vec!["T1".to_string(), "T2".to_string(), "T1".to_string()] },
+            UpgradeTreeIssue::Cycle { path: vec!["T2".to_string(), "T2".to_string()] }
+        ]);
+    }
+
+    #[test]
+    fn reports_level_requirements_lower_than_parent() {
+        let tree = tree(&[(0, 0, &[1]), (1, 30, &[2]), (2, 15, &[])], &[]);
+        assert_eq!(tree.validate(), vec![
+            UpgradeTreeIssue::LevelRequirement {
+                parent: "T1".to_string(),
+                parent_level: 30,
+                child: "T2".to_string(),
+                child_level: 15
+            }
+        ]);
+    }
+
+    #[test]
+    fn exports_dot() {
+        let dot = tree(&[(0, 0, &[1]), (1, 15, &[])], &[]).to_dot();
+
+        assert!(dot.starts_with("digraph turret {"));
+        assert!(dot.contains("\"T1\" [label=\"T1\\nLevel 15\"];"));
+        assert!(dot.contains("\"T0\" -> \"T1\";"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn exports_json() {
+        let json: serde_json::Value = serde_json::from_str(&tree(&[(0, 0, &[1]), (1, 15, &[])], &[]).to_json()).unwrap();
+
+        assert_eq!(json["kind"], "turret");
+        assert_eq!(json["root"], 0);
+        assert_eq!(json["nodes"][1]["name"], "T1");
+        assert_eq!(json["nodes"][0]["upgrades"], serde_json::json!([1]));
+    }
+}
\ No newline at end of file
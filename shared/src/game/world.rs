@@ -0,0 +1,118 @@
+use crate::utils::{consts::{ARENA_SIZE, TICK_RATE}, vec2::Vector2D};
+
+/// The smallest the arena can shrink to.
+pub const MIN_ARENA_SIZE: f32 = 5000.0;
+/// How much the arena grows with the square root of the player count.
+pub const ARENA_SIZE_PER_PLAYER: f32 = 2500.0;
+/// The side length of the center region, where radiant orbs spawn, relative to the arena.
+pub const CENTER_REGION_SCALE: f32 = 0.15;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum GameMode {
+    #[default]
+    FreeForAll,
+    /// Free for all, with clans competing for kills in timed rounds.
+    ClanWars
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::FreeForAll => "Free For All",
+            GameMode::ClanWars => "Clan Wars"
+        }
+    }
+
+    /// The mode named `name`, ignoring case, spaces and underscores (e.g. `clan_wars`).
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        match name.replace([' ', '_', '-'], "").to_lowercase().as_str() {
+            "freeforall" | "ffa" => Some(GameMode::FreeForAll),
+            "clanwars" => Some(GameMode::ClanWars),
+            _ => None
+        }
+    }
+}
+
+/// An impassable rectangle inside the arena.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wall {
+    /// The top left corner of the wall.
+    pub position: Vector2D,
+    pub width: f32,
+    pub height: f32
+}
+
+/// The layout of the arena, sent when a player joins and whenever it changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldInfo {
+    /// The side length of the square arena.
+    pub arena_size: f32,
+    pub mode: GameMode,
+    /// The number of server ticks per second.
+    pub tick_rate: u64,
+    /// The number of teams, which is zero when every player fights for themselves.
+    pub teams: usize,
+    /// The walls inside the arena. No arena has any yet.
+    pub walls: Vec<Wall>
+}
+
+impl Default for WorldInfo {
+    fn default() -> WorldInfo {
+        WorldInfo { arena_size: ARENA_SIZE, mode: GameMode::default(), tick_rate: TICK_RATE, teams: 0, walls: vec![] }
+    }
+}
+
+impl WorldInfo {
+    /// The lowest and highest coordinates of the center region, along either axis.
+    pub fn get_center_region(&self) -> (f32, f32) {
+        let center_size = self.arena_size * CENTER_REGION_SCALE;
+        ((self.arena_size - center_size) / 2.0, (self.arena_size + center_size) / 2.0)
+    }
+}
+
+/// The side length of the arena with `players` players in it. Like diep.io, the arena
+/// grows with the square root of the player count, so every player has about the same room.
+pub fn get_arena_size(players: usize) -> f32 {
+    let size = (ARENA_SIZE_PER_PLAYER * (players.max(1) as f32).sqrt() / 500.0).round() * 500.0;
+    size.clamp(MIN_ARENA_SIZE, ARENA_SIZE)
+}
+
+/// The number of orbs in an arena of `arena_size`, given `max_orbs` in the largest arena.
+pub fn get_orb_count(max_orbs: usize, arena_size: f32) -> usize {
+    (max_orbs as f32 * (arena_size / ARENA_SIZE).powi(2)).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena_grows_with_players_within_bounds() {
+        assert_eq!(get_arena_size(0), MIN_ARENA_SIZE);
+        assert_eq!(get_arena_size(1), MIN_ARENA_SIZE);
+        assert_eq!(get_arena_size(9), 7500.0);
+        assert_eq!(get_arena_size(16), 10000.0);
+        assert_eq!(get_arena_size(1000), ARENA_SIZE);
+
+        for players in 1..100 {
+            assert!(get_arena_size(players) <= get_arena_size(players + 1));
+        }
+    }
+
+    #[test]
+    fn modes_are_parsed_by_name() {
+        assert_eq!(GameMode::from_name("ClanWars"), Some(GameMode::ClanWars));
+        assert_eq!(GameMode::from_name("clan_wars"), Some(GameMode::ClanWars));
+        assert_eq!(GameMode::from_name(GameMode::FreeForAll.name()), Some(GameMode::FreeForAll));
+        assert_eq!(GameMode::from_name("ffa"), Some(GameMode::FreeForAll));
+        assert_eq!(GameMode::from_name("teams"), None);
+    }
+
+    #[test]
+    fn orbs_scale_with_arena_area() {
+        assert_eq!(get_orb_count(300, ARENA_SIZE), 300);
+        assert_eq!(get_orb_count(300, ARENA_SIZE / 2.0), 75);
+        assert_eq!(get_orb_count(0, MIN_ARENA_SIZE), 0);
+    }
+}
@@ -3,6 +3,8 @@ pub const ARENA_SIZE: f32 = 12500.0;
 pub const MAX_LEVEL: usize = 45;
 pub const SCREEN_WIDTH: f32 = 1920.0;
 pub const SCREEN_HEIGHT: f32 = 1080.0;
+/// The number of server ticks per second.
+pub const TICK_RATE: u64 = 25;
 
 pub const CLAN_NAME_LENGTH: i32 = 25;
 pub const CLAN_DESC_LENGTH: i32 = 50;
@@ -0,0 +1,99 @@
+use std::process::ExitCode;
+use shared::{game::{balance::{TankBalance, TankBuild}, body::BodyIdentityIds, entity::{UpgradeStats, MAX_STAT_INVESTMENT}, turret::TurretIdentityIds}, utils::consts::MAX_LEVEL};
+use strum::{EnumCount, IntoEnumIterator};
+use tools::take_definitions_arg;
+
+const USAGE: &str = "\
+Usage: balance <turret>[+<body>]... [--level <n>] [--build <stats>] [--definitions <dir>]
+
+    <turret>[+<body>]      A tank to compare, e.g. `Sniper` or `Booster+Smasher`.
+                           The body defaults to Base.
+
+    --level <n>            The level of every tank (default 45).
+    --build <stats>        Stat investments separated by slashes, in the order
+                           regen/health/body damage/bullet speed/penetration/damage/reload/movement
+                           (default 0/0/0/0/0/0/0/0).
+    --definitions <dir>    Loads definitions from a directory instead of the embedded ones.
+
+A single tank also lists each of its turrets.";
+
+/// Removes `<flag> <value>` from the arguments, returning the value.
+fn take_arg(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(index) = args.iter().position(|arg| arg == flag) else { return Ok(None); };
+    if index + 1 >= args.len() {
+        return Err(format!("{} expects a value", flag));
+    }
+
+    let value = args.remove(index + 1);
+    args.remove(index);
+
+    Ok(Some(value))
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+fn parse_tank(spec: &str, build: &TankBuild) -> Result<TankBuild, String> {
+    let (turret, body) = spec.split_once('+').unwrap_or((spec, "base"));
+
+    let turret = TurretIdentityIds::iter()
+        .find(|id| normalize(&format!("{:?}", id)) == normalize(turret))
+        .ok_or(format!("unknown turret `{}`", turret))?;
+    let body = BodyIdentityIds::iter()
+        .find(|id| normalize(&format!("{:?}", id)) == normalize(body))
+        .ok_or(format!("unknown body `{}`", body))?;
+
+    Ok(TankBuild { turret, body, ..build.clone() })
+}
+
+fn parse_build(stats: &str) -> Result<[usize; UpgradeStats::COUNT], String> {
+    let stats = stats.split('/')
+        .map(|stat| stat.trim().parse::<usize>().map_err(|_| format!("invalid stat investment `{}`", stat)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if stats.iter().any(|&stat| stat > MAX_STAT_INVESTMENT) {
+        return Err(format!("stat investments cannot exceed {}", MAX_STAT_INVESTMENT));
+    }
+
+    stats.try_into().map_err(|_| format!("a build needs {} stat investments", UpgradeStats::COUNT))
+}
+
+fn run(mut args: Vec<String>) -> Result<String, String> {
+    take_definitions_arg(&mut args)?;
+
+    let mut build = TankBuild::default();
+    if let Some(level) = take_arg(&mut args, "--level")? {
+        build.level = level.parse::<usize>()
+            .ok()
+            .filter(|level| (1..=MAX_LEVEL).contains(level))
+            .ok_or(format!("the level must be between 1 and {}", MAX_LEVEL))?;
+    }
+
+    if let Some(stats) = take_arg(&mut args, "--build")? {
+        build.stat_investments = parse_build(&stats)?;
+    }
+
+    if args.is_empty() || args.iter().any(|arg| arg.starts_with("--")) {
+        return Err(USAGE.to_string());
+    }
+
+    let tanks = args.iter()
+        .map(|spec| parse_tank(spec, &build).and_then(|build| TankBalance::compute(&build)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(TankBalance::to_table(&tanks))
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(table) => {
+            print!("{}", table);
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
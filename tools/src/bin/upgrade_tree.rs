@@ -0,0 +1,64 @@
+use std::process::ExitCode;
+use shared::game::upgrade_tree::UpgradeTree;
+use tools::take_definitions_arg;
+
+const USAGE: &str = "\
+Usage: upgrade_tree [check|dot|json] [turret|body] [--definitions <dir>]
+
+    check    Reports problems in the upgrade trees (default).
+    dot      Exports a tree in Graphviz DOT format.
+    json     Exports a tree as JSON.
+
+    turret   Only the turret tree (default for exports).
+    body     Only the body tree.
+
+    --definitions <dir>    Loads definitions from a directory instead of the embedded ones.";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(error) = take_definitions_arg(&mut args) {
+        eprintln!("{}", error);
+        return ExitCode::FAILURE;
+    }
+
+    let command = args.first().map(String::as_str).unwrap_or("check");
+    let trees = match args.get(1).map(String::as_str) {
+        None if command == "check" => vec![UpgradeTree::turrets(), UpgradeTree::bodies()],
+        None | Some("turret") => vec![UpgradeTree::turrets()],
+        Some("body") => vec![UpgradeTree::bodies()],
+        Some(_) => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command {
+        "check" => {
+            let mut valid = true;
+            for tree in trees.iter() {
+                let issues = tree.validate();
+                println!("{} tree: {} identities, {} issues", tree.kind, tree.nodes.len(), issues.len());
+
+                for issue in issues.iter() {
+                    println!("    {}", issue);
+                }
+
+                valid &= issues.is_empty();
+            }
+
+            if valid { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        },
+        "dot" => {
+            print!("{}", trees[0].to_dot());
+            ExitCode::SUCCESS
+        },
+        "json" => {
+            println!("{}", trees[0].to_json());
+            ExitCode::SUCCESS
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
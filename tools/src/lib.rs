@@ -0,0 +1,22 @@
+use std::path::Path;
+use shared::game::definitions::{set_definitions, Definitions};
+
+/// Loads the definitions in `directory` in place of the embedded ones.
+pub fn load_definitions(directory: &Path) -> Result<(), String> {
+    let definitions = Definitions::load_dir(directory).map_err(|error| error.to_string())?;
+    set_definitions(definitions);
+    Ok(())
+}
+
+/// Removes `--definitions <dir>` from the arguments, loading the definitions if present.
+pub fn take_definitions_arg(args: &mut Vec<String>) -> Result<(), String> {
+    let Some(index) = args.iter().position(|arg| arg == "--definitions") else { return Ok(()); };
+    if index + 1 >= args.len() {
+        return Err("--definitions expects a directory".to_string());
+    }
+
+    let directory = args.remove(index + 1);
+    args.remove(index);
+
+    load_definitions(Path::new(&directory))
+}
@@ -1,4 +1,4 @@
-use shared::{fuzzy_compare, game::{body::{get_body_base_identity, BodyIdentity, BodyRenderingHints}, entity::FICTITIOUS_TANK_RADIUS, theme::{PLAYER_FILL, PLAYER_STROKE, SMASHER_GUARD_FILL, SMASHER_GUARD_STROKE, STROKE_SIZE, TURRET_FILL, TURRET_STROKE}, turret::{get_turret_base_identity, TurretRenderingHints, TurretStructure}}, lerp, utils::{color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
+use shared::{fuzzy_compare, game::{body::{BodyIdentity, BodyIdentityIds, BodyRenderingHints}, entity::FICTITIOUS_TANK_RADIUS, theme::{PLAYER_FILL, PLAYER_STROKE, SMASHER_GUARD_FILL, SMASHER_GUARD_STROKE, STROKE_SIZE, TURRET_FILL, TURRET_STROKE}, turret::{TurretIdentityIds, TurretRenderingHints, TurretStructure}}, lerp, utils::{color::Color, interpolatable::Interpolatable, vec2::Vector2D}};
 use web_sys::MouseEvent;
 
 use crate::{canvas2d::{Canvas2d, Transform}, core::{BoundingRect, DeletionEffects, ElementType, Events, HoverEffects, UiElement}, DEBUG};
@@ -36,8 +36,8 @@ impl Default for Tank {
             is_animating: Default::default(),
             opacity: Interpolatable::new(1.0),
             destroyed: Default::default(),
-            body_identity: get_body_base_identity(),
-            turret_structure: get_turret_base_identity(),
+            body_identity: BodyIdentity::try_from(BodyIdentityIds::Base).unwrap(),
+            turret_structure: TurretStructure::try_from(TurretIdentityIds::Base).unwrap(),
             stroke: STROKE_SIZE,
             ticks: Default::default(),
         }
@@ -167,8 +167,12 @@ impl UiElement for Tank {
         context.global_alpha(self.opacity.value);
         context.rotate(self.angle);
 
-        Tank::render_turrets(context, self.stroke, self.radius, &self.turret_structure, &vec![Interpolatable::new(1.0); self.turret_structure.turrets.len()]);
+        let turret_structure = self.turret_structure.clone().with_body(&self.body_identity);
+        let turret_lengths = vec![Interpolatable::new(1.0); turret_structure.turrets.len()];
+
+        Tank::render_turrets(context, self.stroke, self.radius, &turret_structure, &turret_lengths);
         Tank::render_body(context, self.stroke, &self.body_identity, self.radius, PLAYER_FILL, PLAYER_STROKE);
+        Tank::render_auto_turrets(context, self.stroke, self.radius, &turret_structure, &turret_lengths, &[]);
         
         if DEBUG {
             context.save();
@@ -224,6 +228,10 @@ impl Tank {
         let size_factor = radius / FICTITIOUS_TANK_RADIUS;
 
         for (i, turret) in turret_structure.turrets.iter().enumerate() {
+            if turret.auto.is_some() {
+                continue;
+            }
+
             context.save();
             context.rotate(turret.angle);
             context.translate(
@@ -324,6 +332,43 @@ impl Tank {
         context.restore();
     }
 
+    /// Renders the automatic turrets mounted on top of the body.
+    /// `angles` holds each turret's angle relative to the tank, falling back to its resting angle.
+    pub fn render_auto_turrets(context: &mut Canvas2d, stroke: f32, radius: f32, turret_structure: &TurretStructure, turret_lengths: &[Interpolatable<f32>], angles: &[f32]) {
+        context.save();
+
+        context.fill_style(TURRET_FILL);
+        context.stroke_style(TURRET_STROKE);
+        context.set_stroke_size(stroke);
+
+        let size_factor = radius / FICTITIOUS_TANK_RADIUS;
+
+        for (i, turret) in turret_structure.turrets.iter().enumerate() {
+            if turret.auto.is_none() {
+                continue;
+            }
+
+            let mount = Vector2D::from_polar(turret.x_offset * size_factor, turret.angle);
+            let length = turret_lengths.get(i).map(|length| length.value).unwrap_or(1.0) * turret.length * size_factor;
+            let width = turret.width * size_factor;
+
+            context.save();
+            context.translate(mount.x, mount.y);
+            context.rotate(angles.get(i).copied().unwrap_or(turret.angle));
+
+            context.fill_rect(0.0, -width / 2.0, length, width);
+            context.stroke_rect(0.0, -width / 2.0, length, width);
+
+            context.begin_arc(0.0, 0.0, width * 0.8, std::f32::consts::TAU);
+            context.fill();
+            context.stroke();
+
+            context.restore();
+        }
+
+        context.restore();
+    }
+
     pub fn render_body(context: &mut Canvas2d, stroke_size: f32, body_identity: &BodyIdentity, radius: f32, fill: Color, stroke: Color) {
         context.save();
 
@@ -350,7 +395,59 @@ impl Tank {
                     context.stroke();
 
                     context.restore();
-                }
+                },
+                BodyRenderingHints::Spikes { spikes, length } => {
+                    let outer_radius = length * radius;
+
+                    context.save();
+
+                    context.fill_style(SMASHER_GUARD_FILL);
+                    context.stroke_style(SMASHER_GUARD_STROKE);
+
+                    context.begin_path();
+                    context.move_to(outer_radius, 0.0);
+                    for i in 1..=(spikes * 2) {
+                        let (x_angle, y_angle) = (std::f32::consts::PI * i as f32 / spikes as f32).sin_cos();
+                        let r = if i % 2 == 0 { outer_radius } else { radius };
+                        context.line_to(r * y_angle, r * x_angle);
+                    }
+                    context.close_path();
+                    context.fill();
+                    context.stroke();
+
+                    context.restore();
+                },
+                BodyRenderingHints::RamPlate { thickness, arc } => {
+                    let arc = arc.to_radians();
+
+                    context.save();
+
+                    context.fill_style(SMASHER_GUARD_FILL);
+                    context.stroke_style(SMASHER_GUARD_STROKE);
+
+                    context.rotate(-arc / 2.0);
+                    context.begin_path();
+                    context.move_to(0.0, 0.0);
+                    context.arc(0.0, 0.0, thickness * radius, arc);
+                    context.close_path();
+                    context.fill();
+                    context.stroke();
+
+                    context.restore();
+                },
+                BodyRenderingHints::Armor { thickness } => {
+                    context.save();
+
+                    context.fill_style(SMASHER_GUARD_FILL);
+                    context.stroke_style(SMASHER_GUARD_STROKE);
+
+                    context.begin_arc(0.0, 0.0, thickness * radius, std::f32::consts::TAU);
+                    context.fill();
+                    context.stroke();
+
+                    context.restore();
+                },
+                BodyRenderingHints::Studs { .. } => ()
             }
         }
 
@@ -361,6 +458,21 @@ impl Tank {
         context.fill();
         context.stroke();
 
+        // Studs sit on top of the body.
+        for &hint in body_identity.render_hints.iter() {
+            if let BodyRenderingHints::Studs { studs, size } = hint {
+                context.fill_style(SMASHER_GUARD_FILL);
+                context.stroke_style(SMASHER_GUARD_STROKE);
+
+                for i in 0..studs {
+                    let (x_angle, y_angle) = (std::f32::consts::TAU * i as f32 / studs as f32).sin_cos();
+                    context.begin_arc(0.6 * radius * y_angle, 0.6 * radius * x_angle, size * radius, std::f32::consts::TAU);
+                    context.fill();
+                    context.stroke();
+                }
+            }
+        }
+
         context.restore();
     }
 